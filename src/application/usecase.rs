@@ -1,3 +1,11 @@
+pub mod generate_random_team;
+pub mod load_battle;
+pub mod load_replay;
 pub mod load_static_data;
+pub mod record_battle;
+pub mod replay_battle;
+pub mod run_battle;
+pub mod save_battle;
+pub mod save_replay;
 pub mod select_party;
 pub mod start_new_battle;
@@ -0,0 +1,482 @@
+use crate::application::dto::loaded_static_data::LoadedStaticData;
+use crate::application::usecase::select_party::{PartyError, SelectPartyUsecase};
+use crate::domain::model::battle_rng::{BattleRng, SeededRng};
+use crate::domain::model::format::{Format, LevelRule};
+use crate::domain::model::r#move::{DamageClass, Move};
+use crate::domain::model::nature::Nature;
+use crate::domain::model::pokemon::Pokemon;
+use crate::domain::model::pokemon_build::{MAX_EV, MAX_MOVES, PokemonBuild, StatSpread};
+use crate::domain::model::stats::StatName;
+use crate::domain::model::team::Team;
+use crate::domain::service::format_validator::FormatValidator;
+use std::collections::HashSet;
+use std::fmt;
+
+/// 持たせる候補の道具（技や特性を問わず使いやすいもの）
+const HELD_ITEMS: [&str; 12] = [
+    "leftovers",
+    "life-orb",
+    "focus-sash",
+    "sitrus-berry",
+    "lum-berry",
+    "rocky-helmet",
+    "expert-belt",
+    "clear-amulet",
+    "covert-cloak",
+    "mental-herb",
+    "safety-goggles",
+    "shell-bell",
+];
+
+/// これ以上の素早さ種族値なら素早さに努力値を振る
+const FAST_SPEED: u32 = 80;
+
+/// ランダムなパーティを作れない場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TeamGenerationError {
+    /// ルールに沿って登録できるポケモンが選出する数より少ない
+    NotEnoughCandidates { required: usize, available: usize },
+    /// 作ったパーティが図鑑データやルールと合わない
+    Party(PartyError),
+}
+
+impl fmt::Display for TeamGenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TeamGenerationError::NotEnoughCandidates {
+                required,
+                available,
+            } => write!(
+                f,
+                "登録できるポケモンが足りません（{}体必要ですが{}体です）",
+                required, available
+            ),
+            TeamGenerationError::Party(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// 種族値から決める役割。努力値・性格・技の分類を選ぶのに使う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    PhysicalSweeper,
+    SpecialSweeper,
+    PhysicalTank,
+    SpecialTank,
+}
+
+impl Role {
+    fn of(pokemon: &Pokemon) -> Self {
+        let physical =
+            base_stat(pokemon, &StatName::Attack) >= base_stat(pokemon, &StatName::SpecialAttack);
+        let fast = base_stat(pokemon, &StatName::Speed) >= FAST_SPEED;
+        match (physical, fast) {
+            (true, true) => Role::PhysicalSweeper,
+            (false, true) => Role::SpecialSweeper,
+            (true, false) => Role::PhysicalTank,
+            (false, false) => Role::SpecialTank,
+        }
+    }
+
+    fn damage_class(self) -> DamageClass {
+        match self {
+            Role::PhysicalSweeper | Role::PhysicalTank => DamageClass::Physical,
+            Role::SpecialSweeper | Role::SpecialTank => DamageClass::Special,
+        }
+    }
+
+    fn nature(self) -> Nature {
+        match self {
+            Role::PhysicalSweeper => Nature::Jolly,
+            Role::SpecialSweeper => Nature::Timid,
+            Role::PhysicalTank => Nature::Adamant,
+            Role::SpecialTank => Nature::Modest,
+        }
+    }
+
+    /// 252・252・4 の努力値
+    fn evs(self) -> StatSpread {
+        let (first, second, rest) = match self {
+            Role::PhysicalSweeper => (StatName::Attack, StatName::Speed, StatName::Hp),
+            Role::SpecialSweeper => (StatName::SpecialAttack, StatName::Speed, StatName::Hp),
+            Role::PhysicalTank => (StatName::Hp, StatName::Attack, StatName::Defense),
+            Role::SpecialTank => (
+                StatName::Hp,
+                StatName::SpecialAttack,
+                StatName::SpecialDefense,
+            ),
+        };
+        let mut evs = StatSpread::default();
+        evs.set(&first, MAX_EV);
+        evs.set(&second, MAX_EV);
+        evs.set(&rest, 4);
+        evs
+    }
+}
+
+fn base_stat(pokemon: &Pokemon, stat: &StatName) -> u32 {
+    pokemon
+        .stats
+        .iter()
+        .find(|s| s.stat == *stat)
+        .map(|s| s.base_stat)
+        .unwrap_or(0)
+}
+
+/// 対戦のルールに沿ったランダムなパーティを作る。
+/// シードが同じなら同じパーティになるため、モンテカルロ試行やテストのフィクスチャに使う
+pub struct GenerateRandomTeamUsecase<'a> {
+    data: &'a LoadedStaticData,
+    seed: u64,
+}
+
+impl<'a> GenerateRandomTeamUsecase<'a> {
+    pub fn new(data: &'a LoadedStaticData) -> Self {
+        Self { data, seed: 0 }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn execute(&self, format: &Format) -> Result<Team, TeamGenerationError> {
+        let mut rng = SeededRng::new(self.seed);
+        let level = match format.level {
+            LevelRule::Flat(level) | LevelRule::Cap(level) => level,
+        };
+        let evolves: HashSet<&str> = self
+            .data
+            .pokemon_species
+            .iter()
+            .filter_map(|species| species.evolves_from_species.as_deref())
+            .collect();
+        // 型を作るのは重いため、先にポケモンを選んでから選んだものだけ型を作る
+        let mut candidates: Vec<&Pokemon> = self
+            .data
+            .pokemons
+            .iter()
+            .filter(|pokemon| !evolves.contains(self.species_name(pokemon)))
+            .collect();
+
+        let mut members: Vec<PokemonBuild> = Vec::new();
+        let mut restricted = 0;
+        while members.len() < format.team_size && !candidates.is_empty() {
+            let pokemon = candidates.swap_remove(rng.next_below(candidates.len() as u32) as usize);
+            let species_name = self.species_name(pokemon);
+            if members.iter().any(|other| {
+                FormatValidator::species_name(&other.species, &self.data.pokemons) == species_name
+            }) {
+                continue;
+            }
+            let Some(member) = self.build_member(pokemon, format, level, &mut rng) else {
+                continue;
+            };
+            let is_restricted =
+                FormatValidator::is_restricted(format, &member, &self.data.pokemons);
+            if is_restricted && restricted >= format.max_restricted {
+                continue;
+            }
+            if is_restricted {
+                restricted += 1;
+            }
+            members.push(member);
+        }
+        if members.len() < format.bring_count {
+            return Err(TeamGenerationError::NotEnoughCandidates {
+                required: format.bring_count,
+                available: members.len(),
+            });
+        }
+        let members = self.assign_items(members, &mut rng);
+
+        let team = SelectPartyUsecase::new(self.data)
+            .create_team("random", members)
+            .map_err(TeamGenerationError::Party)?;
+        FormatValidator::validate(
+            format,
+            &team,
+            &self.data.pokemons,
+            &self.data.pokemon_species,
+        )
+        .map_err(|violations| TeamGenerationError::Party(PartyError::Format(violations)))?;
+        Ok(team)
+    }
+
+    /// 種族の名前（種族のデータがなければポケモンの名前）
+    fn species_name<'p>(&self, pokemon: &'p Pokemon) -> &'p str {
+        pokemon.species.as_deref().unwrap_or(&pokemon.name)
+    }
+
+    /// 1体分の型を作る。ルールに合わない、またはタイプ一致の攻撃技を覚えない場合は None
+    fn build_member(
+        &self,
+        pokemon: &Pokemon,
+        format: &Format,
+        level: u32,
+        rng: &mut SeededRng,
+    ) -> Option<PokemonBuild> {
+        if pokemon.abilities.is_empty() {
+            return None;
+        }
+        let ability = &pokemon.abilities[rng.next_below(pokemon.abilities.len() as u32) as usize];
+        let role = Role::of(pokemon);
+        let moves = self.choose_moves(pokemon, role, rng)?;
+        let mut member = PokemonBuild::new(&pokemon.name, &ability.ability.name)
+            .with_moves(&moves)
+            .with_nature(role.nature())
+            .with_evs(role.evs());
+        member.level = level;
+        if !format.no_tera
+            && let Some(pokemon_type) = pokemon.types.first()
+            && self
+                .data
+                .types
+                .iter()
+                .any(|known| known.name == pokemon_type.type_info.name)
+        {
+            member = member.with_tera_type(&pokemon_type.type_info.name);
+        }
+        FormatValidator::member_violations(
+            format,
+            0,
+            &member,
+            &self.data.pokemons,
+            &self.data.pokemon_species,
+        )
+        .is_empty()
+        .then_some(member)
+    }
+
+    /// タイプ一致の攻撃技を1つ選び、残りを役割に合った技から選ぶ
+    fn choose_moves(
+        &self,
+        pokemon: &Pokemon,
+        role: Role,
+        rng: &mut SeededRng,
+    ) -> Option<Vec<&str>> {
+        let learnable: Vec<&Move> = self
+            .data
+            .moves
+            .iter()
+            .filter(|candidate| pokemon.can_learn(&candidate.name))
+            .collect();
+        let is_stab = |candidate: &Move| {
+            !candidate.power.is_none()
+                && pokemon
+                    .types
+                    .iter()
+                    .any(|t| t.type_info.name == candidate.type_info.name)
+        };
+        let stab: Vec<&Move> = learnable
+            .iter()
+            .copied()
+            .filter(|candidate| is_stab(candidate) && candidate.damage_class == role.damage_class())
+            .collect();
+        let stab = if stab.is_empty() {
+            learnable
+                .iter()
+                .copied()
+                .filter(|candidate| is_stab(candidate))
+                .collect()
+        } else {
+            stab
+        };
+        if stab.is_empty() {
+            return None;
+        }
+        let first = stab[rng.next_below(stab.len() as u32) as usize];
+
+        let mut pool: Vec<&Move> = learnable
+            .iter()
+            .copied()
+            .filter(|candidate| {
+                candidate.name != first.name
+                    && (candidate.damage_class.is_status()
+                        || candidate.damage_class == role.damage_class())
+            })
+            .collect();
+        let mut moves = vec![first.name.as_str()];
+        while moves.len() < MAX_MOVES && !pool.is_empty() {
+            let chosen = pool.swap_remove(rng.next_below(pool.len() as u32) as usize);
+            moves.push(chosen.name.as_str());
+        }
+        Some(moves)
+    }
+
+    /// 道具が重複しないように持たせる。候補の道具を使い切ったら何も持たせない
+    fn assign_items(&self, members: Vec<PokemonBuild>, rng: &mut SeededRng) -> Vec<PokemonBuild> {
+        let mut items: Vec<&str> = HELD_ITEMS
+            .into_iter()
+            .filter(|name| self.data.items.iter().any(|item| item.name == *name))
+            .collect();
+        members
+            .into_iter()
+            .map(|member| {
+                if items.is_empty() {
+                    return member;
+                }
+                let item = items.swap_remove(rng.next_below(items.len() as u32) as usize);
+                member.with_item(item)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::test_support::{
+        test_ability, test_item, test_learnset, test_move, test_pokemon,
+    };
+
+    fn static_data() -> LoadedStaticData {
+        let mut garchomp = test_pokemon("garchomp", &["dragon", "ground"], 108, 102);
+        test_learnset(
+            &mut garchomp,
+            "rough-skin",
+            &[
+                "earthquake",
+                "dragon-claw",
+                "swords-dance",
+                "rock-slide",
+                "surf",
+            ],
+        );
+        let mut gholdengo = test_pokemon("gholdengo", &["steel", "ghost"], 87, 100);
+        gholdengo.stats[1].base_stat = 60;
+        gholdengo.stats[3].base_stat = 133;
+        test_learnset(
+            &mut gholdengo,
+            "good-as-gold",
+            &["make-it-rain", "shadow-ball", "nasty-plot"],
+        );
+        let mut pikachu = test_pokemon("pikachu", &["electric"], 35, 55);
+        test_learnset(&mut pikachu, "static", &["nasty-plot"]);
+
+        LoadedStaticData {
+            items: vec![test_item("leftovers"), test_item("life-orb")],
+            pokemons: vec![garchomp, gholdengo, pikachu],
+            moves: vec![
+                test_move(
+                    "earthquake",
+                    "ground",
+                    "physical",
+                    Some(100),
+                    "all-other-pokemon",
+                ),
+                test_move(
+                    "dragon-claw",
+                    "dragon",
+                    "physical",
+                    Some(80),
+                    "selected-pokemon",
+                ),
+                test_move("swords-dance", "normal", "status", None, "user"),
+                test_move("rock-slide", "rock", "physical", Some(75), "all-opponents"),
+                test_move("surf", "water", "special", Some(90), "all-other-pokemon"),
+                test_move(
+                    "make-it-rain",
+                    "steel",
+                    "special",
+                    Some(120),
+                    "all-opponents",
+                ),
+                test_move(
+                    "shadow-ball",
+                    "ghost",
+                    "special",
+                    Some(80),
+                    "selected-pokemon",
+                ),
+                test_move("nasty-plot", "dark", "status", None, "user"),
+            ],
+            pokemon_species: vec![],
+            types: vec![],
+            abilities: vec![
+                test_ability("rough-skin"),
+                test_ability("good-as-gold"),
+                test_ability("static"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_generate_random_team() {
+        let data = static_data();
+        let format = Format {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            battle_format: BattleFormat::Singles,
+            level: LevelRule::Flat(50),
+            team_size: 6,
+            bring_count: 2,
+            item_clause: true,
+            species_clause: true,
+            banned_species: vec![],
+            restricted_species: vec![],
+            max_restricted: 0,
+            no_mythicals: false,
+            allowed_pokedexes: vec![],
+            sleep_clause: false,
+            ohko_clause: false,
+            evasion_clause: false,
+            no_tera: false,
+        };
+        let usecase = GenerateRandomTeamUsecase::new(&data).with_seed(7);
+
+        let team = usecase.execute(&format).unwrap();
+        assert_eq!(team, usecase.execute(&format).unwrap());
+        assert_eq!(team.members.len(), 2);
+        let garchomp = team
+            .members
+            .iter()
+            .find(|member| member.species == "garchomp")
+            .unwrap();
+        assert_eq!(garchomp.nature, Nature::Jolly);
+        assert_eq!(garchomp.evs.attack, MAX_EV);
+        assert_eq!(garchomp.moves.len(), 4);
+        assert!(garchomp.moves.iter().all(|choice| choice.name != "surf"));
+        assert_ne!(team.members[0].held_item, team.members[1].held_item);
+    }
+
+    #[test]
+    fn test_generate_random_team_picks_one_form_per_species() {
+        let mut data = static_data();
+        data.pokemons = ["rotom-wash", "rotom-heat"]
+            .iter()
+            .map(|name| {
+                let mut rotom = test_pokemon(name, &["electric", "ghost"], 65, 86);
+                rotom.species = Some("rotom".to_string());
+                test_learnset(&mut rotom, "static", &["shadow-ball", "nasty-plot"]);
+                rotom
+            })
+            .collect();
+        let format = Format {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            battle_format: BattleFormat::Singles,
+            level: LevelRule::Flat(50),
+            team_size: 6,
+            bring_count: 1,
+            item_clause: false,
+            species_clause: true,
+            banned_species: vec![],
+            restricted_species: vec![],
+            max_restricted: 0,
+            no_mythicals: false,
+            allowed_pokedexes: vec![],
+            sleep_clause: false,
+            ohko_clause: false,
+            evasion_clause: false,
+            no_tera: false,
+        };
+
+        let team = GenerateRandomTeamUsecase::new(&data)
+            .with_seed(3)
+            .execute(&format)
+            .unwrap();
+        assert_eq!(team.members.len(), 1);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::repository::battle_snapshot_repository::BattleSnapshotRepository;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// 保存したバトルの状態を読み込み、続きから再開できるようにする
+pub struct LoadBattleUsecase<R>
+where
+    R: BattleSnapshotRepository,
+{
+    repository: R,
+}
+
+impl<R> LoadBattleUsecase<R>
+where
+    R: BattleSnapshotRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub fn execute(&self, name: &str) -> Result<Battle, RepositoryError> {
+        self.repository.load_snapshot(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::usecase::save_battle::SaveBattleUsecase;
+    use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::battle_engine::BattleEngine;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+    use crate::infrastructure::persistence::file_battle_snapshot_repository::FileBattleSnapshotRepository;
+    use std::fs;
+
+    #[test]
+    fn test_resume_saved_battle() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(11);
+        let engine = BattleEngine::new(TypeChart::default());
+        let actions = || {
+            (0..2)
+                .map(|side| {
+                    ChosenAction::new(
+                        SlotPosition::new(side, 0),
+                        BattleAction::UseMove {
+                            move_index: 0,
+                            target: None,
+                            terastallize: false,
+                        },
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        engine.execute_turn(&mut battle, actions()).unwrap();
+
+        SaveBattleUsecase::new(FileBattleSnapshotRepository::new(
+            "test_data_for_load_battle",
+        ))
+        .execute("battle", &battle)
+        .unwrap();
+        let mut resumed = LoadBattleUsecase::new(FileBattleSnapshotRepository::new(
+            "test_data_for_load_battle",
+        ))
+        .execute("battle")
+        .unwrap();
+        fs::remove_dir_all("test_data_for_load_battle").unwrap();
+
+        // 乱数の状態も復元されるため、再開後のターンは元のバトルと同じになる
+        assert_eq!(
+            engine.execute_turn(&mut resumed, actions()).unwrap(),
+            engine.execute_turn(&mut battle, actions()).unwrap()
+        );
+    }
+}
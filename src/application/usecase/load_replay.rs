@@ -0,0 +1,100 @@
+use crate::domain::model::replay::Replay;
+use crate::domain::repository::replay_repository::ReplayRepository;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// 保存したリプレイを読み込む。再生は ReplayBattleUsecase で行う
+pub struct LoadReplayUsecase<R>
+where
+    R: ReplayRepository,
+{
+    repository: R,
+}
+
+impl<R> LoadReplayUsecase<R>
+where
+    R: ReplayRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub fn execute(&self, name: &str) -> Result<Replay, RepositoryError> {
+        self.repository.load_replay(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::usecase::record_battle::RecordBattleUsecase;
+    use crate::application::usecase::replay_battle::ReplayBattleUsecase;
+    use crate::application::usecase::save_replay::SaveReplayUsecase;
+    use crate::domain::model::battle::Battle;
+    use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::battle_engine::BattleEngine;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+    use crate::infrastructure::persistence::file_replay_repository::FileReplayRepository;
+    use std::fs;
+
+    #[test]
+    fn test_save_and_replay_loaded_replay() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(5);
+        let engine = BattleEngine::new(TypeChart::default());
+        let mut recorder = RecordBattleUsecase::new(engine.clone(), battle);
+        let tackle = |side| {
+            ChosenAction::new(
+                SlotPosition::new(side, 0),
+                BattleAction::UseMove {
+                    move_index: 0,
+                    target: None,
+                    terastallize: false,
+                },
+            )
+        };
+        while !recorder.battle().is_over() {
+            recorder.play_turn(vec![tackle(0), tackle(1)]).unwrap();
+        }
+        let replay = recorder.finish();
+
+        SaveReplayUsecase::new(FileReplayRepository::new("test_data_for_load_replay"))
+            .execute("battle", &replay)
+            .unwrap();
+        let loaded = LoadReplayUsecase::new(FileReplayRepository::new("test_data_for_load_replay"))
+            .execute("battle")
+            .unwrap();
+        fs::remove_dir_all("test_data_for_load_replay").unwrap();
+
+        assert_eq!(loaded.turns, replay.turns);
+        let replayed = ReplayBattleUsecase::new(engine).execute(&loaded).unwrap();
+        assert_eq!(replayed.result, replay.result);
+        assert_eq!(
+            LoadReplayUsecase::new(FileReplayRepository::default())
+                .execute("../escape")
+                .unwrap_err(),
+            RepositoryError::InvalidName("../escape".to_string())
+        );
+    }
+}
@@ -5,7 +5,9 @@ use crate::domain::repository::move_repository::MoveRepository;
 use crate::domain::repository::pokemon_repository::PokemonRepository;
 use crate::domain::repository::pokemon_species_repository::PokemonSpeciesRepository;
 use crate::domain::repository::type_repository::TypeRepository;
+use crate::domain::service::availability::retain_available;
 
+/// 静的データを読み込む。既定ではスカーレット・バイオレットに登場しないポケモン・技・特性・道具を除く
 pub struct LoadStaticDataUsecase<I, P, M, S, T, A>
 where
     I: ItemRepository,
@@ -21,6 +23,7 @@ where
     pokemon_species_repository: S,
     type_repository: T,
     ability_repository: A,
+    include_unavailable: bool,
 }
 
 impl<I, P, M, S, T, A> LoadStaticDataUsecase<I, P, M, S, T, A>
@@ -47,16 +50,30 @@ where
             pokemon_species_repository,
             type_repository,
             ability_repository,
+            include_unavailable: false,
         }
     }
 
+    /// スカーレット・バイオレットに登場しないデータも読み込む
+    pub fn with_unavailable(mut self, include_unavailable: bool) -> Self {
+        self.include_unavailable = include_unavailable;
+        self
+    }
+
     pub fn execute(&self) -> LoadedStaticData {
-        let items = self.item_repository.find_all_items();
-        let pokemons = self.pokemon_repository.find_all_pokemons();
-        let moves = self.move_repository.find_all_moves();
+        let mut items = self.item_repository.find_all_items();
+        let mut pokemons = self.pokemon_repository.find_all_pokemons();
+        let mut moves = self.move_repository.find_all_moves();
         let pokemon_species = self.pokemon_species_repository.find_all_species();
         let types = self.type_repository.find_all_types();
-        let abilities = self.ability_repository.find_all_abilities();
+        let mut abilities = self.ability_repository.find_all_abilities();
+
+        if !self.include_unavailable {
+            retain_available(&mut items);
+            retain_available(&mut pokemons);
+            retain_available(&mut moves);
+            retain_available(&mut abilities);
+        }
 
         LoadedStaticData {
             items,
@@ -72,6 +89,13 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::model::ability::Ability;
+    use crate::domain::model::item::Item;
+    use crate::domain::model::r#move::Move;
+    use crate::domain::model::poke_type::PokeType;
+    use crate::domain::model::pokemon::Pokemon;
+    use crate::domain::model::pokemon_species::PokemonSpecies;
+    use crate::domain::test_support::test_pokemon;
     use crate::infrastructure::persistence::file_ability_repository::FileAbilityRepository;
     use crate::infrastructure::persistence::file_item_repository::FileItemRepository;
     use crate::infrastructure::persistence::file_move_repository::FileMoveRepository;
@@ -105,5 +129,69 @@ mod tests {
         assert!(!loaded_data.pokemon_species.is_empty());
         assert!(!loaded_data.types.is_empty());
         assert!(!loaded_data.abilities.is_empty());
+        assert!(
+            loaded_data
+                .pokemons
+                .iter()
+                .all(|pokemon| pokemon.name != "abomasnow-mega")
+        );
+    }
+
+    /// 登場しないポケモンだけを返すリポジトリ
+    struct UnavailableOnly;
+
+    impl ItemRepository for UnavailableOnly {
+        fn find_all_items(&self) -> Vec<Item> {
+            vec![]
+        }
+    }
+
+    impl PokemonRepository for UnavailableOnly {
+        fn find_all_pokemons(&self) -> Vec<Pokemon> {
+            vec![test_pokemon("abomasnow-mega", &["grass", "ice"], 90, 100)]
+        }
+    }
+
+    impl MoveRepository for UnavailableOnly {
+        fn find_all_moves(&self) -> Vec<Move> {
+            vec![]
+        }
+    }
+
+    impl PokemonSpeciesRepository for UnavailableOnly {
+        fn find_all_species(&self) -> Vec<PokemonSpecies> {
+            vec![]
+        }
+    }
+
+    impl TypeRepository for UnavailableOnly {
+        fn find_all_types(&self) -> Vec<PokeType> {
+            vec![]
+        }
+    }
+
+    impl AbilityRepository for UnavailableOnly {
+        fn find_all_abilities(&self) -> Vec<Ability> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_load_static_data_with_unavailable() {
+        let usecase = || {
+            LoadStaticDataUsecase::new(
+                UnavailableOnly,
+                UnavailableOnly,
+                UnavailableOnly,
+                UnavailableOnly,
+                UnavailableOnly,
+                UnavailableOnly,
+            )
+        };
+
+        assert!(usecase().execute().pokemons.is_empty());
+        let loaded_data = usecase().with_unavailable(true).execute();
+        assert_eq!(loaded_data.pokemons.len(), 1);
+        assert_eq!(loaded_data.pokemons[0].name, "abomasnow-mega");
     }
 }
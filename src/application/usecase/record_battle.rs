@@ -0,0 +1,211 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::{ChosenAction, SlotPosition};
+use crate::domain::model::battle_event::BattleEvent;
+use crate::domain::model::replay::{Replay, ReplayTurn};
+use crate::domain::service::battle_engine::{BattleEngine, BattleError};
+
+/// リプレイに記録できない操作が行われた場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    Battle(BattleError),
+    /// ターンを1つも記録していないうちに交代が行われた（交代は直前のターンの記録に加える）
+    ReplacementBeforeFirstTurn,
+}
+
+impl From<BattleError> for RecordError {
+    fn from(error: BattleError) -> Self {
+        Self::Battle(error)
+    }
+}
+
+/// バトルを進めながら、選ばれた行動と発生した出来事をリプレイとして記録する
+pub struct RecordBattleUsecase {
+    engine: BattleEngine,
+    battle: Battle,
+    replay: Replay,
+}
+
+impl RecordBattleUsecase {
+    /// 記録を始める時点のバトルを受け取り、その状態とエンジンのターン数の上限を記録する
+    pub fn new(engine: BattleEngine, battle: Battle) -> Self {
+        let replay = Replay::new(battle.clone(), engine.turn_limit());
+        Self {
+            engine,
+            battle,
+            replay,
+        }
+    }
+
+    pub fn battle(&self) -> &Battle {
+        &self.battle
+    }
+
+    /// 1ターンを実行して記録する。不正な行動は記録しない
+    pub fn play_turn(
+        &mut self,
+        actions: Vec<ChosenAction>,
+    ) -> Result<Vec<BattleEvent>, BattleError> {
+        let events = self
+            .engine
+            .execute_turn(&mut self.battle, actions.clone())?;
+        self.replay.turns.push(ReplayTurn {
+            actions,
+            replacements: Vec::new(),
+            events: events.clone(),
+        });
+        self.replay.result = self.battle.result;
+        Ok(events)
+    }
+
+    /// ひんし後の交代を実行し、直前のターンの記録に加える
+    pub fn submit_replacements(
+        &mut self,
+        choices: Vec<(SlotPosition, usize)>,
+    ) -> Result<Vec<BattleEvent>, RecordError> {
+        let Some(turn) = self.replay.turns.last_mut() else {
+            return Err(RecordError::ReplacementBeforeFirstTurn);
+        };
+        let events = self
+            .engine
+            .submit_replacements(&mut self.battle, choices.clone())?;
+        turn.replacements.extend(choices);
+        turn.events.extend(events.iter().cloned());
+        Ok(events)
+    }
+
+    pub fn finish(self) -> Replay {
+        self.replay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_action::BattleAction;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    #[test]
+    fn test_record_battle() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(7);
+        let mut usecase = RecordBattleUsecase::new(BattleEngine::new(TypeChart::default()), battle);
+
+        let action = ChosenAction::new(
+            SlotPosition::new(0, 0),
+            BattleAction::UseMove {
+                move_index: 0,
+                target: None,
+                terastallize: false,
+            },
+        );
+        let events = usecase.play_turn(vec![action.clone()]).unwrap();
+
+        let replay = usecase.finish();
+        assert_eq!(replay.start.seed, 7);
+        assert_eq!(replay.start.sides.len(), 2);
+        assert_eq!(replay.turns.len(), 1);
+        assert_eq!(replay.turns[0].actions, vec![action]);
+        assert_eq!(replay.turns[0].events, events);
+    }
+
+    #[test]
+    fn test_record_replacement_after_faint() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![
+                    test_active_pokemon("a", &["normal"], &moves),
+                    test_active_pokemon("c", &["normal"], &moves),
+                ],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(7);
+        battle.sides[0].party[0].current_hp = 1;
+        let mut usecase = RecordBattleUsecase::new(BattleEngine::new(TypeChart::default()), battle);
+
+        let tackle = ChosenAction::new(
+            SlotPosition::new(1, 0),
+            BattleAction::UseMove {
+                move_index: 0,
+                target: None,
+                terastallize: false,
+            },
+        );
+        let turn_events = usecase.play_turn(vec![tackle]).unwrap();
+        let replacement = (SlotPosition::new(0, 0), 1);
+        let replacement_events = usecase.submit_replacements(vec![replacement]).unwrap();
+
+        let replay = usecase.finish();
+        assert_eq!(replay.turns.len(), 1);
+        assert_eq!(replay.turns[0].replacements, vec![replacement]);
+        assert_eq!(
+            replay.turns[0].events,
+            [turn_events, replacement_events].concat()
+        );
+        assert_eq!(replay.start.sides[0].party[0].current_hp, 1);
+    }
+
+    #[test]
+    fn test_replacement_before_first_turn_is_rejected() {
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![
+                    test_active_pokemon("a", &["normal"], &[]),
+                    test_active_pokemon("c", &["normal"], &[]),
+                ],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &[])],
+                1,
+            ),
+        );
+        battle.sides[0].party[0].current_hp = 0;
+        let mut usecase = RecordBattleUsecase::new(BattleEngine::new(TypeChart::default()), battle);
+
+        assert_eq!(
+            usecase.submit_replacements(vec![(SlotPosition::new(0, 0), 1)]),
+            Err(RecordError::ReplacementBeforeFirstTurn)
+        );
+        assert!(usecase.finish().turns.is_empty());
+    }
+}
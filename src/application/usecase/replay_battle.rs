@@ -0,0 +1,241 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_event::BattleEvent;
+use crate::domain::model::battle_result::BattleResult;
+use crate::domain::model::replay::{REPLAY_VERSION, Replay};
+use crate::domain::service::battle_engine::{BattleEngine, BattleError};
+use std::fmt;
+
+/// 記録された出来事と再実行した出来事が最初に食い違った箇所
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivergenceReport {
+    /// 食い違ったターン（バトルの通算のターン数）
+    pub turn: usize,
+    /// ターン内で何番目の出来事か（0始まり）
+    pub event_index: usize,
+    /// リプレイに記録されていた出来事（記録の方が短い場合は None）
+    pub expected: Option<BattleEvent>,
+    /// 再実行で発生した出来事（再実行の方が短い場合は None）
+    pub actual: Option<BattleEvent>,
+}
+
+impl DivergenceReport {
+    /// 2つの出来事の列を比べ、最初に食い違った箇所を返す
+    fn compare(turn: usize, expected: &[BattleEvent], actual: &[BattleEvent]) -> Option<Self> {
+        let length = expected.len().max(actual.len());
+        (0..length)
+            .find(|index| expected.get(*index) != actual.get(*index))
+            .map(|event_index| Self {
+                turn,
+                event_index,
+                expected: expected.get(event_index).cloned(),
+                actual: actual.get(event_index).cloned(),
+            })
+    }
+}
+
+impl fmt::Display for DivergenceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ターン{}の{}番目の出来事が記録と異なります\n  記録: {:?}\n  再生: {:?}",
+            self.turn, self.event_index, self.expected, self.actual
+        )
+    }
+}
+
+/// リプレイを再生できなかった場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// 対応していない形式のリプレイ
+    UnsupportedVersion(u32),
+    /// 記録された行動をエンジンが受け付けなかった
+    Engine { turn: usize, error: BattleError },
+    /// 発生した出来事が記録と異なる
+    Diverged(Box<DivergenceReport>),
+    /// 全ターンを再生した後の勝敗が記録と異なる
+    ResultMismatch {
+        expected: Option<BattleResult>,
+        actual: Option<BattleResult>,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "対応していないリプレイの形式です: {}", version)
+            }
+            ReplayError::Engine { turn, error } => {
+                write!(f, "ターン{}の行動を実行できません: {:?}", turn, error)
+            }
+            ReplayError::Diverged(report) => write!(f, "{}", report),
+            ReplayError::ResultMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "勝敗が記録と異なります: 記録 {:?}, 再生 {:?}",
+                    expected, actual
+                )
+            }
+        }
+    }
+}
+
+/// リプレイをエンジンで再実行し、記録と同じ出来事が発生することを確かめる
+pub struct ReplayBattleUsecase {
+    engine: BattleEngine,
+}
+
+impl ReplayBattleUsecase {
+    pub fn new(engine: BattleEngine) -> Self {
+        Self { engine }
+    }
+
+    /// 全ターンを再生し、再現されたバトルを返す
+    pub fn execute(&self, replay: &Replay) -> Result<Battle, ReplayError> {
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(replay.version));
+        }
+        // 記録したときと同じターン数で打ち切る
+        let engine = self.engine.clone().with_turn_limit(replay.turn_limit);
+        let mut battle = replay.start.clone();
+
+        for (index, recorded) in replay.turns.iter().enumerate() {
+            let turn = replay.start.turn as usize + index + 1;
+            let mut events = engine
+                .execute_turn(&mut battle, recorded.actions.clone())
+                .map_err(|error| ReplayError::Engine { turn, error })?;
+            if !recorded.replacements.is_empty() {
+                let replacement_events = engine
+                    .submit_replacements(&mut battle, recorded.replacements.clone())
+                    .map_err(|error| ReplayError::Engine { turn, error })?;
+                events.extend(replacement_events);
+            }
+            if let Some(report) = DivergenceReport::compare(turn, &recorded.events, &events) {
+                return Err(ReplayError::Diverged(Box::new(report)));
+            }
+        }
+
+        if battle.result != replay.result {
+            return Err(ReplayError::ResultMismatch {
+                expected: replay.result,
+                actual: battle.result,
+            });
+        }
+        Ok(battle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::usecase::record_battle::RecordBattleUsecase;
+    use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    fn record_replay() -> Replay {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(42);
+        let mut recorder =
+            RecordBattleUsecase::new(BattleEngine::new(TypeChart::default()), battle);
+        let tackle = |side| {
+            ChosenAction::new(
+                SlotPosition::new(side, 0),
+                BattleAction::UseMove {
+                    move_index: 0,
+                    target: None,
+                    terastallize: false,
+                },
+            )
+        };
+        while !recorder.battle().is_over() {
+            recorder.play_turn(vec![tackle(0), tackle(1)]).unwrap();
+        }
+        recorder.finish()
+    }
+
+    #[test]
+    fn test_replay_reproduces_recorded_battle() {
+        let replay = record_replay();
+        let json = serde_json::to_string(&replay).unwrap();
+        let loaded: Replay = serde_json::from_str(&json).unwrap();
+
+        let usecase = ReplayBattleUsecase::new(BattleEngine::new(TypeChart::default()));
+        let battle = usecase.execute(&loaded).unwrap();
+        assert_eq!(battle.result, replay.result);
+    }
+
+    #[test]
+    fn test_replay_reports_divergence() {
+        let mut replay = record_replay();
+        replay.turns[0].events.pop();
+
+        let usecase = ReplayBattleUsecase::new(BattleEngine::new(TypeChart::default()));
+        let Err(ReplayError::Diverged(report)) = usecase.execute(&replay) else {
+            panic!("divergence was not detected");
+        };
+        assert_eq!(report.turn, 1);
+        assert_eq!(report.expected, None);
+    }
+
+    #[test]
+    fn test_replay_from_mid_battle_with_turn_limit() {
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &[])],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &[])],
+                1,
+            ),
+        )
+        .with_seed(5);
+        for side in &mut battle.sides {
+            side.party[0].max_hp = 1000;
+            side.party[0].current_hp = 1000;
+        }
+        let engine = BattleEngine::new(TypeChart::default()).with_turn_limit(3);
+        let struggle = |side| ChosenAction::new(SlotPosition::new(side, 0), BattleAction::Struggle);
+        // 1ターン進めて乱数・ターン数が開始時点から変わった状態から記録する
+        engine
+            .execute_turn(&mut battle, vec![struggle(0), struggle(1)])
+            .unwrap();
+        let mut recorder = RecordBattleUsecase::new(engine, battle);
+        while !recorder.battle().is_over() {
+            recorder.play_turn(vec![struggle(0), struggle(1)]).unwrap();
+        }
+        let replay = recorder.finish();
+        assert_eq!(replay.turns.len(), 2);
+
+        // 再生側のエンジンの上限に関わらず、記録した上限で打ち切られる
+        let usecase = ReplayBattleUsecase::new(BattleEngine::new(TypeChart::default()));
+        let battle = usecase.execute(&replay).unwrap();
+        assert_eq!(battle.turn, 3);
+        assert_eq!(battle.result, replay.result);
+    }
+}
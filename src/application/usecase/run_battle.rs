@@ -0,0 +1,198 @@
+use crate::application::usecase::record_battle::{RecordBattleUsecase, RecordError};
+use crate::domain::model::battle_event::BattleObserver;
+use crate::domain::model::battle_history::BattleHistory;
+use crate::domain::model::battle_result::BattleResult;
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::model::replay::Replay;
+use crate::domain::service::battle_agent::BattleAgent;
+use crate::domain::service::battle_engine::BattleEngine;
+use crate::domain::service::legal_actions::LegalActions;
+
+/// 決着までのバトルの記録
+#[derive(Debug, Clone)]
+pub struct BattleRun {
+    pub result: BattleResult,
+    /// ターンごとの状態。巻き戻しや分岐に使う
+    pub history: BattleHistory,
+    /// 保存して再生するためのリプレイ
+    pub replay: Replay,
+}
+
+/// 両サイドのエージェントに行動を選ばせながら、決着までバトルを進める
+pub struct RunBattleUsecase {
+    engine: BattleEngine,
+}
+
+impl RunBattleUsecase {
+    pub fn new(engine: BattleEngine) -> Self {
+        Self { engine }
+    }
+
+    /// ひんし後の交代とターンの行動をエージェントに選ばせ、記録の現在の分岐の最新の状態から
+    /// 決着まで進めて同じ記録に書き足す。新しいバトルは `BattleHistory::new` から始め、
+    /// 分岐してから呼べば元の展開を残したまま戦い直せる。リプレイは進め始めた時点の状態からの記録になる。
+    /// 各サイドのオブザーバーには、そのプレイヤーから見える形に直した出来事を渡す
+    pub fn execute(
+        &self,
+        history: BattleHistory,
+        agents: [&mut dyn BattleAgent; 2],
+        observers: [&mut dyn BattleObserver; 2],
+    ) -> Result<BattleRun, RecordError> {
+        let mut agents = agents;
+        let mut observers = observers;
+        let mut history = history;
+        let battle = history
+            .state_at(history.current_branch(), history.latest_turn())
+            .expect("history has at least the initial state")
+            .clone();
+        let mut recorder = RecordBattleUsecase::new(self.engine.clone(), battle);
+        while !recorder.battle().is_over() {
+            let battle = recorder.battle();
+            let requests = battle.replacement_requests();
+            let events = if requests.is_empty() {
+                let mut actions = Vec::new();
+                for (side, agent) in agents.iter_mut().enumerate() {
+                    let options = LegalActions::for_side(battle, side);
+                    actions.extend(agent.choose_actions(&PlayerView::new(battle, side), &options));
+                }
+                recorder.play_turn(actions)?
+            } else {
+                let mut choices = Vec::new();
+                for request in &requests {
+                    let view = PlayerView::new(battle, request.side);
+                    choices.extend(agents[request.side].choose_replacements(&view, request));
+                }
+                recorder.submit_replacements(choices)?
+            };
+            history.record(recorder.battle());
+            for (side, observer) in observers.iter_mut().enumerate() {
+                let view = PlayerView::new(recorder.battle(), side);
+                observer.on_events(&view.observe_events(&events));
+            }
+        }
+        let result = recorder.battle().result.unwrap_or(BattleResult::Draw);
+        Ok(BattleRun {
+            result,
+            history,
+            replay: recorder.finish(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle::Battle;
+    use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+    use crate::domain::model::battle_event::{BattleEvent, EventLog};
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{ScriptedAgent, test_active_pokemon, test_move};
+
+    /// 控えのいる自分と、HP200の相手1体のシングルバトル
+    fn test_battle() -> Battle {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let party = |name: &str, size: usize| {
+            (0..size)
+                .map(|_| test_active_pokemon(name, &["normal"], &moves))
+                .collect()
+        };
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), party("a", 2), 1),
+            BattleSide::new("opponent".to_string(), party("b", 1), 1),
+        )
+        .with_seed(1);
+        battle.sides[1].party[0].max_hp = 200;
+        battle.sides[1].party[0].current_hp = 200;
+        battle
+    }
+
+    #[test]
+    fn test_run_battle_until_finished() {
+        let battle = test_battle();
+        let usecase = RunBattleUsecase::new(BattleEngine::new(TypeChart::default()));
+        let mut player_log = EventLog::default();
+        let mut opponent_log = EventLog::default();
+
+        let run = usecase
+            .execute(
+                BattleHistory::new(&battle),
+                [&mut ScriptedAgent::default(), &mut ScriptedAgent::default()],
+                [&mut player_log, &mut opponent_log],
+            )
+            .unwrap();
+        let history = &run.history;
+        let last = history
+            .state_at(history.current_branch(), history.latest_turn())
+            .unwrap();
+        assert_eq!(last.result, Some(run.result));
+        assert_eq!(run.replay.result, Some(run.result));
+        assert_eq!(history.latest_turn() as usize, run.replay.turns.len());
+        assert!(matches!(
+            player_log.events.last(),
+            Some(BattleEvent::BattleEnded { .. })
+        ));
+        // 相手のHPは割合で伝わる
+        let opponent_damage = |log: &EventLog| {
+            log.events.iter().find_map(|event| match event {
+                BattleEvent::Damage {
+                    target,
+                    remaining_hp,
+                    ..
+                } if target.side == 1 => Some(*remaining_hp),
+                _ => None,
+            })
+        };
+        let actual = opponent_damage(&opponent_log).unwrap();
+        assert_eq!(
+            opponent_damage(&player_log),
+            Some((actual * 100).div_ceil(200))
+        );
+    }
+
+    #[test]
+    fn test_branch_from_earlier_turn_keeps_original_line() {
+        let usecase = RunBattleUsecase::new(BattleEngine::new(TypeChart::default()));
+        let run = |history: BattleHistory, player: &mut ScriptedAgent| {
+            usecase
+                .execute(
+                    history,
+                    [player, &mut ScriptedAgent::default()],
+                    [&mut EventLog::default(), &mut EventLog::default()],
+                )
+                .unwrap()
+        };
+        let original = run(
+            BattleHistory::new(&test_battle()),
+            &mut ScriptedAgent::default(),
+        );
+        let original_turns = original.history.latest_turn();
+        let mut history = original.history;
+
+        // 2ターン目に技ではなく控えへの交代を選び直す
+        history.branch_from(1).unwrap();
+        let switch = ChosenAction::new(
+            SlotPosition::new(0, 0),
+            BattleAction::Switch { party_index: 1 },
+        );
+        let branched = run(history, &mut ScriptedAgent::new(vec![vec![switch.clone()]]));
+
+        assert_eq!(branched.history.branch_count(), 2);
+        assert_eq!(branched.history.current_branch(), 1);
+        assert_eq!(branched.replay.turns[0].actions[0], switch);
+        assert_ne!(original.replay.turns[1].actions[0], switch);
+        // 元の展開は最後まで残っている
+        assert_eq!(
+            branched.history.state_at(0, original_turns).unwrap().result,
+            Some(original.result)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::repository::battle_snapshot_repository::BattleSnapshotRepository;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// 進行中のバトルを中断するために状態を保存する
+pub struct SaveBattleUsecase<R>
+where
+    R: BattleSnapshotRepository,
+{
+    repository: R,
+}
+
+impl<R> SaveBattleUsecase<R>
+where
+    R: BattleSnapshotRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub fn execute(&self, name: &str, battle: &Battle) -> Result<(), RepositoryError> {
+        self.repository.save_snapshot(name, battle)
+    }
+}
@@ -0,0 +1,24 @@
+use crate::domain::model::replay::Replay;
+use crate::domain::repository::replay_repository::ReplayRepository;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// 記録したリプレイをファイルなどに保存する
+pub struct SaveReplayUsecase<R>
+where
+    R: ReplayRepository,
+{
+    repository: R,
+}
+
+impl<R> SaveReplayUsecase<R>
+where
+    R: ReplayRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub fn execute(&self, name: &str, replay: &Replay) -> Result<(), RepositoryError> {
+        self.repository.save_replay(name, replay)
+    }
+}
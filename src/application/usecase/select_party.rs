@@ -1 +1,397 @@
+use crate::application::dto::loaded_static_data::LoadedStaticData;
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::format::Format;
+use crate::domain::model::pokemon::Pokemon;
+use crate::domain::model::pokemon_build::{BuildError, MoveChoice, PokemonBuild, StatSpread};
+use crate::domain::model::team::{MAX_TEAM_SIZE, Team};
+use crate::domain::service::format_validator::{FormatValidator, FormatViolation};
+use std::fmt;
 
+/// パーティの作成・編集が図鑑データやルールと合わない場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartyError {
+    /// パーティの数が1〜6体ではない
+    InvalidSize(usize),
+    /// パーティにいない番号を指定した
+    MemberOutOfRange(usize),
+    UnknownSpecies {
+        index: usize,
+        species: String,
+    },
+    /// そのポケモンが持てない特性
+    IllegalAbility {
+        index: usize,
+        species: String,
+        ability: String,
+    },
+    /// そのポケモンがスカーレット・バイオレットで覚えない技
+    IllegalMove {
+        index: usize,
+        species: String,
+        move_name: String,
+    },
+    UnknownItem {
+        index: usize,
+        item: String,
+    },
+    UnknownType {
+        index: usize,
+        type_name: String,
+    },
+    /// 個体値・努力値・技の数などが型としてありえない
+    InvalidBuild {
+        index: usize,
+        error: BuildError,
+    },
+    /// 対戦のルールに違反している
+    Format(Vec<FormatViolation>),
+}
+
+impl fmt::Display for PartyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartyError::InvalidSize(size) => write!(
+                f,
+                "パーティは1〜{}体で組みます（{}体）",
+                MAX_TEAM_SIZE, size
+            ),
+            PartyError::MemberOutOfRange(index) => {
+                write!(f, "パーティに{}番のポケモンはいません", index + 1)
+            }
+            PartyError::UnknownSpecies { index, species } => {
+                write!(f, "{}番: ポケモンが見つかりません: {}", index + 1, species)
+            }
+            PartyError::IllegalAbility {
+                index,
+                species,
+                ability,
+            } => write!(
+                f,
+                "{}番: {}は特性{}を持てません",
+                index + 1,
+                species,
+                ability
+            ),
+            PartyError::IllegalMove {
+                index,
+                species,
+                move_name,
+            } => write!(
+                f,
+                "{}番: {}は技{}を覚えません",
+                index + 1,
+                species,
+                move_name
+            ),
+            PartyError::UnknownItem { index, item } => {
+                write!(f, "{}番: 道具が見つかりません: {}", index + 1, item)
+            }
+            PartyError::UnknownType { index, type_name } => {
+                write!(f, "{}番: タイプが見つかりません: {}", index + 1, type_name)
+            }
+            PartyError::InvalidBuild { index, error } => write!(f, "{}番: {}", index + 1, error),
+            PartyError::Format(violations) => {
+                let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+                write!(f, "{}", messages.join("\n"))
+            }
+        }
+    }
+}
+
+/// 図鑑データに照らしてパーティを作成・編集する。
+/// 編集は検証に通った場合だけ反映し、失敗した場合はパーティを変えない
+pub struct SelectPartyUsecase<'a> {
+    data: &'a LoadedStaticData,
+}
+
+impl<'a> SelectPartyUsecase<'a> {
+    pub fn new(data: &'a LoadedStaticData) -> Self {
+        Self { data }
+    }
+
+    /// 型の一覧から1〜6体のパーティを作る
+    pub fn create_team(&self, name: &str, members: Vec<PokemonBuild>) -> Result<Team, PartyError> {
+        if members.is_empty() || members.len() > MAX_TEAM_SIZE {
+            return Err(PartyError::InvalidSize(members.len()));
+        }
+        for (index, member) in members.iter().enumerate() {
+            self.validate_member(index, member)?;
+        }
+        Ok(Team::new(name, members))
+    }
+
+    pub fn add_member(&self, team: &mut Team, member: PokemonBuild) -> Result<(), PartyError> {
+        if team.members.len() >= MAX_TEAM_SIZE {
+            return Err(PartyError::InvalidSize(team.members.len() + 1));
+        }
+        self.validate_member(team.members.len(), &member)?;
+        team.members.push(member);
+        Ok(())
+    }
+
+    pub fn remove_member(&self, team: &mut Team, index: usize) -> Result<PokemonBuild, PartyError> {
+        if index >= team.members.len() {
+            return Err(PartyError::MemberOutOfRange(index));
+        }
+        if team.members.len() == 1 {
+            return Err(PartyError::InvalidSize(0));
+        }
+        Ok(team.members.remove(index))
+    }
+
+    /// 指定したポケモンを先発にする
+    pub fn set_lead(&self, team: &mut Team, index: usize) -> Result<(), PartyError> {
+        if index >= team.members.len() {
+            return Err(PartyError::MemberOutOfRange(index));
+        }
+        team.set_lead(index);
+        Ok(())
+    }
+
+    pub fn set_ability(
+        &self,
+        team: &mut Team,
+        index: usize,
+        ability: &str,
+    ) -> Result<(), PartyError> {
+        self.edit(team, index, |member| member.ability = ability.to_string())
+    }
+
+    /// 技を入れ替える。同じ技のポイントアップの数は引き継ぐ
+    pub fn set_moves(
+        &self,
+        team: &mut Team,
+        index: usize,
+        moves: &[&str],
+    ) -> Result<(), PartyError> {
+        self.edit(team, index, |member| {
+            member.moves = moves
+                .iter()
+                .map(|name| {
+                    member
+                        .moves
+                        .iter()
+                        .find(|choice| choice.name == *name)
+                        .cloned()
+                        .unwrap_or_else(|| MoveChoice::new(name))
+                })
+                .collect();
+        })
+    }
+
+    pub fn set_item(
+        &self,
+        team: &mut Team,
+        index: usize,
+        item: Option<&str>,
+    ) -> Result<(), PartyError> {
+        self.edit(team, index, |member| {
+            member.held_item = item.map(str::to_string)
+        })
+    }
+
+    pub fn set_evs(
+        &self,
+        team: &mut Team,
+        index: usize,
+        evs: StatSpread,
+    ) -> Result<(), PartyError> {
+        self.edit(team, index, |member| member.evs = evs)
+    }
+
+    pub fn set_tera_type(
+        &self,
+        team: &mut Team,
+        index: usize,
+        tera_type: Option<&str>,
+    ) -> Result<(), PartyError> {
+        self.edit(team, index, |member| {
+            member.tera_type = tera_type.map(str::to_string)
+        })
+    }
+
+    /// パーティの型から、バトル用のポケモンをパーティの順に作る
+    pub fn to_battle_party(&self, team: &Team) -> Result<Vec<ActivePokemon>, PartyError> {
+        team.members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| {
+                let pokemon = self.validate_member(index, member)?;
+                let ability = self
+                    .data
+                    .abilities
+                    .iter()
+                    .find(|ability| ability.name == member.ability)
+                    .ok_or_else(|| PartyError::IllegalAbility {
+                        index,
+                        species: member.species.clone(),
+                        ability: member.ability.clone(),
+                    })?;
+                ActivePokemon::new(member, pokemon, ability, &self.data.moves)
+                    .map_err(|error| PartyError::InvalidBuild { index, error })
+            })
+            .collect()
+    }
+
+    /// 対戦のルールに沿っているかを確かめ、レベルのルールを反映したバトル用のポケモンを作る
+    pub fn to_format_party(
+        &self,
+        team: &Team,
+        format: &Format,
+    ) -> Result<Vec<ActivePokemon>, PartyError> {
+        FormatValidator::validate(
+            format,
+            team,
+            &self.data.pokemons,
+            &self.data.pokemon_species,
+        )
+        .map_err(PartyError::Format)?;
+        let members = team
+            .members
+            .iter()
+            .map(|member| format.apply_level(member))
+            .collect();
+        self.to_battle_party(&Team::new(&team.name, members))
+    }
+
+    /// 1体分の編集を複製に適用し、検証に通った場合だけパーティに反映する
+    fn edit(
+        &self,
+        team: &mut Team,
+        index: usize,
+        apply: impl FnOnce(&mut PokemonBuild),
+    ) -> Result<(), PartyError> {
+        let mut member = team
+            .members
+            .get(index)
+            .cloned()
+            .ok_or(PartyError::MemberOutOfRange(index))?;
+        apply(&mut member);
+        self.validate_member(index, &member)?;
+        team.members[index] = member;
+        Ok(())
+    }
+
+    /// 型が図鑑データと矛盾しないかを確かめ、対応するポケモンのデータを返す
+    fn validate_member(&self, index: usize, member: &PokemonBuild) -> Result<&Pokemon, PartyError> {
+        member
+            .validate()
+            .map_err(|error| PartyError::InvalidBuild { index, error })?;
+        let pokemon = self
+            .data
+            .pokemons
+            .iter()
+            .find(|pokemon| pokemon.name == member.species)
+            .ok_or_else(|| PartyError::UnknownSpecies {
+                index,
+                species: member.species.clone(),
+            })?;
+        if !pokemon
+            .abilities
+            .iter()
+            .any(|ability| ability.ability.name == member.ability)
+        {
+            return Err(PartyError::IllegalAbility {
+                index,
+                species: member.species.clone(),
+                ability: member.ability.clone(),
+            });
+        }
+        if let Some(choice) = member
+            .moves
+            .iter()
+            .find(|choice| !pokemon.can_learn(&choice.name))
+        {
+            return Err(PartyError::IllegalMove {
+                index,
+                species: member.species.clone(),
+                move_name: choice.name.clone(),
+            });
+        }
+        if let Some(item) = &member.held_item
+            && !self.data.items.iter().any(|known| known.name == *item)
+        {
+            return Err(PartyError::UnknownItem {
+                index,
+                item: item.clone(),
+            });
+        }
+        if let Some(tera_type) = &member.tera_type
+            && !self.data.types.iter().any(|known| known.name == *tera_type)
+        {
+            return Err(PartyError::UnknownType {
+                index,
+                type_name: tera_type.clone(),
+            });
+        }
+        Ok(pokemon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{
+        test_ability, test_item, test_learnset, test_move, test_pokemon,
+    };
+
+    fn static_data() -> LoadedStaticData {
+        let mut garchomp = test_pokemon("garchomp", &["dragon", "ground"], 108, 102);
+        test_learnset(&mut garchomp, "rough-skin", &["earthquake", "dragon-claw"]);
+        LoadedStaticData {
+            items: vec![test_item("choice-scarf")],
+            pokemons: vec![garchomp],
+            moves: vec![
+                test_move(
+                    "earthquake",
+                    "ground",
+                    "physical",
+                    Some(100),
+                    "all-other-pokemon",
+                ),
+                test_move(
+                    "dragon-claw",
+                    "dragon",
+                    "physical",
+                    Some(80),
+                    "selected-pokemon",
+                ),
+            ],
+            pokemon_species: vec![],
+            types: vec![],
+            abilities: vec![test_ability("rough-skin")],
+        }
+    }
+
+    #[test]
+    fn test_create_and_edit_team() {
+        let data = static_data();
+        let usecase = SelectPartyUsecase::new(&data);
+        let mut team = usecase
+            .create_team(
+                "sand",
+                vec![PokemonBuild::new("garchomp", "rough-skin").with_moves(&["earthquake"])],
+            )
+            .unwrap();
+
+        usecase
+            .set_moves(&mut team, 0, &["earthquake", "dragon-claw"])
+            .unwrap();
+        usecase
+            .set_item(&mut team, 0, Some("choice-scarf"))
+            .unwrap();
+        assert_eq!(
+            usecase.set_moves(&mut team, 0, &["surf"]),
+            Err(PartyError::IllegalMove {
+                index: 0,
+                species: "garchomp".to_string(),
+                move_name: "surf".to_string(),
+            })
+        );
+        assert_eq!(team.members[0].moves.len(), 2);
+
+        let party = usecase.to_battle_party(&team).unwrap();
+        assert_eq!(party[0].moves[1].name, "dragon-claw");
+        assert!(party[0].has_item("choice-scarf"));
+    }
+}
@@ -1 +1,260 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_format::BattleFormat;
+use crate::domain::model::battle_side::BattleSide;
+use crate::domain::model::format::{BattleRules, Format};
+use crate::domain::model::team_preview::{TeamPreview, TeamSheet};
+use crate::domain::service::battle_agent::BattleAgent;
+use std::fmt;
 
+/// 見せ合いで選んだポケモンが不正な場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TeamSelectionError {
+    /// パーティにポケモンがいない
+    EmptyParty { side: usize },
+    /// 選んだ数が選出する数と違う
+    WrongCount {
+        side: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// パーティにいない番号を選んだ
+    OutOfRange { side: usize, party_index: usize },
+    /// 同じポケモンを2回選んだ
+    Duplicate { side: usize, party_index: usize },
+}
+
+impl fmt::Display for TeamSelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TeamSelectionError::EmptyParty { side } => {
+                write!(f, "サイド{}のパーティにポケモンがいません", side)
+            }
+            TeamSelectionError::WrongCount {
+                side,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "サイド{}は{}体を選出する必要がありますが、{}体が選ばれました",
+                side, expected, actual
+            ),
+            TeamSelectionError::OutOfRange { side, party_index } => {
+                write!(f, "サイド{}のパーティに{}番はいません", side, party_index)
+            }
+            TeamSelectionError::Duplicate { side, party_index } => {
+                write!(f, "サイド{}が{}番を重複して選びました", side, party_index)
+            }
+        }
+    }
+}
+
+/// 両者のパーティを見せ合い、各エージェントが選んだポケモンだけでバトルを始める
+pub struct StartNewBattleUsecase {
+    format: BattleFormat,
+    bring_count: usize,
+    team_sheet: TeamSheet,
+    rules: BattleRules,
+    seed: u64,
+}
+
+impl StartNewBattleUsecase {
+    /// 選出する数は形式ごとの既定値（シングル3体・ダブル4体）、チームシートは非公開で始める
+    pub fn new(format: BattleFormat) -> Self {
+        Self {
+            format,
+            bring_count: format.bring_count(),
+            team_sheet: TeamSheet::Closed,
+            rules: BattleRules::default(),
+            seed: 0,
+        }
+    }
+
+    /// 対戦のルールの形式・選出する数・バトル中のルールで始める
+    pub fn for_format(format: &Format) -> Self {
+        Self::new(format.battle_format)
+            .with_bring_count(format.bring_count)
+            .with_rules(format.battle_rules())
+    }
+
+    pub fn with_bring_count(mut self, bring_count: usize) -> Self {
+        self.bring_count = bring_count;
+        self
+    }
+
+    pub fn with_team_sheet(mut self, team_sheet: TeamSheet) -> Self {
+        self.team_sheet = team_sheet;
+        self
+    }
+
+    pub fn with_rules(mut self, rules: BattleRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// 両サイドの名前とパーティを受け取り、見せ合いで選ばれた順に並べたバトルを返す。
+    /// パーティが選出する数より少ない場合は全員を連れて行く
+    pub fn execute(
+        &self,
+        teams: [(String, Vec<ActivePokemon>); 2],
+        agents: [&mut dyn BattleAgent; 2],
+    ) -> Result<Battle, TeamSelectionError> {
+        let names: Vec<Vec<String>> = teams
+            .iter()
+            .map(|(_, party)| party.iter().map(|pokemon| pokemon.name.clone()).collect())
+            .collect();
+        let mut sides = Vec::with_capacity(2);
+        for (side, (name, party)) in teams.into_iter().enumerate() {
+            if party.is_empty() {
+                return Err(TeamSelectionError::EmptyParty { side });
+            }
+            let preview = TeamPreview {
+                own: names[side].clone(),
+                opponent: names[1 - side].clone(),
+                bring_count: self.bring_count.min(party.len()),
+                team_sheet: self.team_sheet,
+            };
+            let selection = agents[side].choose_team(&preview);
+            Self::validate(side, &preview, &selection)?;
+
+            let mut party: Vec<Option<ActivePokemon>> = party.into_iter().map(Some).collect();
+            let chosen = selection
+                .iter()
+                .filter_map(|party_index| party[*party_index].take())
+                .map(|mut pokemon| {
+                    if self.team_sheet == TeamSheet::Open {
+                        pokemon.revealed.reveal_all(pokemon.moves.len());
+                    }
+                    pokemon
+                })
+                .collect();
+            let mut battle_side = BattleSide::new(name, chosen, self.format.active_slots());
+            battle_side.preview = preview.own;
+            sides.push(battle_side);
+        }
+        let second = sides.pop().expect("two sides");
+        let first = sides.pop().expect("two sides");
+        Ok(Battle::new(self.format, first, second)
+            .with_seed(self.seed)
+            .with_rules(self.rules))
+    }
+
+    fn validate(
+        side: usize,
+        preview: &TeamPreview,
+        selection: &[usize],
+    ) -> Result<(), TeamSelectionError> {
+        if selection.len() != preview.bring_count {
+            return Err(TeamSelectionError::WrongCount {
+                side,
+                expected: preview.bring_count,
+                actual: selection.len(),
+            });
+        }
+        for (position, party_index) in selection.iter().enumerate() {
+            if *party_index >= preview.own.len() {
+                return Err(TeamSelectionError::OutOfRange {
+                    side,
+                    party_index: *party_index,
+                });
+            }
+            if selection[..position].contains(party_index) {
+                return Err(TeamSelectionError::Duplicate {
+                    side,
+                    party_index: *party_index,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_action::ChosenAction;
+    use crate::domain::model::player_view::PlayerView;
+    use crate::domain::service::legal_actions::SlotOptions;
+    use crate::domain::test_support::{ScriptedAgent, test_active_pokemon, test_move};
+
+    /// 決めた番号を選出するエージェント
+    struct PickingAgent(Vec<usize>);
+
+    impl BattleAgent for PickingAgent {
+        fn choose_team(&mut self, _preview: &TeamPreview) -> Vec<usize> {
+            self.0.clone()
+        }
+
+        fn choose_actions(
+            &mut self,
+            _view: &PlayerView,
+            _options: &[SlotOptions],
+        ) -> Vec<ChosenAction> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_start_battle_with_chosen_members() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let party = |prefix: &str| {
+            (0..6)
+                .map(|index| test_active_pokemon(&format!("{prefix}{index}"), &["normal"], &moves))
+                .collect::<Vec<_>>()
+        };
+        let usecase =
+            StartNewBattleUsecase::new(BattleFormat::Singles).with_team_sheet(TeamSheet::Open);
+
+        let battle = usecase
+            .execute(
+                [
+                    ("player".to_string(), party("a")),
+                    ("opponent".to_string(), party("b")),
+                ],
+                [
+                    &mut PickingAgent(vec![4, 0, 2]),
+                    &mut ScriptedAgent::default(),
+                ],
+            )
+            .unwrap();
+        let names: Vec<&str> = battle.sides[0]
+            .party
+            .iter()
+            .map(|pokemon| pokemon.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["a4", "a0", "a2"]);
+        assert_eq!(battle.sides[1].party.len(), 3);
+        assert!(battle.sides[1].party[0].revealed.ability);
+
+        let error = usecase
+            .execute(
+                [
+                    ("player".to_string(), party("a")),
+                    ("opponent".to_string(), party("b")),
+                ],
+                [
+                    &mut PickingAgent(vec![1, 1, 2]),
+                    &mut ScriptedAgent::default(),
+                ],
+            )
+            .unwrap_err();
+        assert_eq!(
+            error,
+            TeamSelectionError::Duplicate {
+                side: 0,
+                party_index: 1
+            }
+        );
+    }
+}
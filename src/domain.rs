@@ -1,14 +1,35 @@
 pub mod model {
     pub mod ability;
     pub mod active_pokemon;
+    pub mod battle;
+    pub mod battle_action;
+    pub mod battle_event;
+    pub mod battle_format;
+    pub mod battle_history;
+    pub mod battle_result;
+    pub mod battle_rng;
+    pub mod battle_side;
     pub mod common;
+    pub mod field;
+    pub mod format;
     pub mod item;
     pub mod r#move;
+    pub mod move_slot;
+    pub mod nature;
+    pub mod player_view;
     pub mod poke_type;
     pub mod pokemon;
+    pub mod pokemon_build;
     pub mod pokemon_species;
+    pub mod replay;
     pub mod stats;
     pub mod status;
+    pub mod team;
+    pub mod team_preview;
+    pub mod volatile_status;
 }
 pub mod repository;
 pub mod service;
+
+#[cfg(test)]
+pub mod test_support;
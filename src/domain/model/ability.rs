@@ -28,6 +28,7 @@ pub struct FlavorTextEntry {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Name {
     pub name: String,
+    pub language: String,
 }
 
 // Helper functions for deserialization - filter by language during JSON parsing
@@ -106,7 +107,10 @@ where
     Ok(names
         .into_iter()
         .filter(|name| ["ja", "en"].contains(&name.language.name.as_str()))
-        .map(|name| Name { name: name.name })
+        .map(|name| Name {
+            name: name.name,
+            language: name.language.name,
+        })
         .collect())
 }
 
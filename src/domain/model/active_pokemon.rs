@@ -1,18 +1,75 @@
 use crate::domain::model::ability::Ability;
+use crate::domain::model::common::PokemonTypeName;
+use crate::domain::model::r#move::Move;
+use crate::domain::model::move_slot::MoveSlot;
 use crate::domain::model::pokemon::Pokemon;
+use crate::domain::model::pokemon_build::{BuildError, PokemonBuild, StatSpread};
 use crate::domain::model::stats::StatName;
 use crate::domain::model::status::StatusCondition;
+use crate::domain::model::volatile_status::VolatileStatus;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+/// レベル50フラット（ランクバトル準拠）
+pub const DEFAULT_LEVEL: u32 = 50;
+
+/// バトル中に相手へ明かした情報（使った技・発動した特性・持ち物）
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RevealedInfo {
+    /// 使ったことのある技の番号（使った順）
+    pub moves: Vec<usize>,
+    pub ability: bool,
+    pub item: bool,
+    /// テラスタルする前からテラスタイプが分かっているか（オープンチームシート）
+    pub tera_type: bool,
+}
+
+impl RevealedInfo {
+    pub fn reveal_move(&mut self, move_index: usize) {
+        if !self.moves.contains(&move_index) {
+            self.moves.push(move_index);
+        }
+    }
+
+    /// オープンチームシートで、技・特性・持ち物・テラスタイプをすべて公開する
+    pub fn reveal_all(&mut self, move_count: usize) {
+        self.moves = (0..move_count).collect();
+        self.ability = true;
+        self.item = true;
+        self.tera_type = true;
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActivePokemon {
     // Pokemon由来の不変に近い情報
     pub name: String,
-    pub active_ability: Ability,
+    /// 特性のデータは大きく変化しないため共有し、探索で状態を複製する際のコストを抑える
+    #[serde(with = "ability_ref")]
+    pub active_ability: Arc<Ability>,
+    pub level: u32,
+    pub types: Vec<PokemonTypeName>,
+    pub moves: Vec<MoveSlot>,
+    /// 持たせている道具
+    pub held_item: Option<String>,
+    /// テラスタルした時のタイプ
+    pub tera_type: Option<PokemonTypeName>,
 
     // バトル中の変動ステータス
     pub current_hp: u32,
     pub max_hp: u32,
     pub status: Option<StatusCondition>,
+    /// ねむり状態の残りターン数
+    pub sleep_turns: u32,
+    pub volatiles: VolatileStatus,
+    /// テラスタルしているか。バトル中は交代しても解除されない
+    pub terastallized: bool,
+    #[serde(default)]
+    pub revealed: RevealedInfo,
+    /// 種族値。相手から見た能力値の見積もりに使う
+    #[serde(default)]
+    pub base_stats: StatSpread,
 
     // 能力値
     pub attack: u32,
@@ -32,24 +89,61 @@ pub struct ActivePokemon {
 }
 
 impl ActivePokemon {
-    pub fn new(pokemon: &Pokemon, ability: &Ability) -> Self {
-        let get_stat = |stat_name: StatName| -> u32 {
-            pokemon
-                .stats
-                .iter()
-                .find(|s| s.stat == stat_name)
-                .map(|s| s.base_stat)
-                .unwrap_or_else(|| panic!("'{:?}' stat not found for {}", stat_name, pokemon.name))
+    /// 育成した型と、型が参照する図鑑データ（ポケモン・特性・技）からバトル用のポケモンを作る
+    pub fn new(
+        build: &PokemonBuild,
+        pokemon: &Pokemon,
+        ability: &Ability,
+        moves: &[Move],
+    ) -> Result<Self, BuildError> {
+        build.validate()?;
+        let check = |expected: &str, actual: &str| {
+            if expected == actual {
+                Ok(())
+            } else {
+                Err(BuildError::DataMismatch {
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                })
+            }
         };
+        check(&build.species, &pokemon.name)?;
+        check(&build.ability, &ability.name)?;
+        let move_slots = build
+            .moves
+            .iter()
+            .map(|choice| {
+                let move_data = moves
+                    .iter()
+                    .find(|move_data| move_data.name == choice.name)
+                    .ok_or_else(|| BuildError::MissingMoveData(choice.name.clone()))?;
+                Ok(MoveSlot::new(move_data).with_max_pp(choice.max_pp(move_data.pp.value())))
+            })
+            .collect::<Result<Vec<_>, BuildError>>()?;
+        let get_stat = |stat_name: StatName| build.stat(pokemon, &stat_name);
 
         let max_hp = get_stat(StatName::Hp);
+        let mut base_stats = StatSpread::default();
+        for stat in &pokemon.stats {
+            base_stats.set(&stat.stat, stat.base_stat);
+        }
 
-        Self {
+        Ok(Self {
             name: pokemon.name.clone(),
-            active_ability: ability.clone(),
+            active_ability: Arc::new(ability.clone()),
+            level: build.level,
+            types: pokemon.types.iter().map(|t| t.type_info.clone()).collect(),
+            moves: move_slots,
+            held_item: build.held_item.clone(),
+            tera_type: build.tera_type.clone().map(PokemonTypeName::new),
             max_hp,
             current_hp: max_hp,
             status: None,
+            sleep_turns: 0,
+            volatiles: VolatileStatus::default(),
+            terastallized: false,
+            revealed: RevealedInfo::default(),
+            base_stats,
             attack: get_stat(StatName::Attack),
             defense: get_stat(StatName::Defense),
             special_attack: get_stat(StatName::SpecialAttack),
@@ -62,7 +156,7 @@ impl ActivePokemon {
             speed_rank: 0,
             accuracy_rank: 0,
             evasion_rank: 0,
-        }
+        })
     }
 
     pub fn take_damage(&mut self, damage: u32) {
@@ -76,6 +170,125 @@ impl ActivePokemon {
     pub fn set_status(&mut self, status: StatusCondition) {
         self.status = Some(status);
     }
+
+    /// 交代で引っ込む際に、能力ランクと一時的な状態を元に戻す
+    pub fn reset_on_switch_out(&mut self) {
+        self.attack_rank = 0;
+        self.defense_rank = 0;
+        self.special_attack_rank = 0;
+        self.special_defense_rank = 0;
+        self.speed_rank = 0;
+        self.accuracy_rank = 0;
+        self.evasion_rank = 0;
+        self.volatiles = VolatileStatus::default();
+    }
+
+    pub fn has_item(&self, item_name: &str) -> bool {
+        self.held_item.as_deref() == Some(item_name)
+    }
+
+    pub fn has_ability(&self, ability_name: &str) -> bool {
+        self.active_ability.name == ability_name
+    }
+
+    /// 現在のタイプを持つか（テラスタル中はテラスタイプのみ）
+    pub fn has_type(&self, type_name: &str) -> bool {
+        self.current_types().iter().any(|t| t.name == type_name)
+    }
+
+    /// 相性の判定に使う現在のタイプ
+    pub fn current_types(&self) -> Vec<PokemonTypeName> {
+        match &self.tera_type {
+            Some(tera_type) if self.terastallized => vec![tera_type.clone()],
+            _ => self.types.clone(),
+        }
+    }
+
+    pub fn can_terastallize(&self) -> bool {
+        self.tera_type.is_some() && !self.terastallized
+    }
+
+    /// 地面に接しているか（ひこうタイプ・ふゆうは浮いている）
+    pub fn is_grounded(&self) -> bool {
+        !self.has_type("flying") && !self.has_ability("levitate")
+    }
+
+    /// 最大HPに対する現在HPの割合（%、切り上げ）
+    pub fn hp_percentage(&self) -> u32 {
+        (self.current_hp * 100).div_ceil(self.max_hp.max(1))
+    }
+
+    /// 能力ランク補正を反映した実数値を返す
+    pub fn effective_stat(&self, stat: StatName) -> u32 {
+        let (value, rank) = match stat {
+            StatName::Hp => return self.max_hp,
+            StatName::Attack => (self.attack, self.attack_rank),
+            StatName::Defense => (self.defense, self.defense_rank),
+            StatName::SpecialAttack => (self.special_attack, self.special_attack_rank),
+            StatName::SpecialDefense => (self.special_defense, self.special_defense_rank),
+            StatName::Speed => (self.speed, self.speed_rank),
+        };
+        let rank = rank.clamp(-6, 6) as i32;
+        let (numerator, denominator) = if rank >= 0 {
+            (2 + rank, 2)
+        } else {
+            (2, 2 - rank)
+        };
+        value * numerator as u32 / denominator as u32
+    }
+
+    /// 能力ランクを変化させ、実際に変化した段階数を返す
+    pub fn change_rank(&mut self, stat: StatName, change: i32) -> i32 {
+        let rank = match stat {
+            StatName::Hp => return 0,
+            StatName::Attack => &mut self.attack_rank,
+            StatName::Defense => &mut self.defense_rank,
+            StatName::SpecialAttack => &mut self.special_attack_rank,
+            StatName::SpecialDefense => &mut self.special_defense_rank,
+            StatName::Speed => &mut self.speed_rank,
+        };
+        let before = *rank as i32;
+        let after = (before + change).clamp(-6, 6);
+        *rank = after as i8;
+        after - before
+    }
+}
+
+/// バトル状態の保存用に、特性は図鑑データを除いたIDと名前だけを書き出す
+mod ability_ref {
+    use crate::domain::model::ability::Ability;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+
+    #[derive(Serialize, Deserialize)]
+    struct AbilityRef {
+        id: u32,
+        name: String,
+    }
+
+    pub fn serialize<S: Serializer>(
+        ability: &Arc<Ability>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        AbilityRef {
+            id: ability.id,
+            name: ability.name.clone(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Arc<Ability>, D::Error> {
+        let ability = AbilityRef::deserialize(deserializer)?;
+        Ok(Arc::new(Ability {
+            id: ability.id,
+            name: ability.name,
+            effect_entries: Vec::new(),
+            flavor_text_entries: Vec::new(),
+            names: Vec::new(),
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -83,6 +296,7 @@ mod tests {
     use super::*;
     use crate::domain::model::ability::Ability;
     use crate::domain::model::pokemon::{Pokemon, PokemonStat};
+    use crate::domain::test_support::test_move;
 
     fn create_test_pokemon() -> Pokemon {
         Pokemon {
@@ -128,6 +342,19 @@ mod tests {
         }
     }
 
+    /// Testmon をレベル50・個体値最大・努力値なし・無補正の性格で育成した型から作る
+    fn create_test_active_pokemon() -> ActivePokemon {
+        let tackle = test_move("tackle", "normal", "physical", Some(40), "selected-pokemon");
+        let build = PokemonBuild::new("Testmon", "Test-Ability").with_moves(&["tackle"]);
+        ActivePokemon::new(
+            &build,
+            &create_test_pokemon(),
+            &create_test_ability(),
+            &[tackle],
+        )
+        .unwrap()
+    }
+
     fn create_test_ability() -> Ability {
         Ability {
             id: 1,
@@ -140,15 +367,15 @@ mod tests {
 
     #[test]
     fn test_new_active_pokemon() {
-        let pokemon = create_test_pokemon();
-        let ability = create_test_ability();
-        let active_pokemon = ActivePokemon::new(&pokemon, &ability);
+        let active_pokemon = create_test_active_pokemon();
 
         assert_eq!(active_pokemon.name, "Testmon");
-        assert_eq!(active_pokemon.max_hp, 100);
-        assert_eq!(active_pokemon.current_hp, 100);
-        assert_eq!(active_pokemon.attack, 50);
-        assert_eq!(active_pokemon.speed, 70);
+        assert_eq!(active_pokemon.level, 50);
+        assert_eq!(active_pokemon.max_hp, 175);
+        assert_eq!(active_pokemon.current_hp, 175);
+        assert_eq!(active_pokemon.attack, 70);
+        assert_eq!(active_pokemon.speed, 90);
+        assert_eq!(active_pokemon.moves[0].name, "tackle");
         assert_eq!(active_pokemon.attack_rank, 0);
         assert!(active_pokemon.status.is_none());
         assert_eq!(active_pokemon.active_ability.name, "Test-Ability");
@@ -156,31 +383,35 @@ mod tests {
 
     #[test]
     fn test_take_damage() {
-        let pokemon = create_test_pokemon();
-        let ability = create_test_ability();
-        let mut active_pokemon = ActivePokemon::new(&pokemon, &ability);
+        let mut active_pokemon = create_test_active_pokemon();
 
         active_pokemon.take_damage(30);
-        assert_eq!(active_pokemon.current_hp, 70);
+        assert_eq!(active_pokemon.current_hp, 145);
         assert!(!active_pokemon.is_fainted());
     }
 
     #[test]
     fn test_take_fatal_damage() {
-        let pokemon = create_test_pokemon();
-        let ability = create_test_ability();
-        let mut active_pokemon = ActivePokemon::new(&pokemon, &ability);
+        let mut active_pokemon = create_test_active_pokemon();
 
-        active_pokemon.take_damage(120);
+        active_pokemon.take_damage(200);
         assert_eq!(active_pokemon.current_hp, 0);
         assert!(active_pokemon.is_fainted());
     }
 
+    #[test]
+    fn test_effective_stat_with_rank() {
+        let mut active_pokemon = create_test_active_pokemon();
+
+        assert_eq!(active_pokemon.change_rank(StatName::Attack, 2), 2);
+        assert_eq!(active_pokemon.effective_stat(StatName::Attack), 140);
+        assert_eq!(active_pokemon.change_rank(StatName::Speed, -1), -1);
+        assert_eq!(active_pokemon.effective_stat(StatName::Speed), 60);
+    }
+
     #[test]
     fn test_set_status() {
-        let pokemon = create_test_pokemon();
-        let ability = create_test_ability();
-        let mut active_pokemon = ActivePokemon::new(&pokemon, &ability);
+        let mut active_pokemon = create_test_active_pokemon();
 
         assert!(active_pokemon.status.is_none());
         active_pokemon.set_status(StatusCondition::Poison);
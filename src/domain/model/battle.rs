@@ -0,0 +1,191 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle_action::SlotPosition;
+use crate::domain::model::battle_format::BattleFormat;
+use crate::domain::model::battle_result::BattleResult;
+use crate::domain::model::battle_rng::SeededRng;
+use crate::domain::model::battle_side::BattleSide;
+use crate::domain::model::field::Field;
+use crate::domain::model::format::BattleRules;
+use serde::{Deserialize, Serialize};
+
+/// ひんしになったポケモンの代わりを出すよう、サイドに求める内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplacementRequest {
+    pub side: usize,
+    /// 空いた枠
+    pub slots: Vec<usize>,
+    /// 繰り出せる控えのパーティ番号
+    pub candidates: Vec<usize>,
+}
+
+impl ReplacementRequest {
+    /// 実際に埋める必要がある枠の数（控えが足りない場合は少なくなる）
+    pub fn required_count(&self) -> usize {
+        self.slots.len().min(self.candidates.len())
+    }
+}
+
+/// 進行中のバトル全体の状態。乱数の内部状態も含めてJSONに保存・復元できる
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Battle {
+    pub format: BattleFormat,
+    /// 対戦のルールのうち、バトル中に守るもの
+    #[serde(default)]
+    pub rules: BattleRules,
+    /// 0: プレイヤー側, 1: 相手側
+    pub sides: Vec<BattleSide>,
+    pub field: Field,
+    pub turn: u32,
+    pub result: Option<BattleResult>,
+    /// 乱数のシード値。同じシードと同じ行動からは同じバトルが再現される
+    pub seed: u64,
+    pub rng: SeededRng,
+}
+
+impl Battle {
+    pub fn new(format: BattleFormat, first: BattleSide, second: BattleSide) -> Self {
+        Self {
+            format,
+            rules: BattleRules::default(),
+            sides: vec![first, second],
+            field: Field::default(),
+            turn: 0,
+            result: None,
+            seed: 0,
+            rng: SeededRng::new(0),
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = SeededRng::new(seed);
+        self
+    }
+
+    pub fn with_rules(mut self, rules: BattleRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// ターン終了時に、ひんしで空いた枠への交代を各サイドに求める
+    pub fn replacement_requests(&self) -> Vec<ReplacementRequest> {
+        if self.is_over() {
+            return vec![];
+        }
+        self.sides
+            .iter()
+            .enumerate()
+            .filter_map(|(side_index, side)| {
+                let slots: Vec<usize> = (0..side.active.len())
+                    .filter(|slot| !side.is_slot_alive(*slot))
+                    .collect();
+                let candidates = side.bench();
+                if slots.is_empty() || candidates.is_empty() {
+                    return None;
+                }
+                Some(ReplacementRequest {
+                    side: side_index,
+                    slots,
+                    candidates,
+                })
+            })
+            .collect()
+    }
+
+    pub fn pokemon_at(&self, position: SlotPosition) -> Option<&ActivePokemon> {
+        self.sides.get(position.side)?.active_pokemon(position.slot)
+    }
+
+    pub fn pokemon_at_mut(&mut self, position: SlotPosition) -> Option<&mut ActivePokemon> {
+        self.sides
+            .get_mut(position.side)?
+            .active_pokemon_mut(position.slot)
+    }
+
+    /// 位置にひんしでないポケモンがいるか
+    pub fn is_alive(&self, position: SlotPosition) -> bool {
+        self.sides
+            .get(position.side)
+            .is_some_and(|side| side.is_slot_alive(position.slot))
+    }
+
+    /// 場に出ている（ひんしでない）ポケモンの位置を、サイド・枠の順に返す
+    pub fn active_positions(&self) -> Vec<SlotPosition> {
+        (0..self.sides.len())
+            .flat_map(|side| {
+                (0..self.format.active_slots()).map(move |slot| SlotPosition::new(side, slot))
+            })
+            .filter(|position| self.is_alive(*position))
+            .collect()
+    }
+
+    /// 味方の位置（シングルでは存在しない）
+    pub fn ally_positions(&self, position: SlotPosition) -> Vec<SlotPosition> {
+        (0..self.format.active_slots())
+            .map(|slot| SlotPosition::new(position.side, slot))
+            .filter(|ally| ally.is_ally_of(&position))
+            .collect()
+    }
+
+    pub fn opponent_positions(&self, position: SlotPosition) -> Vec<SlotPosition> {
+        let opponent_side = 1 - position.side;
+        (0..self.format.active_slots())
+            .map(|slot| SlotPosition::new(opponent_side, slot))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::test_party;
+
+    #[test]
+    fn test_doubles_positions() {
+        let format = BattleFormat::Doubles;
+        let battle = Battle::new(
+            format,
+            BattleSide::new("player".to_string(), test_party(4), 2),
+            BattleSide::new("opponent".to_string(), test_party(4), 2),
+        );
+
+        let user = SlotPosition::new(0, 0);
+        assert_eq!(battle.active_positions().len(), 4);
+        assert_eq!(battle.ally_positions(user), vec![SlotPosition::new(0, 1)]);
+        assert_eq!(
+            battle.opponent_positions(user),
+            vec![SlotPosition::new(1, 0), SlotPosition::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_replacement_requests() {
+        let mut battle = Battle::new(
+            BattleFormat::Doubles,
+            BattleSide::new("player".to_string(), test_party(3), 2),
+            BattleSide::new("opponent".to_string(), test_party(2), 2),
+        );
+        battle
+            .pokemon_at_mut(SlotPosition::new(0, 1))
+            .unwrap()
+            .take_damage(1000);
+        battle
+            .pokemon_at_mut(SlotPosition::new(1, 0))
+            .unwrap()
+            .take_damage(1000);
+
+        // 控えのいない相手側には求めない
+        assert_eq!(
+            battle.replacement_requests(),
+            vec![ReplacementRequest {
+                side: 0,
+                slots: vec![1],
+                candidates: vec![2],
+            }]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// 場の位置（どちらのサイドの何番目の枠か）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SlotPosition {
+    pub side: usize,
+    pub slot: usize,
+}
+
+impl SlotPosition {
+    pub fn new(side: usize, slot: usize) -> Self {
+        Self { side, slot }
+    }
+
+    pub fn is_ally_of(&self, other: &SlotPosition) -> bool {
+        self.side == other.side && self.slot != other.slot
+    }
+
+    pub fn is_opponent_of(&self, other: &SlotPosition) -> bool {
+        self.side != other.side
+    }
+}
+
+/// 1体のポケモンがそのターンに選択する行動
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BattleAction {
+    /// 覚えている技の番号と、対象を選ぶ技の場合はその対象。技を使う前にテラスタルすることもできる
+    UseMove {
+        move_index: usize,
+        target: Option<SlotPosition>,
+        #[serde(default)]
+        terastallize: bool,
+    },
+    /// 控えのパーティ番号への交代
+    Switch { party_index: usize },
+    /// 使える技がない場合の「わるあがき」
+    Struggle,
+    /// 反動で動けないターン
+    Recharge,
+}
+
+/// 行動者の位置と行動の組
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChosenAction {
+    pub user: SlotPosition,
+    pub action: BattleAction,
+}
+
+impl ChosenAction {
+    pub fn new(user: SlotPosition, action: BattleAction) -> Self {
+        Self { user, action }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_position_relations() {
+        let user = SlotPosition::new(0, 0);
+        assert!(SlotPosition::new(0, 1).is_ally_of(&user));
+        assert!(!user.is_ally_of(&user));
+        assert!(SlotPosition::new(1, 0).is_opponent_of(&user));
+    }
+}
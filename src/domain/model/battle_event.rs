@@ -0,0 +1,176 @@
+use crate::domain::model::battle_action::SlotPosition;
+use crate::domain::model::battle_result::BattleResult;
+use crate::domain::model::field::{Terrain, Weather};
+use crate::domain::model::stats::StatName;
+use crate::domain::model::status::StatusCondition;
+use serde::{Deserialize, Serialize};
+
+/// ダメージの発生源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DamageSource {
+    Move,
+    Recoil,
+    Status,
+    Weather,
+    /// バインド技による継続ダメージ
+    Binding,
+}
+
+/// 技の相性（等倍以外のときに通知する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Effectiveness {
+    NoEffect,
+    NotVeryEffective,
+    SuperEffective,
+}
+
+/// 行動できなかった理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CantMoveReason {
+    Asleep,
+    Frozen,
+    Paralyzed,
+    Recharging,
+}
+
+/// バトル中に起きた出来事。UI・リプレイ・統計・テストはこの列を読み取る
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BattleEvent {
+    TurnStarted {
+        turn: u32,
+    },
+    MoveUsed {
+        user: SlotPosition,
+        pokemon: String,
+        move_name: String,
+    },
+    MoveFailed {
+        user: SlotPosition,
+    },
+    CantMove {
+        user: SlotPosition,
+        reason: CantMoveReason,
+    },
+    Missed {
+        user: SlotPosition,
+        target: SlotPosition,
+    },
+    CriticalHit {
+        target: SlotPosition,
+    },
+    Effectiveness {
+        target: SlotPosition,
+        effectiveness: Effectiveness,
+    },
+    Damage {
+        target: SlotPosition,
+        pokemon: String,
+        source: DamageSource,
+        /// 相手側のプレイヤーの視点（PlayerView）では、amount と remaining_hp も割合（%）になる
+        amount: u32,
+        /// 最大HPに対する割合（%）
+        percentage: u32,
+        remaining_hp: u32,
+    },
+    Healed {
+        target: SlotPosition,
+        amount: u32,
+        remaining_hp: u32,
+    },
+    StatusApplied {
+        target: SlotPosition,
+        status: StatusCondition,
+    },
+    StatusCured {
+        target: SlotPosition,
+        status: StatusCondition,
+    },
+    StatChanged {
+        target: SlotPosition,
+        stat: StatName,
+        change: i32,
+    },
+    Switched {
+        position: SlotPosition,
+        pokemon: String,
+        party_index: usize,
+    },
+    Fainted {
+        position: SlotPosition,
+        pokemon: String,
+    },
+    WeatherStarted {
+        weather: Weather,
+    },
+    WeatherEnded {
+        weather: Weather,
+    },
+    TerrainStarted {
+        terrain: Terrain,
+    },
+    TerrainEnded {
+        terrain: Terrain,
+    },
+    AbilityActivated {
+        position: SlotPosition,
+        ability: String,
+    },
+    ItemActivated {
+        position: SlotPosition,
+        item: String,
+    },
+    /// 技を縛る状態やバインドなど、一時的な状態が始まった
+    EffectStarted {
+        target: SlotPosition,
+        effect: String,
+    },
+    Terastallized {
+        position: SlotPosition,
+        tera_type: String,
+    },
+    BattleEnded {
+        result: BattleResult,
+    },
+}
+
+/// バトルの出来事を受け取る側のインターフェース
+pub trait BattleObserver {
+    fn on_event(&mut self, event: &BattleEvent);
+
+    fn on_events(&mut self, events: &[BattleEvent]) {
+        for event in events {
+            self.on_event(event);
+        }
+    }
+}
+
+/// 受け取った出来事をそのまま蓄積するオブザーバー
+#[derive(Debug, Clone, Default)]
+pub struct EventLog {
+    pub events: Vec<BattleEvent>,
+}
+
+impl BattleObserver for EventLog {
+    fn on_event(&mut self, event: &BattleEvent) {
+        self.events.push(event.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_log_collects_events() {
+        let mut log = EventLog::default();
+        log.on_events(&[
+            BattleEvent::TurnStarted { turn: 1 },
+            BattleEvent::MoveFailed {
+                user: SlotPosition::new(0, 0),
+            },
+        ]);
+
+        assert_eq!(log.events.len(), 2);
+        assert_eq!(log.events[0], BattleEvent::TurnStarted { turn: 1 });
+    }
+}
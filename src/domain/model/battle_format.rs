@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// バトル形式（シングル / ダブル）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BattleFormat {
+    Singles,
+    Doubles,
+}
+
+impl BattleFormat {
+    /// 1サイドあたりの場に出せるポケモンの数
+    pub fn active_slots(&self) -> usize {
+        match self {
+            BattleFormat::Singles => 1,
+            BattleFormat::Doubles => 2,
+        }
+    }
+
+    /// 見せ合いで選出する数（シングルは6体から3体、ダブルは6体から4体）
+    pub fn bring_count(&self) -> usize {
+        match self {
+            BattleFormat::Singles => 3,
+            BattleFormat::Doubles => 4,
+        }
+    }
+
+    pub fn is_doubles(&self) -> bool {
+        matches!(self, BattleFormat::Doubles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_slots() {
+        assert_eq!(BattleFormat::Singles.active_slots(), 1);
+        assert_eq!(BattleFormat::Doubles.active_slots(), 2);
+        assert!(BattleFormat::Doubles.is_doubles());
+        assert_eq!(BattleFormat::Doubles.bring_count(), 4);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::domain::model::battle::Battle;
+
+/// ターンごとのバトルの状態を記録し、任意のターンへの巻き戻しと分岐を可能にする。
+/// 各状態は乱数の内部状態も含むため、同じ分岐点から同じ行動を選べば同じ展開になる
+#[derive(Debug, Clone)]
+pub struct BattleHistory {
+    /// 分岐ごとの記録。添字 n はターン `base_turn + n` 終了時点（交代を含む）の状態で、0 は記録を始めた時点
+    branches: Vec<Vec<Battle>>,
+    current: usize,
+    /// 記録を始めた時点のターン。途中から再開したバトルでは 0 にならない
+    base_turn: u32,
+}
+
+impl BattleHistory {
+    pub fn new(initial: &Battle) -> Self {
+        Self {
+            branches: vec![vec![initial.clone()]],
+            current: 0,
+            base_turn: initial.turn,
+        }
+    }
+
+    /// 現在の分岐にバトルの状態を記録する。同じターンを再度記録した場合（ひんし後の交代など）は上書きする
+    pub fn record(&mut self, battle: &Battle) {
+        let index = battle.turn.saturating_sub(self.base_turn) as usize;
+        let line = &mut self.branches[self.current];
+        line.truncate(index);
+        line.push(battle.clone());
+    }
+
+    pub fn current_branch(&self) -> usize {
+        self.current
+    }
+
+    pub fn branch_count(&self) -> usize {
+        self.branches.len()
+    }
+
+    /// 現在の分岐で記録されている最新のターン
+    pub fn latest_turn(&self) -> u32 {
+        self.base_turn + (self.branches[self.current].len() - 1) as u32
+    }
+
+    /// 指定した分岐のターン終了時点の状態
+    pub fn state_at(&self, branch: usize, turn: u32) -> Option<&Battle> {
+        self.branches.get(branch)?.get(self.index(turn)?)
+    }
+
+    /// 現在の分岐のままターン終了時点まで巻き戻す。それより後の記録は破棄される
+    pub fn rewind(&mut self, turn: u32) -> Option<Battle> {
+        let index = self.index(turn)?;
+        let line = &mut self.branches[self.current];
+        let battle = line.get(index)?.clone();
+        line.truncate(index + 1);
+        Some(battle)
+    }
+
+    /// 元の展開を残したまま、指定したターン終了時点から新しい分岐を作って切り替える
+    pub fn branch_from(&mut self, turn: u32) -> Option<Battle> {
+        let index = self.index(turn)?;
+        let line = &self.branches[self.current];
+        let battle = line.get(index)?.clone();
+        let new_line = line[..=index].to_vec();
+        self.branches.push(new_line);
+        self.current = self.branches.len() - 1;
+        Some(battle)
+    }
+
+    /// 別の分岐に切り替え、その分岐の最新の状態を返す
+    pub fn switch_branch(&mut self, branch: usize) -> Option<Battle> {
+        let battle = self.branches.get(branch)?.last()?.clone();
+        self.current = branch;
+        Some(battle)
+    }
+
+    /// ターンに対応する記録の添字。記録を始める前のターンは None
+    fn index(&self, turn: u32) -> Option<usize> {
+        turn.checked_sub(self.base_turn).map(|index| index as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::battle_engine::BattleEngine;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    fn use_move(side: usize, move_index: usize) -> ChosenAction {
+        ChosenAction::new(
+            SlotPosition::new(side, 0),
+            BattleAction::UseMove {
+                move_index,
+                target: None,
+                terastallize: false,
+            },
+        )
+    }
+
+    #[test]
+    fn test_branch_keeps_original_line() {
+        let moves = vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move("swift", "normal", "special", Some(60), "all-opponents"),
+        ];
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(5);
+        let engine = BattleEngine::new(TypeChart::default());
+        let mut history = BattleHistory::new(&battle);
+        for _ in 0..2 {
+            engine
+                .execute_turn(&mut battle, vec![use_move(0, 0), use_move(1, 0)])
+                .unwrap();
+            history.record(&battle);
+        }
+        let original_hp = battle.sides[1].party[0].current_hp;
+
+        let mut branch = history.branch_from(1).unwrap();
+        engine
+            .execute_turn(&mut branch, vec![use_move(0, 1), use_move(1, 0)])
+            .unwrap();
+        history.record(&branch);
+
+        assert_eq!(history.branch_count(), 2);
+        assert_ne!(branch.sides[1].party[0].current_hp, original_hp);
+        let original = history.switch_branch(0).unwrap();
+        assert_eq!(original.turn, 2);
+        assert_eq!(original.sides[1].party[0].current_hp, original_hp);
+
+        // 同じ分岐点から同じ行動を選ぶと同じ展開になる
+        let mut replayed = history.state_at(0, 1).unwrap().clone();
+        engine
+            .execute_turn(&mut replayed, vec![use_move(0, 0), use_move(1, 0)])
+            .unwrap();
+        assert_eq!(replayed.sides[1].party[0].current_hp, original_hp);
+    }
+
+    #[test]
+    fn test_rewind_discards_later_turns() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(9);
+        let engine = BattleEngine::new(TypeChart::default());
+        let mut history = BattleHistory::new(&battle);
+        for _ in 0..2 {
+            engine
+                .execute_turn(&mut battle, vec![use_move(0, 0), use_move(1, 0)])
+                .unwrap();
+            history.record(&battle);
+        }
+        assert_eq!(history.latest_turn(), 2);
+
+        let rewound = history.rewind(1).unwrap();
+        assert_eq!(rewound.turn, 1);
+        assert_eq!(history.current_branch(), 0);
+        assert_eq!(history.latest_turn(), 1);
+        assert!(history.state_at(0, 2).is_none());
+        assert!(history.rewind(5).is_none());
+    }
+
+    #[test]
+    fn test_history_started_mid_battle() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(3);
+        // 保存した途中の状態（ターン5終了時点）から記録を始める
+        battle.turn = 5;
+        let engine = BattleEngine::new(TypeChart::default());
+        let mut history = BattleHistory::new(&battle);
+        assert_eq!(history.latest_turn(), 5);
+        for _ in 0..2 {
+            engine
+                .execute_turn(&mut battle, vec![use_move(0, 0), use_move(1, 0)])
+                .unwrap();
+            history.record(&battle);
+        }
+        // 同じターンをもう一度記録すると上書きになる
+        history.record(&battle);
+
+        assert_eq!(history.latest_turn(), 7);
+        assert_eq!(history.state_at(0, 6).unwrap().turn, 6);
+        assert!(history.state_at(0, 4).is_none());
+        assert_eq!(history.branch_from(6).unwrap().turn, 6);
+        assert_eq!(history.latest_turn(), 6);
+        assert_eq!(history.rewind(5).unwrap().turn, 5);
+        assert_eq!(history.latest_turn(), 5);
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// バトルの決着
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BattleResult {
+    /// 勝利したサイドの番号
+    Win(usize),
+    Draw,
+}
+
+impl BattleResult {
+    pub fn winner(&self) -> Option<usize> {
+        match self {
+            BattleResult::Win(side) => Some(*side),
+            BattleResult::Draw => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winner() {
+        assert_eq!(BattleResult::Win(1).winner(), Some(1));
+        assert_eq!(BattleResult::Draw.winner(), None);
+    }
+}
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+
+/// バトル中の乱数源。実装を差し替えられるようにトレイトとして定義する
+pub trait BattleRng {
+    /// 32bit の乱数を返す
+    fn next_u32(&mut self) -> u32;
+
+    /// 0 以上 bound 未満の乱数を返す
+    fn next_below(&mut self, bound: u32) -> u32 {
+        ((self.next_u32() as u64 * bound as u64) >> 32) as u32
+    }
+
+    /// numerator / denominator の確率で true を返す
+    fn chance(&mut self, numerator: u32, denominator: u32) -> bool {
+        self.next_below(denominator) < numerator
+    }
+}
+
+/// シード値から決定的に乱数列を生成する標準の実装（PCG32）。
+/// 整数演算のみで構成しているため、同じシードならどの環境でも同じ乱数列になる
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeededRng {
+    state: u64,
+}
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+const PCG_INCREMENT: u64 = 1442695040888963407;
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self { state: 0 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+}
+
+impl BattleRng for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        let old_state = self.state;
+        self.state = old_state
+            .wrapping_mul(PCG_MULTIPLIER)
+            .wrapping_add(PCG_INCREMENT);
+        let xorshifted = (((old_state >> 18) ^ old_state) >> 27) as u32;
+        let rotation = (old_state >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+}
+
+/// 分岐数がこれより多い乱数（ダメージ乱数など）は、最小・中央・最大の3通りで代表させる
+const MAX_CHANCE_OUTCOMES: u32 = 3;
+
+/// 探索用に、乱数で結果が分かれる箇所（命中・急所・ダメージ乱数・追加効果など）を列挙する実装。
+/// choices で各分岐点の選択を指定して1回分の展開を再現し、next_choices で次の展開に進む。
+/// 起こる確率が min_probability を下回る分岐は、最も起こりやすい結果にまとめて枝を減らす
+#[derive(Debug, Clone)]
+pub struct ChanceRng {
+    choices: Vec<usize>,
+    /// 通過した分岐点ごとの (選んだ結果, 結果の数)
+    branches: Vec<(usize, usize)>,
+    probability: f64,
+    min_probability: f64,
+}
+
+impl ChanceRng {
+    pub fn new(choices: Vec<usize>, min_probability: f64) -> Self {
+        Self {
+            choices,
+            branches: Vec::new(),
+            probability: 1.0,
+            min_probability,
+        }
+    }
+
+    /// この展開が起こる確率
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// 次に試す展開の選択。すべて試し終えた場合は None
+    pub fn next_choices(&self) -> Option<Vec<usize>> {
+        let mut branches = self.branches.clone();
+        while let Some((choice, count)) = branches.pop() {
+            if choice + 1 < count {
+                let mut choices: Vec<usize> = branches.iter().map(|(choice, _)| *choice).collect();
+                choices.push(choice + 1);
+                return Some(choices);
+            }
+        }
+        None
+    }
+
+    /// 起こりやすい順に並べた結果の確率から1つを選び、その添字を返す
+    fn branch(&mut self, probabilities: &[f64]) -> usize {
+        let negligible = probabilities
+            .iter()
+            .any(|probability| self.probability * probability < self.min_probability);
+        if negligible {
+            return 0;
+        }
+        let choice = self
+            .choices
+            .get(self.branches.len())
+            .copied()
+            .unwrap_or(0)
+            .min(probabilities.len() - 1);
+        self.branches.push((choice, probabilities.len()));
+        self.probability *= probabilities[choice];
+        choice
+    }
+}
+
+impl BattleRng for ChanceRng {
+    fn next_u32(&mut self) -> u32 {
+        u32::MAX / 2
+    }
+
+    fn next_below(&mut self, bound: u32) -> u32 {
+        if bound <= 1 {
+            return 0;
+        }
+        let count = bound.min(MAX_CHANCE_OUTCOMES);
+        // 中央の値を最初に試す
+        let mut values: Vec<u32> = (0..count)
+            .map(|index| index * (bound - 1) / (count - 1))
+            .collect();
+        values.rotate_left(count as usize / 2);
+        let probabilities = vec![1.0 / count as f64; count as usize];
+        values[self.branch(&probabilities)]
+    }
+
+    fn chance(&mut self, numerator: u32, denominator: u32) -> bool {
+        if numerator >= denominator {
+            return true;
+        }
+        if numerator == 0 {
+            return false;
+        }
+        let success = numerator as f64 / denominator as f64;
+        if success >= 0.5 {
+            self.branch(&[success, 1.0 - success]) == 0
+        } else {
+            self.branch(&[1.0 - success, success]) == 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::ScriptedRng;
+
+    #[test]
+    fn test_seeded_rng_is_reproducible() {
+        let mut first = SeededRng::new(42);
+        let mut second = SeededRng::new(42);
+        let first_rolls: Vec<u32> = (0..5).map(|_| first.next_u32()).collect();
+        let second_rolls: Vec<u32> = (0..5).map(|_| second.next_u32()).collect();
+
+        assert_eq!(first_rolls, second_rolls);
+        assert_ne!(SeededRng::new(43).next_u32(), first_rolls[0]);
+    }
+
+    #[test]
+    fn test_next_below_stays_in_range() {
+        let mut rng = SeededRng::new(7);
+        assert!((0..100).all(|_| rng.next_below(16) < 16));
+    }
+
+    #[test]
+    fn test_scripted_rng_returns_predetermined_rolls() {
+        let mut rng = ScriptedRng::new(vec![3, 50]);
+        assert_eq!(rng.next_below(16), 3);
+        assert_eq!(rng.next_below(16), 15);
+        assert_eq!(rng.next_below(16), 15);
+        assert!(!rng.chance(1, 24));
+    }
+
+    #[test]
+    fn test_chance_rng_enumerates_outcomes() {
+        let mut choices = Some(Vec::new());
+        let mut total = 0.0;
+        let mut outcomes = Vec::new();
+        while let Some(current) = choices {
+            let mut rng = ChanceRng::new(current, 0.0);
+            let hit = rng.chance(9, 10);
+            let roll = rng.next_below(16);
+            total += rng.probability();
+            outcomes.push((hit, roll));
+            choices = rng.next_choices();
+        }
+        assert_eq!(outcomes.len(), 6);
+        assert_eq!(outcomes[0], (true, 7));
+        assert!((total - 1.0).abs() < 1e-9);
+
+        // 起こりにくい分岐はまとめる
+        let mut rng = ChanceRng::new(Vec::new(), 0.05);
+        assert!(!rng.chance(1, 24));
+        assert_eq!(rng.next_choices(), None);
+    }
+}
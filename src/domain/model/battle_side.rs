@@ -0,0 +1,126 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use serde::{Deserialize, Serialize};
+
+/// バトルに参加する片側のプレイヤーの状態
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BattleSide {
+    pub name: String,
+    pub party: Vec<ActivePokemon>,
+    /// 場の枠ごとに、出ているポケモンのパーティ番号（空きなら None）
+    pub active: Vec<Option<usize>>,
+    /// このバトルでテラスタルを使ったか（1バトルに1回）
+    pub tera_used: bool,
+    /// 見せ合いで相手に見せたポケモン名。選出しなかったポケモンも含む
+    #[serde(default)]
+    pub preview: Vec<String>,
+    /// 場に出たことのあるポケモンのパーティ番号（出た順）
+    #[serde(default)]
+    pub sent_out: Vec<usize>,
+}
+
+impl BattleSide {
+    /// パーティの先頭から、ひんしでないポケモンを枠の数だけ場に出す
+    pub fn new(name: String, party: Vec<ActivePokemon>, active_slots: usize) -> Self {
+        let mut active: Vec<Option<usize>> = party
+            .iter()
+            .enumerate()
+            .filter(|(_, pokemon)| !pokemon.is_fainted())
+            .map(|(index, _)| Some(index))
+            .take(active_slots)
+            .collect();
+        active.resize(active_slots, None);
+        let preview = party.iter().map(|pokemon| pokemon.name.clone()).collect();
+        let sent_out = active.iter().flatten().copied().collect();
+
+        Self {
+            name,
+            party,
+            active,
+            tera_used: false,
+            preview,
+            sent_out,
+        }
+    }
+
+    /// 枠に出ているポケモン（ひんし状態も含む）
+    pub fn active_pokemon(&self, slot: usize) -> Option<&ActivePokemon> {
+        self.active
+            .get(slot)
+            .copied()
+            .flatten()
+            .map(|index| &self.party[index])
+    }
+
+    pub fn active_pokemon_mut(&mut self, slot: usize) -> Option<&mut ActivePokemon> {
+        let index = self.active.get(slot).copied().flatten()?;
+        self.party.get_mut(index)
+    }
+
+    /// 枠にひんしでないポケモンが出ているか
+    pub fn is_slot_alive(&self, slot: usize) -> bool {
+        self.active_pokemon(slot)
+            .is_some_and(|pokemon| !pokemon.is_fainted())
+    }
+
+    pub fn is_active(&self, party_index: usize) -> bool {
+        self.active.contains(&Some(party_index))
+    }
+
+    /// 交代先として選べる控えのパーティ番号
+    pub fn bench(&self) -> Vec<usize> {
+        self.party
+            .iter()
+            .enumerate()
+            .filter(|(index, pokemon)| !pokemon.is_fainted() && !self.is_active(*index))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn has_remaining_pokemon(&self) -> bool {
+        self.party.iter().any(|pokemon| !pokemon.is_fainted())
+    }
+
+    pub fn switch_in(&mut self, slot: usize, party_index: usize) {
+        self.active[slot] = Some(party_index);
+        if !self.sent_out.contains(&party_index) {
+            self.sent_out.push(party_index);
+        }
+    }
+
+    /// 交代先がいないため、ひんしのポケモンが残った枠を空ける
+    pub fn clear_fainted_slots(&mut self) {
+        if !self.bench().is_empty() {
+            return;
+        }
+        for slot in 0..self.active.len() {
+            if self
+                .active_pokemon(slot)
+                .is_some_and(|pokemon| pokemon.is_fainted())
+            {
+                self.active[slot] = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::test_party;
+
+    #[test]
+    fn test_new_doubles_side() {
+        let side = BattleSide::new("player".to_string(), test_party(4), 2);
+        assert_eq!(side.active, vec![Some(0), Some(1)]);
+        assert_eq!(side.bench(), vec![2, 3]);
+        assert!(side.is_slot_alive(1));
+    }
+
+    #[test]
+    fn test_switch_in() {
+        let mut side = BattleSide::new("player".to_string(), test_party(3), 1);
+        side.switch_in(0, 2);
+        assert_eq!(side.active, vec![Some(2)]);
+        assert_eq!(side.bench(), vec![0, 1]);
+    }
+}
@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Language code value object
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -86,6 +86,26 @@ struct NamedAPIResource {
     url: Option<String>,
 }
 
+/// NamedAPIResource 形式から名前だけを取り出す
+pub fn deserialize_name_from_resource<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let resource = NamedAPIResource::deserialize(deserializer)?;
+    Ok(resource.name)
+}
+
+/// null になり得る NamedAPIResource から名前だけを取り出す
+pub fn deserialize_optional_name_from_resource<'de, D>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let resource: Option<NamedAPIResource> = Option::deserialize(deserializer)?;
+    Ok(resource.map(|r| r.name))
+}
+
 impl From<NamedAPIResource> for Language {
     fn from(resource: NamedAPIResource) -> Self {
         Self::new(resource.name)
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// 天気
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    Sun,
+    Rain,
+    Sandstorm,
+    Snow,
+}
+
+/// フィールド
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Terrain {
+    Electric,
+    Grassy,
+    Misty,
+    Psychic,
+}
+
+/// 技で発生させた天気・フィールドの継続ターン数
+pub const FIELD_EFFECT_TURNS: u32 = 5;
+
+/// 場全体の状態
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
+    pub weather: Option<Weather>,
+    pub weather_turns: u32,
+    pub terrain: Option<Terrain>,
+    pub terrain_turns: u32,
+}
+
+impl Field {
+    pub fn set_weather(&mut self, weather: Weather) {
+        self.weather = Some(weather);
+        self.weather_turns = FIELD_EFFECT_TURNS;
+    }
+
+    pub fn set_terrain(&mut self, terrain: Terrain) {
+        self.terrain = Some(terrain);
+        self.terrain_turns = FIELD_EFFECT_TURNS;
+    }
+
+    /// ターン終了時に残りターンを減らし、終了した天気を返す
+    pub fn tick_weather(&mut self) -> Option<Weather> {
+        self.weather?;
+        self.weather_turns = self.weather_turns.saturating_sub(1);
+        if self.weather_turns == 0 {
+            return self.weather.take();
+        }
+        None
+    }
+
+    /// ターン終了時に残りターンを減らし、終了したフィールドを返す
+    pub fn tick_terrain(&mut self) -> Option<Terrain> {
+        self.terrain?;
+        self.terrain_turns = self.terrain_turns.saturating_sub(1);
+        if self.terrain_turns == 0 {
+            return self.terrain.take();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_ends_after_five_turns() {
+        let mut field = Field::default();
+        field.set_weather(Weather::Rain);
+
+        for _ in 0..4 {
+            assert_eq!(field.tick_weather(), None);
+        }
+        assert_eq!(field.tick_weather(), Some(Weather::Rain));
+        assert_eq!(field.weather, None);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::domain::model::battle_format::BattleFormat;
+use crate::domain::model::pokemon_build::PokemonBuild;
+use serde::{Deserialize, Serialize};
+
+/// 一撃必殺技（一撃必殺禁止ルールの対象）
+pub const OHKO_MOVES: [&str; 4] = ["fissure", "guillotine", "horn-drill", "sheer-cold"];
+/// 回避率を上げる技（回避率上昇禁止ルールの対象）
+pub const EVASION_MOVES: [&str; 2] = ["double-team", "minimize"];
+
+/// レベルの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LevelRule {
+    /// すべてのポケモンをこのレベルとして扱う（レベル50フラットなど）
+    Flat(u32),
+    /// このレベルより高いポケモンは参加できない
+    Cap(u32),
+}
+
+/// バトル中にエンジンが守るルール
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BattleRules {
+    /// 相手のポケモンを2体以上ねむり状態にできない
+    pub sleep_clause: bool,
+    /// テラスタルを使える
+    pub terastallization: bool,
+}
+
+impl Default for BattleRules {
+    fn default() -> Self {
+        Self {
+            sleep_clause: false,
+            terastallization: true,
+        }
+    }
+}
+
+/// 対戦のルール（レギュレーション）。formats/ のJSONファイルから読み込み、
+/// パーティの検証とバトルの進行の両方で使う
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Format {
+    pub id: String,
+    pub name: String,
+    pub battle_format: BattleFormat,
+    pub level: LevelRule,
+    /// 登録できるポケモンの数
+    pub team_size: usize,
+    /// 見せ合いで選出する数
+    pub bring_count: usize,
+    /// 同じ道具を2体以上に持たせられない
+    #[serde(default)]
+    pub item_clause: bool,
+    /// 同じポケモンを2体以上登録できない
+    #[serde(default)]
+    pub species_clause: bool,
+    /// 使用できないポケモン。ポケモンの名前か種族の名前で書く（種族ならすべての姿が対象）
+    #[serde(default)]
+    pub banned_species: Vec<String>,
+    /// 登録できる数に制限のあるポケモン（禁止級など）。書き方は banned_species と同じ
+    #[serde(default)]
+    pub restricted_species: Vec<String>,
+    #[serde(default)]
+    pub max_restricted: usize,
+    /// 幻のポケモンを登録できない
+    #[serde(default)]
+    pub no_mythicals: bool,
+    /// いずれかの図鑑に載っているポケモンだけ登録できる（空なら制限なし）
+    #[serde(default)]
+    pub allowed_pokedexes: Vec<String>,
+    #[serde(default)]
+    pub sleep_clause: bool,
+    #[serde(default)]
+    pub ohko_clause: bool,
+    #[serde(default)]
+    pub evasion_clause: bool,
+    /// テラスタルを使えない
+    #[serde(default)]
+    pub no_tera: bool,
+}
+
+impl Format {
+    pub fn battle_rules(&self) -> BattleRules {
+        BattleRules {
+            sleep_clause: self.sleep_clause,
+            terastallization: !self.no_tera,
+        }
+    }
+
+    /// 判定に種族のデータ（幻かどうか・図鑑）が必要なルールがある
+    pub fn requires_species_data(&self) -> bool {
+        self.no_mythicals || !self.allowed_pokedexes.is_empty()
+    }
+
+    /// レベルのルールを反映した型（フラットならレベルを揃える）
+    pub fn apply_level(&self, build: &PokemonBuild) -> PokemonBuild {
+        let mut build = build.clone();
+        if let LevelRule::Flat(level) = self.level {
+            build.level = level;
+        }
+        build
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_format() {
+        let json = r#"{
+            "id": "test-doubles",
+            "name": "Test Doubles",
+            "battle_format": "Doubles",
+            "level": { "flat": 50 },
+            "team_size": 6,
+            "bring_count": 4,
+            "item_clause": true,
+            "restricted_species": ["koraidon"],
+            "max_restricted": 1,
+            "no_mythicals": true,
+            "allowed_pokedexes": ["paldea", "kitakami", "blueberry"],
+            "no_tera": true
+        }"#;
+
+        let format: Format = serde_json::from_str(json).unwrap();
+        assert_eq!(format.level, LevelRule::Flat(50));
+        assert!(format.item_clause);
+        assert!(!format.species_clause);
+        assert!(format.requires_species_data());
+        assert!(!format.battle_rules().terastallization);
+        let build = PokemonBuild {
+            level: 100,
+            ..PokemonBuild::new("koraidon", "orichalcum-pulse")
+        };
+        assert_eq!(format.apply_level(&build).level, 50);
+    }
+}
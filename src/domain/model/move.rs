@@ -151,7 +151,7 @@ pub struct Move {
     pub effect_chance: Option<u32>,
     pub effect_entries: Vec<EffectEntry>,
     pub flavor_text_entries: Vec<FlavorText>,
-    pub names: Vec<MoveName>,
+    pub names: Vec<Name>,
     pub power: MovePower,
     pub pp: MovePP,
     pub priority: MovePriority,
@@ -160,6 +160,48 @@ pub struct Move {
     pub target: MoveTarget,
     #[serde(rename = "type")]
     pub type_info: PokemonTypeName,
+    #[serde(default)]
+    pub meta: Option<MoveMeta>,
+}
+
+/// 各言語での技の名前
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Name {
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_language")]
+    pub language: String,
+}
+
+/// 技の追加効果などの付随情報（PokeAPI の meta）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveMeta {
+    #[serde(deserialize_with = "deserialize_ailment")]
+    pub ailment: String,
+    pub ailment_chance: u32,
+    pub crit_rate: u32,
+    /// 与えたダメージに対するHP吸収の割合（%）。負の値は反動ダメージ
+    pub drain: i32,
+    pub flinch_chance: u32,
+    pub healing: i32,
+    pub min_hits: Option<u32>,
+    pub max_hits: Option<u32>,
+    pub stat_chance: u32,
+}
+
+// Helper function to deserialize the language resource to its name
+fn deserialize_language<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct NamedAPIResource {
+        name: String,
+        #[allow(dead_code)]
+        url: Option<String>,
+    }
+
+    let resource = NamedAPIResource::deserialize(deserializer)?;
+    Ok(resource.name)
 }
 
 // Helper function to deserialize NamedAPIResource to DamageClass
@@ -220,6 +262,28 @@ where
         .ok_or_else(|| serde::de::Error::custom(format!("Unknown stat name: {}", resource.name)))
 }
 
+// Helper function to deserialize NamedAPIResource to ailment name
+fn deserialize_ailment<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // 保存したバトル状態から読み込む場合は名前だけが書かれている
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Ailment {
+        Resource {
+            name: String,
+            #[allow(dead_code)]
+            url: Option<String>,
+        },
+        Name(String),
+    }
+
+    match Ailment::deserialize(deserializer)? {
+        Ailment::Resource { name, .. } | Ailment::Name(name) => Ok(name),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EffectEntry {
     pub effect: String,
@@ -300,4 +364,46 @@ mod tests {
         assert_eq!(move_obj.pp.value(), 40);
         assert_eq!(move_obj.priority.value(), 0);
     }
+
+    #[test]
+    fn test_deserialize_move_meta() {
+        let json_data = r#"
+        {
+            "id": 394,
+            "name": "flare-blitz",
+            "accuracy": 100,
+            "damage_class": { "name": "physical", "url": "" },
+            "effect_chance": 10,
+            "effect_entries": [],
+            "flavor_text_entries": [],
+            "names": [],
+            "power": 120,
+            "pp": 15,
+            "priority": 0,
+            "stat_changes": [],
+            "target": { "name": "selected-pokemon", "url": "" },
+            "type": { "name": "fire", "url": "" },
+            "meta": {
+                "ailment": { "name": "burn", "url": "" },
+                "ailment_chance": 10,
+                "category": { "name": "damage+ailment", "url": "" },
+                "crit_rate": 0,
+                "drain": -33,
+                "flinch_chance": 0,
+                "healing": 0,
+                "max_hits": null,
+                "max_turns": null,
+                "min_hits": null,
+                "min_turns": null,
+                "stat_chance": 0
+            }
+        }
+        "#;
+
+        let move_obj: Move = serde_json::from_str(json_data).unwrap();
+        let meta = move_obj.meta.unwrap();
+        assert_eq!(meta.ailment, "burn");
+        assert_eq!(meta.ailment_chance, 10);
+        assert_eq!(meta.drain, -33);
+    }
 }
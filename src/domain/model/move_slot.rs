@@ -0,0 +1,111 @@
+use crate::domain::model::r#move::{DamageClass, Move, MoveMeta, MoveTarget};
+use crate::domain::model::stats::StatName;
+use serde::{Deserialize, Serialize};
+
+/// バトル中のポケモンが覚えている技1つ分の情報（残りPPを含む）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveSlot {
+    pub name: String,
+    pub type_name: String,
+    pub damage_class: DamageClass,
+    pub power: Option<u32>,
+    pub accuracy: Option<u32>,
+    pub priority: i32,
+    pub target: MoveTarget,
+    pub stat_changes: Vec<(StatName, i32)>,
+    pub meta: MoveMeta,
+    pub current_pp: u32,
+    pub max_pp: u32,
+}
+
+impl MoveSlot {
+    pub fn new(move_data: &Move) -> Self {
+        Self {
+            name: move_data.name.clone(),
+            type_name: move_data.type_info.name.clone(),
+            damage_class: move_data.damage_class.clone(),
+            power: move_data.power.value(),
+            accuracy: move_data.accuracy,
+            priority: move_data.priority.value(),
+            target: move_data.target.clone(),
+            stat_changes: move_data
+                .stat_changes
+                .iter()
+                .map(|change| (change.stat.clone(), change.change))
+                .collect(),
+            meta: move_data.meta.clone().unwrap_or_default(),
+            current_pp: move_data.pp.value(),
+            max_pp: move_data.pp.value(),
+        }
+    }
+
+    /// 出せる技がない場合に使う「わるあがき」（タイプなし・必中・威力50）
+    pub fn struggle() -> Self {
+        Self {
+            name: "struggle".to_string(),
+            type_name: "typeless".to_string(),
+            damage_class: DamageClass::Physical,
+            power: Some(50),
+            accuracy: None,
+            priority: 0,
+            target: MoveTarget::RandomOpponent,
+            stat_changes: Vec::new(),
+            meta: MoveMeta::default(),
+            current_pp: 1,
+            max_pp: 1,
+        }
+    }
+
+    /// ポイントアップを反映した最大PPにする（残りPPも最大まで回復する）
+    pub fn with_max_pp(mut self, max_pp: u32) -> Self {
+        self.max_pp = max_pp;
+        self.current_pp = max_pp;
+        self
+    }
+
+    pub fn has_pp(&self) -> bool {
+        self.current_pp > 0
+    }
+
+    pub fn consume_pp(&mut self) {
+        self.current_pp = self.current_pp.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_move_slot() {
+        let json_data = r#"
+        {
+            "id": 33,
+            "name": "tackle",
+            "accuracy": 100,
+            "damage_class": { "name": "physical", "url": "" },
+            "effect_chance": null,
+            "effect_entries": [],
+            "flavor_text_entries": [],
+            "names": [],
+            "power": 40,
+            "pp": 35,
+            "priority": 0,
+            "stat_changes": [],
+            "target": { "name": "selected-pokemon", "url": "" },
+            "type": { "name": "normal", "url": "" }
+        }
+        "#;
+        let move_data: Move = serde_json::from_str(json_data).unwrap();
+
+        let mut slot = MoveSlot::new(&move_data);
+        assert_eq!(slot.name, "tackle");
+        assert_eq!(slot.type_name, "normal");
+        assert_eq!(slot.power, Some(40));
+        assert_eq!(slot.current_pp, 35);
+
+        slot.consume_pp();
+        assert_eq!(slot.current_pp, 34);
+        assert!(slot.has_pp());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::domain::model::stats::StatName;
+use serde::{Deserialize, Serialize};
+
+/// 性格。HP以外の能力値を1つ1.1倍、1つ0.9倍にする（無補正の性格もある）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Nature {
+    #[default]
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+/// 性格の並び順（上昇する能力 × 下降する能力）に対応する能力
+const NATURE_STATS: [StatName; 5] = [
+    StatName::Attack,
+    StatName::Defense,
+    StatName::Speed,
+    StatName::SpecialAttack,
+    StatName::SpecialDefense,
+];
+
+impl Nature {
+    pub const ALL: [Nature; 25] = [
+        Nature::Hardy,
+        Nature::Lonely,
+        Nature::Brave,
+        Nature::Adamant,
+        Nature::Naughty,
+        Nature::Bold,
+        Nature::Docile,
+        Nature::Relaxed,
+        Nature::Impish,
+        Nature::Lax,
+        Nature::Timid,
+        Nature::Hasty,
+        Nature::Serious,
+        Nature::Jolly,
+        Nature::Naive,
+        Nature::Modest,
+        Nature::Mild,
+        Nature::Quiet,
+        Nature::Bashful,
+        Nature::Rash,
+        Nature::Calm,
+        Nature::Gentle,
+        Nature::Sassy,
+        Nature::Careful,
+        Nature::Quirky,
+    ];
+
+    /// 性格の名前（英語の小文字）
+    pub fn name(self) -> &'static str {
+        const NAMES: [&str; 25] = [
+            "hardy", "lonely", "brave", "adamant", "naughty", "bold", "docile", "relaxed",
+            "impish", "lax", "timid", "hasty", "serious", "jolly", "naive", "modest", "mild",
+            "quiet", "bashful", "rash", "calm", "gentle", "sassy", "careful", "quirky",
+        ];
+        NAMES[self.index()]
+    }
+
+    /// 大文字・小文字を区別せずに名前から性格を探す
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|nature| nature.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|nature| *nature == self)
+            .unwrap_or(0)
+    }
+
+    /// 1.1倍になる能力（無補正の性格は None）
+    pub fn increased(self) -> Option<StatName> {
+        let index = self.index();
+        (index / 5 != index % 5).then(|| NATURE_STATS[index / 5].clone())
+    }
+
+    /// 0.9倍になる能力（無補正の性格は None）
+    pub fn decreased(self) -> Option<StatName> {
+        let index = self.index();
+        (index / 5 != index % 5).then(|| NATURE_STATS[index % 5].clone())
+    }
+
+    /// 能力値に掛ける補正を (分子, 10) の分子で返す
+    pub fn modifier(self, stat: &StatName) -> u32 {
+        if self.increased().as_ref() == Some(stat) {
+            11
+        } else if self.decreased().as_ref() == Some(stat) {
+            9
+        } else {
+            10
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nature_modifier() {
+        assert_eq!(Nature::Adamant.increased(), Some(StatName::Attack));
+        assert_eq!(Nature::Adamant.decreased(), Some(StatName::SpecialAttack));
+        assert_eq!(Nature::Timid.modifier(&StatName::Speed), 11);
+        assert_eq!(Nature::Timid.modifier(&StatName::Attack), 9);
+        assert_eq!(Nature::Serious.increased(), None);
+        assert_eq!(Nature::from_name("Jolly"), Some(Nature::Jolly));
+    }
+}
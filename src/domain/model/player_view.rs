@@ -0,0 +1,477 @@
+use crate::domain::model::ability::Ability;
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::SlotPosition;
+use crate::domain::model::battle_event::BattleEvent;
+use crate::domain::model::battle_rng::SeededRng;
+use crate::domain::model::stats::StatName;
+use crate::domain::model::volatile_status::MoveRestriction;
+use std::sync::Arc;
+
+/// 明かされていない特性の代わりに置く名前
+pub const UNKNOWN_ABILITY: &str = "unknown";
+
+/// 相手の能力値を見積もるときの個体値（努力値は0、性格は補正なしとみなす）
+const ESTIMATED_IV: u32 = 31;
+
+/// 「イリュージョン」で別のポケモンに化けている相手
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Disguise {
+    position: SlotPosition,
+    /// 化けているポケモンのパーティ番号
+    actual_index: usize,
+    actual: String,
+    /// 化けた姿のポケモンのパーティ番号
+    shown_index: usize,
+    shown: String,
+}
+
+/// 行動を選ぶプレイヤーから見たバトルの状態。
+/// 相手のポケモンは、実際の対戦で見える範囲（場に出たポケモンだけを出た順に並べ、
+/// HPは割合、使った技、発動した特性、テラスタル後のテラスタイプ）だけを残し、今後の乱数も伏せる。
+/// 選出されたかどうか分からない相手のポケモンは、見せ合いで見えた名前だけを `opponent_preview` に残す。
+/// エージェントや出力はこの状態から作るため、見えない情報を使えない
+#[derive(Debug, Clone)]
+pub struct PlayerView {
+    /// 自分のサイドの番号
+    pub side: usize,
+    /// 見えている範囲だけを残したバトル
+    pub battle: Battle,
+    /// 見せ合いで見えた相手のパーティのポケモン名
+    pub opponent_preview: Vec<String>,
+    disguises: Vec<Disguise>,
+    /// 見えている相手のパーティの並びごとの、実際のパーティ番号
+    opponent_indices: Vec<usize>,
+    /// 見えている相手のパーティの実際の最大HP。出来事のHPを割合に直すためだけに使う
+    opponent_max_hp: Vec<u32>,
+}
+
+impl PlayerView {
+    pub fn new(battle: &Battle, side: usize) -> Self {
+        let mut observed = battle.clone();
+        observed.seed = 0;
+        observed.rng = SeededRng::new(0);
+        let opponent = 1 - side;
+        let disguises = Self::disguises(battle, opponent);
+        let opponent_indices = Self::visible_party(battle, opponent, &disguises);
+
+        let actual = &battle.sides[opponent];
+        let observed_side = &mut observed.sides[opponent];
+        observed_side.party = opponent_indices
+            .iter()
+            .map(|index| {
+                let mut pokemon = actual.party[*index].clone();
+                if let Some(disguise) = disguises
+                    .iter()
+                    .find(|disguise| disguise.actual_index == *index)
+                {
+                    let shown = &actual.party[disguise.shown_index];
+                    pokemon.name = shown.name.clone();
+                    pokemon.types = shown.types.clone();
+                    pokemon.base_stats = shown.base_stats;
+                }
+                Self::hide(&mut pokemon);
+                pokemon
+            })
+            .collect();
+        observed_side.active = actual
+            .active
+            .iter()
+            .map(|index| index.and_then(|index| Self::view_index(&opponent_indices, index)))
+            .collect();
+        observed_side.sent_out = (0..opponent_indices.len()).collect();
+        Self {
+            side,
+            opponent_preview: actual.preview.clone(),
+            battle: observed,
+            disguises,
+            opponent_max_hp: opponent_indices
+                .iter()
+                .map(|index| actual.party[*index].max_hp)
+                .collect(),
+            opponent_indices,
+        }
+    }
+
+    pub fn opponent_side(&self) -> usize {
+        1 - self.side
+    }
+
+    /// 出来事を、このプレイヤーに見える形に直す。
+    /// 相手のHPは最大HPに対する割合（%）で表し、化けている相手は化けた姿の名前にする
+    pub fn observe_event(&self, event: &BattleEvent) -> BattleEvent {
+        let opponent = self.opponent_side();
+        let mut event = event.clone();
+        match &mut event {
+            BattleEvent::Damage {
+                target,
+                pokemon,
+                percentage,
+                amount,
+                remaining_hp,
+                ..
+            } if target.side == opponent => {
+                *amount = *percentage;
+                *remaining_hp = self.hp_percentage(*target, *remaining_hp);
+                self.disguise_name(*target, pokemon);
+            }
+            BattleEvent::Healed {
+                target,
+                amount,
+                remaining_hp,
+            } if target.side == opponent => {
+                *amount = self.hp_percentage(*target, *amount);
+                *remaining_hp = self.hp_percentage(*target, *remaining_hp);
+            }
+            BattleEvent::MoveUsed { user, pokemon, .. } => self.disguise_name(*user, pokemon),
+            BattleEvent::Switched {
+                position,
+                pokemon,
+                party_index,
+            } => {
+                if position.side == opponent {
+                    *party_index = Self::view_index(&self.opponent_indices, *party_index)
+                        .unwrap_or(*party_index);
+                }
+                self.disguise_name(*position, pokemon);
+            }
+            BattleEvent::Fainted { position, pokemon } => self.disguise_name(*position, pokemon),
+            _ => {}
+        }
+        event
+    }
+
+    pub fn observe_events(&self, events: &[BattleEvent]) -> Vec<BattleEvent> {
+        events
+            .iter()
+            .map(|event| self.observe_event(event))
+            .collect()
+    }
+
+    /// 相手のHPの値を、実際の最大HPに対する割合（%、切り上げ）に直す
+    fn hp_percentage(&self, position: SlotPosition, hp: u32) -> u32 {
+        let max_hp = self.battle.sides[position.side]
+            .active
+            .get(position.slot)
+            .copied()
+            .flatten()
+            .and_then(|index| self.opponent_max_hp.get(index))
+            .map_or(1, |max_hp| (*max_hp).max(1));
+        (hp * 100).div_ceil(max_hp)
+    }
+
+    fn disguise_name(&self, position: SlotPosition, name: &mut String) {
+        if let Some(disguise) = self
+            .disguises
+            .iter()
+            .find(|disguise| disguise.position == position && disguise.actual == *name)
+        {
+            *name = disguise.shown.clone();
+        }
+    }
+
+    /// 場で「イリュージョン」が働いている相手と、化けている姿（控えのうちパーティの最後尾）
+    fn disguises(battle: &Battle, side: usize) -> Vec<Disguise> {
+        let battle_side = &battle.sides[side];
+        battle
+            .active_positions()
+            .into_iter()
+            .filter(|position| position.side == side)
+            .filter_map(|position| {
+                let actual_index = battle_side.active.get(position.slot).copied().flatten()?;
+                let pokemon = &battle_side.party[actual_index];
+                if !pokemon.has_ability("illusion") || pokemon.volatiles.illusion_broken {
+                    return None;
+                }
+                let (shown_index, shown) = battle_side
+                    .party
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find(|(_, member)| !member.is_fainted() && member.name != pokemon.name)?;
+                Some(Disguise {
+                    position,
+                    actual_index,
+                    actual: pokemon.name.clone(),
+                    shown_index,
+                    shown: shown.name.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// 相手のパーティのうち、場に出たことのあるポケモンの実際のパーティ番号を出た順に並べる。
+    /// 化けた姿がすでに場に出ている場合は、化けているポケモンをその姿の位置に置き、
+    /// 同じ名前のポケモンが2体並ばないようにする
+    fn visible_party(battle: &Battle, side: usize, disguises: &[Disguise]) -> Vec<usize> {
+        let battle_side = &battle.sides[side];
+        let mut sent_out = battle_side.sent_out.clone();
+        // 出た順の記録がない状態（古い保存データなど）でも、場にいるポケモンは見える
+        for index in battle_side.active.iter().flatten() {
+            if !sent_out.contains(index) {
+                sent_out.push(*index);
+            }
+        }
+        sent_out
+            .iter()
+            .filter_map(|index| {
+                let disguised = disguises.iter().find(|disguise| {
+                    disguise.actual_index == *index || disguise.shown_index == *index
+                });
+                match disguised {
+                    Some(disguise) if sent_out.contains(&disguise.shown_index) => {
+                        (disguise.shown_index == *index).then_some(disguise.actual_index)
+                    }
+                    _ => Some(*index),
+                }
+            })
+            .collect()
+    }
+
+    fn view_index(indices: &[usize], party_index: usize) -> Option<usize> {
+        indices.iter().position(|index| *index == party_index)
+    }
+
+    /// 相手のポケモンから、まだ明かされていない情報を取り除く
+    fn hide(pokemon: &mut ActivePokemon) {
+        let revealed = pokemon.revealed.clone();
+        // 技の番号は見えている技だけを並べ直した番号に付け替える
+        let remap =
+            |move_index: usize| revealed.moves.iter().position(|index| *index == move_index);
+        let remap_restriction = |restriction: Option<MoveRestriction>| {
+            restriction.and_then(|restriction| {
+                Some(MoveRestriction {
+                    move_index: remap(restriction.move_index)?,
+                    ..restriction
+                })
+            })
+        };
+        pokemon.moves = revealed
+            .moves
+            .iter()
+            .filter_map(|index| pokemon.moves.get(*index).cloned())
+            .collect();
+        let volatiles = &mut pokemon.volatiles;
+        volatiles.last_move = volatiles.last_move.and_then(|mut last_move| {
+            last_move.move_index = remap(last_move.move_index)?;
+            Some(last_move)
+        });
+        volatiles.choice_lock = volatiles.choice_lock.and_then(remap);
+        volatiles.locked_move = remap_restriction(volatiles.locked_move);
+        volatiles.encore = remap_restriction(volatiles.encore);
+        volatiles.disable = remap_restriction(volatiles.disable);
+        pokemon.revealed.moves = (0..pokemon.moves.len()).collect();
+
+        if !revealed.ability {
+            pokemon.active_ability = Arc::new(Ability {
+                id: 0,
+                name: UNKNOWN_ABILITY.to_string(),
+                effect_entries: Vec::new(),
+                flavor_text_entries: Vec::new(),
+                names: Vec::new(),
+            });
+        }
+        if !revealed.item {
+            pokemon.held_item = None;
+        }
+        if !pokemon.terastallized && !revealed.tera_type {
+            pokemon.tera_type = None;
+        }
+        pokemon.sleep_turns = 0;
+
+        // 能力値は種族値とレベルからの見積もりに置き換え、HPは割合だけを残す
+        let percentage = pokemon.hp_percentage();
+        let estimate = |stat: StatName| Self::estimate_stat(pokemon, stat);
+        let max_hp = estimate(StatName::Hp);
+        let attack = estimate(StatName::Attack);
+        let defense = estimate(StatName::Defense);
+        let special_attack = estimate(StatName::SpecialAttack);
+        let special_defense = estimate(StatName::SpecialDefense);
+        let speed = estimate(StatName::Speed);
+        pokemon.max_hp = max_hp;
+        pokemon.attack = attack;
+        pokemon.defense = defense;
+        pokemon.special_attack = special_attack;
+        pokemon.special_defense = special_defense;
+        pokemon.speed = speed;
+        if !pokemon.is_fainted() {
+            pokemon.current_hp = (max_hp * percentage / 100).max(1);
+        }
+    }
+
+    /// 種族値とレベルから見積もった能力値
+    fn estimate_stat(pokemon: &ActivePokemon, stat: StatName) -> u32 {
+        let raw = (2 * pokemon.base_stats.get(&stat) + ESTIMATED_IV) * pokemon.level / 100;
+        match stat {
+            StatName::Hp => raw + pokemon.level + 10,
+            _ => raw + 5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_event::DamageSource;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::model::common::PokemonTypeName;
+    use crate::domain::test_support::{test_ability, test_active_pokemon, test_move};
+
+    #[test]
+    fn test_view_hides_unrevealed_opponent_information() {
+        let moves = vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move("swift", "normal", "special", Some(60), "all-opponents"),
+        ];
+        let mut zoroark = test_active_pokemon("zoroark", &["dark"], &moves);
+        zoroark.active_ability = test_ability("illusion").into();
+        zoroark.held_item = Some("choice-scarf".to_string());
+        zoroark.tera_type = Some(PokemonTypeName {
+            name: "ghost".to_string(),
+        });
+        zoroark.revealed.reveal_move(1);
+        zoroark.current_hp = 33;
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![
+                    zoroark,
+                    test_active_pokemon("gardevoir", &["psychic"], &moves),
+                ],
+                1,
+            ),
+        );
+
+        let view = PlayerView::new(&battle, 0);
+        // まだ場に出ていないサーナイトは見えない
+        assert_eq!(view.battle.sides[1].party.len(), 1);
+        let opponent = view.battle.pokemon_at(SlotPosition::new(1, 0)).unwrap();
+        assert_eq!(opponent.name, "gardevoir");
+        assert!(opponent.has_type("psychic"));
+        assert_eq!(opponent.moves.len(), 1);
+        assert_eq!(opponent.moves[0].name, "swift");
+        assert_eq!(opponent.active_ability.name, UNKNOWN_ABILITY);
+        assert_eq!(opponent.held_item, None);
+        assert_eq!(opponent.tera_type, None);
+        // 能力値は種族値とレベル50からの見積もりになり、HPは割合だけが分かる
+        assert_eq!(opponent.max_hp, 175);
+        assert_eq!(opponent.attack, 120);
+        assert_eq!(opponent.current_hp, 57);
+        assert_eq!(view.battle.sides[0].party[0].moves.len(), 2);
+
+        let event = view.observe_event(&BattleEvent::Damage {
+            target: SlotPosition::new(1, 0),
+            pokemon: "zoroark".to_string(),
+            source: DamageSource::Move,
+            amount: 20,
+            percentage: 20,
+            remaining_hp: 33,
+        });
+        assert_eq!(
+            event,
+            BattleEvent::Damage {
+                target: SlotPosition::new(1, 0),
+                pokemon: "gardevoir".to_string(),
+                source: DamageSource::Move,
+                amount: 20,
+                percentage: 20,
+                remaining_hp: 33,
+            }
+        );
+    }
+
+    #[test]
+    fn test_view_hides_opponent_back_line() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let party = |names: &[&str]| -> Vec<ActivePokemon> {
+            names
+                .iter()
+                .map(|name| test_active_pokemon(name, &["normal"], &moves))
+                .collect()
+        };
+        let mut opponent = BattleSide::new("opponent".to_string(), party(&["x", "y", "z"]), 1);
+        opponent.preview = ["u", "v", "x", "y", "z", "w"].map(str::to_string).to_vec();
+        opponent.switch_in(0, 2);
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), party(&["a"]), 1),
+            opponent,
+        );
+
+        let view = PlayerView::new(&battle, 0);
+        let names: Vec<&str> = view.battle.sides[1]
+            .party
+            .iter()
+            .map(|pokemon| pokemon.name.as_str())
+            .collect();
+        // 選出の中身と並び順は、場に出たポケモンと出た順からしか分からない
+        assert_eq!(names, vec!["x", "z"]);
+        assert_eq!(view.battle.sides[1].active, vec![Some(1)]);
+        assert_eq!(view.opponent_preview.len(), 6);
+        assert_eq!(
+            view.observe_event(&BattleEvent::Switched {
+                position: SlotPosition::new(1, 0),
+                pokemon: "z".to_string(),
+                party_index: 2,
+            }),
+            BattleEvent::Switched {
+                position: SlotPosition::new(1, 0),
+                pokemon: "z".to_string(),
+                party_index: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_view_does_not_duplicate_illusion_target() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let mut zoroark = test_active_pokemon("zoroark", &["dark"], &moves);
+        zoroark.active_ability = test_ability("illusion").into();
+        let mut opponent = BattleSide::new(
+            "opponent".to_string(),
+            vec![
+                zoroark,
+                test_active_pokemon("gardevoir", &["psychic"], &moves),
+            ],
+            1,
+        );
+        // サーナイトが一度場に出てから、ゾロアークに交代した
+        opponent.switch_in(0, 1);
+        opponent.switch_in(0, 0);
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            opponent,
+        );
+
+        let view = PlayerView::new(&battle, 0);
+        let party = &view.battle.sides[1].party;
+        assert_eq!(party.len(), 1);
+        assert_eq!(party[0].name, "gardevoir");
+        assert!(party[0].has_type("psychic"));
+        assert_eq!(view.battle.sides[1].active, vec![Some(0)]);
+    }
+}
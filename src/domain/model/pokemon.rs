@@ -1,5 +1,7 @@
 use super::stats::StatName;
-use crate::domain::model::common::PokemonTypeName;
+use crate::domain::model::common::{
+    PokemonTypeName, VersionGroup, deserialize_optional_name_from_resource,
+};
 use crate::domain::model::r#move::MoveName;
 use serde::{
     Deserialize, Serialize,
@@ -19,23 +21,6 @@ impl AbilityName {
     }
 }
 
-fn deserialize_name_from_resource_optional<'de, D>(
-    deserializer: D,
-) -> Result<Option<String>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    #[derive(Deserialize)]
-    struct NamedResource {
-        name: String,
-        #[allow(dead_code)]
-        url: String,
-    }
-
-    let resource = Option::<NamedResource>::deserialize(deserializer)?;
-    Ok(resource.map(|r| r.name))
-}
-
 fn deserialize_ability_name<'de, D>(deserializer: D) -> Result<AbilityName, D::Error>
 where
     D: Deserializer<'de>,
@@ -88,12 +73,22 @@ pub struct Pokemon {
     pub abilities: Vec<PokemonAbility>,
     pub forms: Vec<PokemonForm>,
     pub moves: Vec<PokemonMove>,
-    #[serde(deserialize_with = "deserialize_name_from_resource_optional")]
+    #[serde(deserialize_with = "deserialize_optional_name_from_resource")]
     pub species: Option<String>,
     pub stats: Vec<PokemonStat>,
     pub types: Vec<PokemonType>,
 }
 
+impl Pokemon {
+    /// スカーレット・バイオレットで覚えられる技か
+    pub fn can_learn(&self, move_name: &str) -> bool {
+        let scarlet_violet = VersionGroup::scarlet_violet();
+        self.moves.iter().any(|learnable| {
+            learnable.r#move.name == move_name && learnable.is_learnable_in(&scarlet_violet)
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PokemonAbility {
     #[serde(deserialize_with = "deserialize_ability_name")]
@@ -112,6 +107,117 @@ pub struct PokemonForm {
 pub struct PokemonMove {
     #[serde(rename = "move", deserialize_with = "deserialize_move_name")]
     pub r#move: MoveName,
+    /// バージョングループごとの覚え方
+    #[serde(default)]
+    pub version_group_details: Vec<MoveLearnDetail>,
+}
+
+impl PokemonMove {
+    /// 指定したバージョングループで覚えられるか
+    pub fn is_learnable_in(&self, version_group: &VersionGroup) -> bool {
+        self.version_group_details
+            .iter()
+            .any(|detail| detail.version_group == *version_group)
+    }
+}
+
+/// 技の覚え方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveLearnMethod {
+    LevelUp,
+    Machine,
+    Egg,
+    Tutor,
+    /// 上記以外（思い出し・フォルムチェンジなど）
+    Other(String),
+}
+
+impl MoveLearnMethod {
+    fn from_name(name: String) -> Self {
+        match name.as_str() {
+            "level-up" => Self::LevelUp,
+            "machine" => Self::Machine,
+            "egg" => Self::Egg,
+            "tutor" => Self::Tutor,
+            _ => Self::Other(name),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::LevelUp => "level-up",
+            Self::Machine => "machine",
+            Self::Egg => "egg",
+            Self::Tutor => "tutor",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+/// 1つのバージョングループでの技の覚え方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveLearnDetail {
+    pub learn_method: MoveLearnMethod,
+    /// レベルアップで覚えるレベル（レベルアップ以外は 0）
+    pub level_learned_at: u32,
+    pub version_group: VersionGroup,
+}
+
+impl<'de> Deserialize<'de> for MoveLearnDetail {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct NamedResource {
+            name: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Outer {
+            level_learned_at: u32,
+            move_learn_method: NamedResource,
+            version_group: NamedResource,
+        }
+
+        let helper = Outer::deserialize(deserializer)?;
+        Ok(MoveLearnDetail {
+            learn_method: MoveLearnMethod::from_name(helper.move_learn_method.name),
+            level_learned_at: helper.level_learned_at,
+            version_group: VersionGroup::new(helper.version_group.name),
+        })
+    }
+}
+
+impl Serialize for MoveLearnDetail {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct NamedResource<'a> {
+            name: &'a str,
+            url: &'a str,
+        }
+
+        let mut state = serializer.serialize_struct("MoveLearnDetail", 3)?;
+        state.serialize_field("level_learned_at", &self.level_learned_at)?;
+        state.serialize_field(
+            "move_learn_method",
+            &NamedResource {
+                name: self.learn_method.as_str(),
+                url: "",
+            },
+        )?;
+        state.serialize_field(
+            "version_group",
+            &NamedResource {
+                name: &self.version_group.name,
+                url: "",
+            },
+        )?;
+        state.end()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -208,7 +314,33 @@ mod tests {
                     "move": {
                         "name": "tackle",
                         "url": "https://pokeapi.co/api/v2/move/33/"
-                    }
+                    },
+                    "version_group_details": [
+                        {
+                            "level_learned_at": 1,
+                            "move_learn_method": {
+                                "name": "level-up",
+                                "url": "https://pokeapi.co/api/v2/move-learn-method/1/"
+                            },
+                            "order": null,
+                            "version_group": {
+                                "name": "scarlet-violet",
+                                "url": "https://pokeapi.co/api/v2/version-group/25/"
+                            }
+                        },
+                        {
+                            "level_learned_at": 0,
+                            "move_learn_method": {
+                                "name": "machine",
+                                "url": "https://pokeapi.co/api/v2/move-learn-method/4/"
+                            },
+                            "order": null,
+                            "version_group": {
+                                "name": "red-blue",
+                                "url": "https://pokeapi.co/api/v2/version-group/1/"
+                            }
+                        }
+                    ]
                 }
             ],
             "species": {
@@ -252,6 +384,15 @@ mod tests {
         assert_eq!(pokemon.abilities[0].ability.name, "overgrow");
         assert_eq!(pokemon.moves.len(), 1);
         assert_eq!(pokemon.moves[0].r#move.name, "tackle");
+        assert_eq!(
+            pokemon.moves[0].version_group_details[0].learn_method,
+            MoveLearnMethod::LevelUp
+        );
+        assert_eq!(
+            pokemon.moves[0].version_group_details[0].level_learned_at,
+            1
+        );
+        assert!(pokemon.can_learn("tackle"));
         assert_eq!(pokemon.species, Some("bulbasaur".to_string()));
         assert_eq!(pokemon.stats.len(), 2);
         assert_eq!(pokemon.stats[0].stat, StatName::Hp);
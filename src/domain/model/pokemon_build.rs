@@ -0,0 +1,353 @@
+use crate::domain::model::active_pokemon::DEFAULT_LEVEL;
+use crate::domain::model::nature::Nature;
+use crate::domain::model::pokemon::Pokemon;
+use crate::domain::model::stats::StatName;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 個体値の上限
+pub const MAX_IV: u32 = 31;
+/// 努力値の1能力あたりの上限
+pub const MAX_EV: u32 = 252;
+/// 努力値の合計の上限
+pub const MAX_EV_TOTAL: u32 = 510;
+/// 覚えさせられる技の数
+pub const MAX_MOVES: usize = 4;
+/// 1つの技に使えるポイントアップの数
+pub const MAX_PP_UPS: u32 = 3;
+/// ニックネームの最大文字数
+pub const MAX_NICKNAME_LENGTH: usize = 12;
+
+/// 個体値・努力値を持つ能力の一覧
+pub const ALL_STATS: [StatName; 6] = [
+    StatName::Hp,
+    StatName::Attack,
+    StatName::Defense,
+    StatName::SpecialAttack,
+    StatName::SpecialDefense,
+    StatName::Speed,
+];
+
+/// 性別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Gender {
+    Male,
+    Female,
+    #[default]
+    Genderless,
+}
+
+/// 6つの能力それぞれの値（個体値・努力値）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StatSpread {
+    pub hp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub special_attack: u32,
+    pub special_defense: u32,
+    pub speed: u32,
+}
+
+impl StatSpread {
+    /// すべての能力を同じ値にする
+    pub fn uniform(value: u32) -> Self {
+        Self {
+            hp: value,
+            attack: value,
+            defense: value,
+            special_attack: value,
+            special_defense: value,
+            speed: value,
+        }
+    }
+
+    pub fn get(&self, stat: &StatName) -> u32 {
+        match stat {
+            StatName::Hp => self.hp,
+            StatName::Attack => self.attack,
+            StatName::Defense => self.defense,
+            StatName::SpecialAttack => self.special_attack,
+            StatName::SpecialDefense => self.special_defense,
+            StatName::Speed => self.speed,
+        }
+    }
+
+    pub fn set(&mut self, stat: &StatName, value: u32) {
+        let slot = match stat {
+            StatName::Hp => &mut self.hp,
+            StatName::Attack => &mut self.attack,
+            StatName::Defense => &mut self.defense,
+            StatName::SpecialAttack => &mut self.special_attack,
+            StatName::SpecialDefense => &mut self.special_defense,
+            StatName::Speed => &mut self.speed,
+        };
+        *slot = value;
+    }
+
+    pub fn total(&self) -> u32 {
+        self.hp
+            + self.attack
+            + self.defense
+            + self.special_attack
+            + self.special_defense
+            + self.speed
+    }
+}
+
+/// 覚えさせる技と、使ったポイントアップの数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveChoice {
+    pub name: String,
+    #[serde(default)]
+    pub pp_ups: u32,
+}
+
+impl MoveChoice {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            pp_ups: 0,
+        }
+    }
+
+    /// ポイントアップを反映した最大PP（1つにつき元のPPの5分の1ずつ増える）
+    pub fn max_pp(&self, base_pp: u32) -> u32 {
+        base_pp + base_pp / 5 * self.pp_ups
+    }
+}
+
+/// 育成したポケモン1体の型（ニックネーム・レベル・性格・個体値・努力値・特性・持ち物・技・テラスタイプ）。
+/// 図鑑データ（Pokemon）とは別に保存・編集でき、バトル用のポケモンはこれから作る。
+/// 種族・特性・技などの名前は data/ のスラッグで持つ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PokemonBuild {
+    /// ポケモン（フォルム）の名前
+    pub species: String,
+    pub nickname: Option<String>,
+    pub level: u32,
+    pub gender: Gender,
+    pub shiny: bool,
+    pub nature: Nature,
+    pub ivs: StatSpread,
+    pub evs: StatSpread,
+    pub ability: String,
+    pub held_item: Option<String>,
+    pub moves: Vec<MoveChoice>,
+    pub tera_type: Option<String>,
+}
+
+/// 型の内容がルール上ありえない場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    InvalidLevel(u32),
+    NicknameTooLong(String),
+    IvOutOfRange {
+        stat: StatName,
+        value: u32,
+    },
+    EvOutOfRange {
+        stat: StatName,
+        value: u32,
+    },
+    EvTotalExceeded(u32),
+    /// 技が1つもない、または多すぎる
+    InvalidMoveCount(usize),
+    DuplicateMove(String),
+    TooManyPpUps {
+        move_name: String,
+        pp_ups: u32,
+    },
+    /// 型と異なるポケモン・特性・技のデータが渡された
+    DataMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// 型の技のデータが渡されなかった
+    MissingMoveData(String),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidLevel(level) => write!(f, "レベル{}は設定できません", level),
+            BuildError::NicknameTooLong(nickname) => write!(
+                f,
+                "ニックネームは{}文字までです: {}",
+                MAX_NICKNAME_LENGTH, nickname
+            ),
+            BuildError::IvOutOfRange { stat, value } => write!(
+                f,
+                "{}の個体値{}は0〜{}の範囲外です",
+                stat.as_str(),
+                value,
+                MAX_IV
+            ),
+            BuildError::EvOutOfRange { stat, value } => write!(
+                f,
+                "{}の努力値{}は0〜{}の範囲外です",
+                stat.as_str(),
+                value,
+                MAX_EV
+            ),
+            BuildError::EvTotalExceeded(total) => write!(
+                f,
+                "努力値の合計{}が上限の{}を超えています",
+                total, MAX_EV_TOTAL
+            ),
+            BuildError::InvalidMoveCount(count) => {
+                write!(f, "技は1〜{}個覚えさせます（{}個）", MAX_MOVES, count)
+            }
+            BuildError::DuplicateMove(name) => write!(f, "同じ技を2回覚えさせています: {}", name),
+            BuildError::TooManyPpUps { move_name, pp_ups } => write!(
+                f,
+                "{}のポイントアップ{}個は上限の{}個を超えています",
+                move_name, pp_ups, MAX_PP_UPS
+            ),
+            BuildError::DataMismatch { expected, actual } => {
+                write!(f, "{}のデータの代わりに{}が渡されました", expected, actual)
+            }
+            BuildError::MissingMoveData(name) => write!(f, "技のデータがありません: {}", name),
+        }
+    }
+}
+
+impl PokemonBuild {
+    /// レベル50・個体値最大・努力値なしの型を作る
+    pub fn new(species: &str, ability: &str) -> Self {
+        Self {
+            species: species.to_string(),
+            nickname: None,
+            level: DEFAULT_LEVEL,
+            gender: Gender::default(),
+            shiny: false,
+            nature: Nature::default(),
+            ivs: StatSpread::uniform(MAX_IV),
+            evs: StatSpread::default(),
+            ability: ability.to_string(),
+            held_item: None,
+            moves: Vec::new(),
+            tera_type: None,
+        }
+    }
+
+    pub fn with_moves(mut self, moves: &[&str]) -> Self {
+        self.moves = moves.iter().map(|name| MoveChoice::new(name)).collect();
+        self
+    }
+
+    pub fn with_item(mut self, item: &str) -> Self {
+        self.held_item = Some(item.to_string());
+        self
+    }
+
+    pub fn with_nature(mut self, nature: Nature) -> Self {
+        self.nature = nature;
+        self
+    }
+
+    pub fn with_evs(mut self, evs: StatSpread) -> Self {
+        self.evs = evs;
+        self
+    }
+
+    pub fn with_tera_type(mut self, tera_type: &str) -> Self {
+        self.tera_type = Some(tera_type.to_string());
+        self
+    }
+
+    /// 表示に使う名前（ニックネームがなければポケモンの名前）
+    pub fn display_name(&self) -> &str {
+        self.nickname.as_deref().unwrap_or(&self.species)
+    }
+
+    /// 図鑑データによらず判定できる範囲で、型がルール上ありえるかを確かめる
+    pub fn validate(&self) -> Result<(), BuildError> {
+        if !(1..=100).contains(&self.level) {
+            return Err(BuildError::InvalidLevel(self.level));
+        }
+        if let Some(nickname) = &self.nickname
+            && nickname.chars().count() > MAX_NICKNAME_LENGTH
+        {
+            return Err(BuildError::NicknameTooLong(nickname.clone()));
+        }
+        for stat in ALL_STATS {
+            let iv = self.ivs.get(&stat);
+            if iv > MAX_IV {
+                return Err(BuildError::IvOutOfRange {
+                    stat: stat.clone(),
+                    value: iv,
+                });
+            }
+            let ev = self.evs.get(&stat);
+            if ev > MAX_EV {
+                return Err(BuildError::EvOutOfRange { stat, value: ev });
+            }
+        }
+        if self.evs.total() > MAX_EV_TOTAL {
+            return Err(BuildError::EvTotalExceeded(self.evs.total()));
+        }
+        if self.moves.is_empty() || self.moves.len() > MAX_MOVES {
+            return Err(BuildError::InvalidMoveCount(self.moves.len()));
+        }
+        for (index, choice) in self.moves.iter().enumerate() {
+            if self.moves[..index]
+                .iter()
+                .any(|other| other.name == choice.name)
+            {
+                return Err(BuildError::DuplicateMove(choice.name.clone()));
+            }
+            if choice.pp_ups > MAX_PP_UPS {
+                return Err(BuildError::TooManyPpUps {
+                    move_name: choice.name.clone(),
+                    pp_ups: choice.pp_ups,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// 種族値・個体値・努力値・レベル・性格から能力の実数値を計算する
+    pub fn stat(&self, pokemon: &Pokemon, stat: &StatName) -> u32 {
+        let base = pokemon
+            .stats
+            .iter()
+            .find(|s| s.stat == *stat)
+            .map(|s| s.base_stat)
+            .unwrap_or_else(|| panic!("'{:?}' stat not found for {}", stat, pokemon.name));
+        let raw = (2 * base + self.ivs.get(stat) + self.evs.get(stat) / 4) * self.level / 100;
+        match stat {
+            StatName::Hp => raw + self.level + 10,
+            _ => (raw + 5) * self.nature.modifier(stat) / 10,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::test_pokemon;
+
+    #[test]
+    fn test_build_stats_and_validation() {
+        let garchomp = test_pokemon("garchomp", &["dragon", "ground"], 108, 102);
+        let build = PokemonBuild::new("garchomp", "rough-skin")
+            .with_moves(&["earthquake", "dragon-claw"])
+            .with_nature(Nature::Jolly)
+            .with_evs(StatSpread {
+                attack: 252,
+                speed: 252,
+                special_defense: 4,
+                ..Default::default()
+            });
+
+        assert_eq!(build.validate(), Ok(()));
+        assert_eq!(build.stat(&garchomp, &StatName::Hp), 183);
+        assert_eq!(build.stat(&garchomp, &StatName::Speed), 169);
+        assert_eq!(build.stat(&garchomp, &StatName::SpecialAttack), 109);
+
+        let mut invalid = build.clone();
+        invalid.evs.hp = 252;
+        assert_eq!(invalid.validate(), Err(BuildError::EvTotalExceeded(760)));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::domain::model::common::Version;
+use crate::domain::model::common::{
+    Version, deserialize_name_from_resource, deserialize_optional_name_from_resource,
+};
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// Pokemon color enum for species
@@ -45,6 +47,37 @@ pub struct PokemonSpecies {
     pub egg_groups: Vec<String>,
     pub flavor_text_entries: Vec<FlavorTextEntry>,
     pub names: Vec<Name>,
+    /// 伝説のポケモン
+    pub is_legendary: bool,
+    /// 幻のポケモン
+    pub is_mythical: bool,
+    /// ベイビィポケモン
+    pub is_baby: bool,
+    /// 捕まえやすさ（0〜255）
+    pub capture_rate: u32,
+    /// メスになる確率を8分の1単位で表す。性別不明は -1
+    pub gender_rate: i32,
+    /// 進化前の種族
+    #[serde(deserialize_with = "deserialize_optional_name_from_resource")]
+    pub evolves_from_species: Option<String>,
+    /// 各地方の図鑑での番号
+    pub pokedex_numbers: Vec<PokedexNumber>,
+}
+
+impl PokemonSpecies {
+    /// 指定した図鑑に載っている
+    pub fn is_in_pokedex(&self, pokedex: &str) -> bool {
+        self.pokedex_numbers
+            .iter()
+            .any(|number| number.pokedex == pokedex)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PokedexNumber {
+    pub entry_number: u32,
+    #[serde(deserialize_with = "deserialize_name_from_resource")]
+    pub pokedex: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +89,8 @@ pub struct FlavorTextEntry {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Name {
     pub name: String,
+    #[serde(deserialize_with = "deserialize_name_from_resource")]
+    pub language: String,
 }
 
 // Helper functions for deserialization
@@ -107,12 +142,39 @@ mod tests {
             },
             "egg_groups": [],
             "flavor_text_entries": [],
-            "names": []
+            "names": [],
+            "is_legendary": false,
+            "is_mythical": false,
+            "is_baby": false,
+            "capture_rate": 45,
+            "gender_rate": 1,
+            "evolves_from_species": null,
+            "pokedex_numbers": [
+                {
+                    "entry_number": 1,
+                    "pokedex": {
+                        "name": "national",
+                        "url": "https://pokeapi.co/api/v2/pokedex/1/"
+                    }
+                },
+                {
+                    "entry_number": 84,
+                    "pokedex": {
+                        "name": "kitakami",
+                        "url": "https://pokeapi.co/api/v2/pokedex/32/"
+                    }
+                }
+            ]
         }
         "#;
 
         let species: PokemonSpecies = serde_json::from_str(json_data).unwrap();
         assert_eq!(species.id, 1);
         assert_eq!(species.name, "bulbasaur");
+        assert_eq!(species.capture_rate, 45);
+        assert_eq!(species.evolves_from_species, None);
+        assert_eq!(species.gender_rate, 1);
+        assert!(species.is_in_pokedex("kitakami"));
+        assert!(!species.is_in_pokedex("paldea"));
     }
 }
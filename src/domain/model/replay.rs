@@ -0,0 +1,42 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::{ChosenAction, SlotPosition};
+use crate::domain::model::battle_event::BattleEvent;
+use crate::domain::model::battle_result::BattleResult;
+use serde::{Deserialize, Serialize};
+
+/// リプレイファイルの形式のバージョン
+pub const REPLAY_VERSION: u32 = 2;
+
+/// 1ターン分の記録。ターン終了後の交代もこのターンに含める
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayTurn {
+    pub actions: Vec<ChosenAction>,
+    #[serde(default)]
+    pub replacements: Vec<(SlotPosition, usize)>,
+    /// ターンと交代で発生した出来事（発生順）
+    pub events: Vec<BattleEvent>,
+}
+
+/// 記録を始めた時点のバトルと各ターンの行動から、バトルを再現するための記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    /// 記録を始めた時点のバトル。乱数の状態・場・ターン数も含むため、途中から始めた記録も再現できる
+    pub start: Battle,
+    /// バトルを打ち切るターン数
+    pub turn_limit: u32,
+    pub turns: Vec<ReplayTurn>,
+    pub result: Option<BattleResult>,
+}
+
+impl Replay {
+    pub fn new(start: Battle, turn_limit: u32) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            start,
+            turn_limit,
+            turns: Vec::new(),
+            result: None,
+        }
+    }
+}
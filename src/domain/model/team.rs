@@ -0,0 +1,72 @@
+use crate::domain::model::pokemon_build::PokemonBuild;
+use serde::{Deserialize, Serialize};
+
+/// パーティに入れられるポケモンの最大数
+pub const MAX_TEAM_SIZE: usize = 6;
+
+/// 名前を付けて保存・編集できるパーティ。先頭のポケモンが先発になる
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<PokemonBuild>,
+    /// 整理用のフォルダ（タグ）。1つのパーティを複数のフォルダに入れられる
+    #[serde(default)]
+    pub folders: Vec<String>,
+}
+
+impl Team {
+    pub fn new(name: &str, members: Vec<PokemonBuild>) -> Self {
+        Self {
+            name: name.to_string(),
+            members,
+            folders: Vec::new(),
+        }
+    }
+
+    pub fn is_in_folder(&self, folder: &str) -> bool {
+        self.folders.iter().any(|tagged| tagged == folder)
+    }
+
+    /// フォルダに入れる（すでに入っていれば何もしない）
+    pub fn add_folder(&mut self, folder: &str) {
+        if !self.is_in_folder(folder) {
+            self.folders.push(folder.to_string());
+        }
+    }
+
+    pub fn remove_folder(&mut self, folder: &str) {
+        self.folders.retain(|tagged| tagged != folder);
+    }
+
+    /// 指定した番号のポケモンを先頭に移し、他の並びは保つ
+    pub fn set_lead(&mut self, index: usize) {
+        if index < self.members.len() {
+            let lead = self.members.remove(index);
+            self.members.insert(0, lead);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_lead_keeps_order() {
+        let mut team = Team::new(
+            "rain",
+            vec![
+                PokemonBuild::new("pelipper", "drizzle"),
+                PokemonBuild::new("barraskewda", "swift-swim"),
+                PokemonBuild::new("archaludon", "stamina"),
+            ],
+        );
+        team.set_lead(2);
+        let species: Vec<&str> = team
+            .members
+            .iter()
+            .map(|member| member.species.as_str())
+            .collect();
+        assert_eq!(species, vec!["archaludon", "pelipper", "barraskewda"]);
+    }
+}
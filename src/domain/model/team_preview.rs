@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// 見せ合いで相手に公開する情報の範囲
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TeamSheet {
+    /// ポケモンの名前だけを見せる
+    #[default]
+    Closed,
+    /// 技・特性・持ち物・テラスタイプまで見せる
+    Open,
+}
+
+/// バトル開始前に両者のパーティを見せ合う、見せ合い（チーム選出）の情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamPreview {
+    /// 自分のパーティのポケモン名（パーティ順）
+    pub own: Vec<String>,
+    /// 相手のパーティのポケモン名
+    pub opponent: Vec<String>,
+    /// バトルに連れて行く数
+    pub bring_count: usize,
+    pub team_sheet: TeamSheet,
+}
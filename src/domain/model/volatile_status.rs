@@ -0,0 +1,92 @@
+use crate::domain::model::battle_action::SlotPosition;
+use serde::{Deserialize, Serialize};
+
+/// 「このゆびとまれ」系の技による注目の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CenterOfAttention {
+    FollowMe,
+    /// くさタイプ・ぼうじん等の粉を受けないポケモンには効かない
+    RagePowder,
+}
+
+/// 最後に使った技（「さいはい」で再使用される）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastMove {
+    pub move_index: usize,
+    pub target: Option<SlotPosition>,
+}
+
+/// 一定ターンの間、特定の技に掛かる縛り（「アンコール」「かなしばり」）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveRestriction {
+    pub move_index: usize,
+    pub turns: u32,
+}
+
+/// 場にいる間だけ有効な一時的な状態
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolatileStatus {
+    /// このターン相手の技を引き寄せているか
+    pub center_of_attention: Option<CenterOfAttention>,
+    /// このターン「てだすけ」を受けた回数
+    pub helping_hand_count: u32,
+    /// 連続で成功した「サイドチェンジ」の回数
+    pub ally_switch_count: u32,
+    pub last_move: Option<LastMove>,
+    /// こだわり系の道具で固定された技
+    pub choice_lock: Option<usize>,
+    /// 「げきりん」などで暴れ続ける技と、残りの追加ターン数
+    pub locked_move: Option<MoveRestriction>,
+    /// 「はかいこうせん」などの反動で次のターン動けない
+    pub must_recharge: bool,
+    /// 「ちょうはつ」の残りターン数
+    pub taunt_turns: u32,
+    pub encore: Option<MoveRestriction>,
+    pub disable: Option<MoveRestriction>,
+    /// 「いちゃもん」を受けている（同じ技を連続で出せない）
+    pub tormented: bool,
+    /// 「しめつける」などのバインド技の残りターン数
+    pub bound_turns: u32,
+    /// 技のダメージを受けて「イリュージョン」の化けの皮がはがれた
+    pub illusion_broken: bool,
+}
+
+impl VolatileStatus {
+    /// ターン終了時に、そのターン限りの状態を解除する
+    pub fn end_turn(&mut self) {
+        self.center_of_attention = None;
+        self.helping_hand_count = 0;
+        self.taunt_turns = self.taunt_turns.saturating_sub(1);
+        self.bound_turns = self.bound_turns.saturating_sub(1);
+        for restriction in [&mut self.encore, &mut self.disable] {
+            if let Some(active) = restriction {
+                active.turns = active.turns.saturating_sub(1);
+                if active.turns == 0 {
+                    *restriction = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_end_turn_clears_turn_scoped_state() {
+        let mut volatiles = VolatileStatus {
+            center_of_attention: Some(CenterOfAttention::FollowMe),
+            helping_hand_count: 1,
+            ally_switch_count: 1,
+            taunt_turns: 1,
+            ..Default::default()
+        };
+        volatiles.end_turn();
+
+        assert_eq!(volatiles.center_of_attention, None);
+        assert_eq!(volatiles.helping_hand_count, 0);
+        assert_eq!(volatiles.ally_switch_count, 1);
+        assert_eq!(volatiles.taunt_turns, 0);
+    }
+}
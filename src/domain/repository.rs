@@ -1,6 +1,11 @@
 pub mod ability_repository;
+pub mod battle_snapshot_repository;
+pub mod format_repository;
 pub mod item_repository;
 pub mod move_repository;
 pub mod pokemon_repository;
 pub mod pokemon_species_repository;
+pub mod replay_repository;
+pub mod repository_error;
+pub mod team_repository;
 pub mod type_repository;
@@ -0,0 +1,8 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// 進行中のバトルの状態を名前を付けて保存・復元する
+pub trait BattleSnapshotRepository {
+    fn save_snapshot(&self, name: &str, battle: &Battle) -> Result<(), RepositoryError>;
+    fn load_snapshot(&self, name: &str) -> Result<Battle, RepositoryError>;
+}
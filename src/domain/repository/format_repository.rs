@@ -0,0 +1,8 @@
+use crate::domain::model::format::Format;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// 対戦のルール（レギュレーション）の定義を読み込む
+pub trait FormatRepository {
+    fn find_all_formats(&self) -> Vec<Format>;
+    fn find_format(&self, id: &str) -> Result<Format, RepositoryError>;
+}
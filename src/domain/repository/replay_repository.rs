@@ -0,0 +1,8 @@
+use crate::domain::model::replay::Replay;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// 記録したバトルのリプレイを名前を付けて保存・読み込みする
+pub trait ReplayRepository {
+    fn save_replay(&self, name: &str, replay: &Replay) -> Result<(), RepositoryError>;
+    fn load_replay(&self, name: &str) -> Result<Replay, RepositoryError>;
+}
@@ -0,0 +1,25 @@
+use std::fmt;
+
+/// 保存・読み込みを伴うリポジトリの操作に失敗した場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepositoryError {
+    /// 指定した名前のデータが存在しない
+    NotFound(String),
+    /// 保存先への読み書きに失敗した
+    Io(String),
+    /// 保存されたデータの形式が不正
+    Format(String),
+    /// 保存先の名前として使えない（空、またはパスの区切りを含む）
+    InvalidName(String),
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepositoryError::NotFound(name) => write!(f, "{} が見つかりません", name),
+            RepositoryError::Io(message) => write!(f, "読み書きに失敗しました: {}", message),
+            RepositoryError::Format(message) => write!(f, "データの形式が不正です: {}", message),
+            RepositoryError::InvalidName(name) => write!(f, "名前として使えません: {}", name),
+        }
+    }
+}
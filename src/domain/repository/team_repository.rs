@@ -0,0 +1,35 @@
+use crate::domain::model::team::Team;
+use crate::domain::repository::repository_error::RepositoryError;
+
+/// パーティを名前で保存・読み込みする
+pub trait TeamRepository {
+    /// 保存されているパーティを名前順で返す
+    fn find_all_teams(&self) -> Result<Vec<Team>, RepositoryError>;
+    fn find_team(&self, name: &str) -> Result<Team, RepositoryError>;
+    /// 同じ名前のパーティがあれば上書きする
+    fn save_team(&self, team: &Team) -> Result<(), RepositoryError>;
+    fn delete_team(&self, name: &str) -> Result<(), RepositoryError>;
+
+    /// 指定したフォルダに入っているパーティを名前順で返す
+    fn find_teams_in_folder(&self, folder: &str) -> Result<Vec<Team>, RepositoryError> {
+        Ok(self
+            .find_all_teams()?
+            .into_iter()
+            .filter(|team| team.is_in_folder(folder))
+            .collect())
+    }
+
+    /// 保存されているパーティをフォルダに入れる
+    fn add_to_folder(&self, name: &str, folder: &str) -> Result<(), RepositoryError> {
+        let mut team = self.find_team(name)?;
+        team.add_folder(folder);
+        self.save_team(&team)
+    }
+
+    /// 保存されているパーティをフォルダから外す
+    fn remove_from_folder(&self, name: &str, folder: &str) -> Result<(), RepositoryError> {
+        let mut team = self.find_team(name)?;
+        team.remove_folder(folder);
+        self.save_team(&team)
+    }
+}
@@ -1 +1,21 @@
-// 空のserviceモジュール
+pub mod ally_support;
+pub mod availability;
+pub mod battle_agent;
+pub mod battle_engine;
+pub mod battle_evaluator;
+pub mod battle_judge;
+pub mod damage_calculator;
+pub mod determinizer;
+pub mod expectiminimax_agent;
+pub mod format_validator;
+pub mod greedy_agent;
+pub mod legal_actions;
+pub mod mcts_agent;
+pub mod random_agent;
+pub mod redirection_resolver;
+pub mod showdown_paste;
+pub mod status_effects;
+pub mod target_resolver;
+pub mod turn_order_resolver;
+pub mod type_chart;
+pub mod type_coverage;
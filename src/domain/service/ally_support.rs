@@ -0,0 +1,107 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::SlotPosition;
+use crate::domain::model::move_slot::MoveSlot;
+use crate::domain::service::damage_calculator::{MODIFIER_BASE, chain_modifiers};
+
+/// 「てだすけ」の威力補正（1.5倍）
+const HELPING_HAND_MODIFIER: u32 = 6144;
+/// 「パワースポット」「バッテリー」の威力補正（1.3倍）
+const ALLY_POWER_MODIFIER: u32 = 5325;
+/// 「フレンドガード」のダメージ補正（0.75倍）
+const FRIEND_GUARD_MODIFIER: u32 = 3072;
+
+/// 味方から受ける補助効果（てだすけ・味方を支える特性）
+pub struct AllySupport;
+
+impl AllySupport {
+    /// 攻撃側の技威力にかかる補正（てだすけ・パワースポット・バッテリー）
+    pub fn base_power_modifier(battle: &Battle, user: SlotPosition, move_slot: &MoveSlot) -> u32 {
+        let mut modifier = MODIFIER_BASE;
+        if let Some(attacker) = battle.pokemon_at(user) {
+            for _ in 0..attacker.volatiles.helping_hand_count {
+                modifier = chain_modifiers(modifier, HELPING_HAND_MODIFIER);
+            }
+        }
+        for ally in Self::alive_allies(battle, user) {
+            let Some(ally) = battle.pokemon_at(ally) else {
+                continue;
+            };
+            if ally.has_ability("power-spot")
+                || (ally.has_ability("battery") && move_slot.damage_class.is_special())
+            {
+                modifier = chain_modifiers(modifier, ALLY_POWER_MODIFIER);
+            }
+        }
+        modifier
+    }
+
+    /// 防御側が受けるダメージにかかる補正（フレンドガード）
+    pub fn final_modifier(battle: &Battle, target: SlotPosition) -> u32 {
+        Self::alive_allies(battle, target)
+            .into_iter()
+            .filter(|ally| {
+                battle
+                    .pokemon_at(*ally)
+                    .is_some_and(|pokemon| pokemon.has_ability("friend-guard"))
+            })
+            .fold(MODIFIER_BASE, |modifier, _| {
+                chain_modifiers(modifier, FRIEND_GUARD_MODIFIER)
+            })
+    }
+
+    /// 「テレパシー」で味方の攻撃技を受けないか
+    pub fn avoids_ally_attack(
+        battle: &Battle,
+        user: SlotPosition,
+        target: SlotPosition,
+        move_slot: &MoveSlot,
+    ) -> bool {
+        target.is_ally_of(&user)
+            && !move_slot.damage_class.is_status()
+            && battle
+                .pokemon_at(target)
+                .is_some_and(|pokemon| pokemon.has_ability("telepathy"))
+    }
+
+    fn alive_allies(battle: &Battle, position: SlotPosition) -> Vec<SlotPosition> {
+        battle
+            .ally_positions(position)
+            .into_iter()
+            .filter(|ally| battle.is_alive(*ally))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{test_ability, test_doubles_battle, test_move};
+
+    #[test]
+    fn test_battery_and_helping_hand_chain() {
+        let mut battle = test_doubles_battle(&[test_move(
+            "surf",
+            "water",
+            "special",
+            Some(90),
+            "all-other-pokemon",
+        )]);
+        let user = SlotPosition::new(0, 0);
+        battle
+            .pokemon_at_mut(SlotPosition::new(0, 1))
+            .unwrap()
+            .active_ability = test_ability("battery").into();
+        battle
+            .pokemon_at_mut(user)
+            .unwrap()
+            .volatiles
+            .helping_hand_count = 1;
+        let move_slot = battle.pokemon_at(user).unwrap().moves[0].clone();
+
+        // 1.5倍 × 1.3倍
+        assert_eq!(
+            AllySupport::base_power_modifier(&battle, user, &move_slot),
+            7988
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::domain::model::ability::Ability;
+use crate::domain::model::common::VersionGroup;
+use crate::domain::model::item::Item;
+use crate::domain::model::r#move::Move;
+use crate::domain::model::pokemon::Pokemon;
+
+/// スカーレット・バイオレットに登場するかどうか。
+/// data/ には過去作だけのデータ（メガシンカ・Zワザなど）も含まれるため、バージョングループの記録で判定する
+pub trait Availability {
+    fn is_available_in_scarlet_violet(&self) -> bool;
+}
+
+impl Availability for Pokemon {
+    /// スカーレット・バイオレットで覚える技がある姿だけを登場するとみなす
+    fn is_available_in_scarlet_violet(&self) -> bool {
+        let scarlet_violet = VersionGroup::scarlet_violet();
+        self.moves
+            .iter()
+            .any(|learnable| learnable.is_learnable_in(&scarlet_violet))
+    }
+}
+
+impl Availability for Move {
+    /// スカーレット・バイオレットの説明文がある技だけを登場するとみなす
+    fn is_available_in_scarlet_violet(&self) -> bool {
+        let scarlet_violet = VersionGroup::scarlet_violet();
+        self.flavor_text_entries
+            .iter()
+            .any(|entry| entry.version_group == scarlet_violet)
+    }
+}
+
+impl Availability for Ability {
+    fn is_available_in_scarlet_violet(&self) -> bool {
+        let scarlet_violet = VersionGroup::scarlet_violet();
+        self.flavor_text_entries
+            .iter()
+            .any(|entry| entry.version_group == scarlet_violet)
+    }
+}
+
+impl Availability for Item {
+    fn is_available_in_scarlet_violet(&self) -> bool {
+        let scarlet_violet = VersionGroup::scarlet_violet();
+        self.flavor_text_entries
+            .iter()
+            .any(|entry| entry.version_group.name == scarlet_violet.name)
+    }
+}
+
+/// スカーレット・バイオレットに登場するものだけを残す
+pub fn retain_available<T: Availability>(entries: &mut Vec<T>) {
+    entries.retain(Availability::is_available_in_scarlet_violet);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::common::{FlavorText, Language};
+    use crate::domain::test_support::{test_learnset, test_move, test_pokemon};
+
+    #[test]
+    fn test_retain_available() {
+        let mut garchomp = test_pokemon("garchomp", &["dragon", "ground"], 108, 102);
+        test_learnset(&mut garchomp, "rough-skin", &["earthquake"]);
+        let mut pokemons = vec![
+            test_pokemon("abomasnow-mega", &["grass", "ice"], 90, 100),
+            garchomp,
+        ];
+        retain_available(&mut pokemons);
+        assert_eq!(pokemons.len(), 1);
+        assert_eq!(pokemons[0].name, "garchomp");
+
+        let mut earthquake = test_move(
+            "earthquake",
+            "ground",
+            "physical",
+            Some(100),
+            "all-other-pokemon",
+        );
+        earthquake.flavor_text_entries = vec![FlavorText::new(
+            "The user sets off an earthquake.".to_string(),
+            Language::english(),
+            VersionGroup::scarlet_violet(),
+        )];
+        let mut moves = vec![
+            test_move(
+                "10-000-000-volt-thunderbolt",
+                "electric",
+                "special",
+                Some(195),
+                "selected-pokemon",
+            ),
+            earthquake,
+        ];
+        retain_available(&mut moves);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].name, "earthquake");
+    }
+}
@@ -0,0 +1,78 @@
+use crate::domain::model::battle::ReplacementRequest;
+use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::model::team_preview::TeamPreview;
+use crate::domain::service::legal_actions::SlotOptions;
+
+/// 行動を選ぶ主体（人間・AI・テスト用の台本）のインターフェース。
+/// バトルの進行はこのトレイトを通してのみ選択を受け取るため、実装は互いに差し替えられる
+pub trait BattleAgent {
+    /// 見せ合いで、連れて行くポケモンのパーティ番号を先発から順に返す
+    fn choose_team(&mut self, preview: &TeamPreview) -> Vec<usize> {
+        (0..preview.bring_count.min(preview.own.len())).collect()
+    }
+
+    /// 行動できる枠ごとに、選べる行動の中から1つずつ選ぶ
+    fn choose_actions(&mut self, view: &PlayerView, options: &[SlotOptions]) -> Vec<ChosenAction>;
+
+    /// ひんしで空いた枠に繰り出す控えを選ぶ
+    fn choose_replacements(
+        &mut self,
+        view: &PlayerView,
+        request: &ReplacementRequest,
+    ) -> Vec<(SlotPosition, usize)> {
+        let _ = view;
+        first_replacements(request)
+    }
+}
+
+/// 空いた枠に、控えをパーティの順に繰り出す
+pub fn first_replacements(request: &ReplacementRequest) -> Vec<(SlotPosition, usize)> {
+    request
+        .slots
+        .iter()
+        .zip(&request.candidates)
+        .take(request.required_count())
+        .map(|(slot, party_index)| (SlotPosition::new(request.side, *slot), *party_index))
+        .collect()
+}
+
+/// 同じターンに選んだ他の枠の行動と両立するか（同じ控えへの交代、2体同時のテラスタルは不可）
+pub fn is_compatible(chosen: &[ChosenAction], action: &BattleAction) -> bool {
+    chosen.iter().all(|other| match (&other.action, action) {
+        (BattleAction::Switch { party_index: a }, BattleAction::Switch { party_index: b }) => {
+            a != b
+        }
+        (
+            BattleAction::UseMove {
+                terastallize: true, ..
+            },
+            BattleAction::UseMove {
+                terastallize: true, ..
+            },
+        ) => false,
+        _ => true,
+    })
+}
+
+/// 各枠の選択肢を組み合わせた、1ターン分の行動の組をすべて列挙する（両立しない組は除く）
+pub fn joint_actions(options: &[SlotOptions]) -> Vec<Vec<ChosenAction>> {
+    let mut joints = vec![Vec::new()];
+    for slot in options {
+        joints = joints
+            .into_iter()
+            .flat_map(|chosen: Vec<ChosenAction>| {
+                slot.actions
+                    .iter()
+                    .filter(|action| is_compatible(&chosen, action))
+                    .map(|action| {
+                        let mut next = chosen.clone();
+                        next.push(ChosenAction::new(slot.position, action.clone()));
+                        next
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    joints
+}
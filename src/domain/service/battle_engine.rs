@@ -0,0 +1,1588 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+use crate::domain::model::battle_event::{
+    BattleEvent, CantMoveReason, DamageSource, Effectiveness,
+};
+use crate::domain::model::battle_rng::BattleRng;
+use crate::domain::model::field::{Terrain, Weather};
+use crate::domain::model::move_slot::MoveSlot;
+use crate::domain::model::stats::StatName;
+use crate::domain::model::status::StatusCondition;
+use crate::domain::model::volatile_status::{CenterOfAttention, LastMove, MoveRestriction};
+use crate::domain::service::ally_support::AllySupport;
+use crate::domain::service::battle_judge::{BattleJudge, FaintTiebreak};
+use crate::domain::service::damage_calculator::{DamageCalculator, DamageContext};
+use crate::domain::service::legal_actions::{CHOICE_ITEMS, IllegalActionReason, LegalActions};
+use crate::domain::service::redirection_resolver::RedirectionResolver;
+use crate::domain::service::status_effects::StatusEffects;
+use crate::domain::service::target_resolver::{TargetError, TargetResolver};
+use crate::domain::service::turn_order_resolver::TurnOrderResolver;
+use crate::domain::service::type_chart::TypeChart;
+use std::collections::VecDeque;
+
+/// ターン実行時に選択された行動が不正だった場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BattleError {
+    /// 指定した位置に行動できるポケモンがいない
+    PokemonNotActive(SlotPosition),
+    /// 覚えていない技、またはPPのない技が選ばれた
+    InvalidMove {
+        user: SlotPosition,
+        move_index: usize,
+    },
+    /// 交代できない控えが選ばれた
+    InvalidSwitch {
+        user: SlotPosition,
+        party_index: usize,
+    },
+    /// 技の対象指定が不正
+    InvalidTarget {
+        user: SlotPosition,
+        error: TargetError,
+    },
+    /// 決着済みのバトルを進めようとした
+    BattleFinished,
+    /// ひんしで空いた枠への交代が済んでいない
+    ReplacementPending,
+    /// 交代を求められていない枠、または繰り出せない控えが選ばれた
+    InvalidReplacement {
+        position: SlotPosition,
+        party_index: usize,
+    },
+    /// 埋めるべき枠の数と選ばれた交代の数が合わない
+    ReplacementCountMismatch { side: usize },
+    /// ルール上選べない行動が選ばれた（こだわり・アンコール・ちょうはつ・捕捉など）
+    IllegalAction {
+        user: SlotPosition,
+        reason: IllegalActionReason,
+    },
+}
+
+/// 使うと自分がひんしになる技
+const SELF_KNOCK_OUT_MOVES: [&str; 4] =
+    ["explosion", "self-destruct", "misty-explosion", "memento"];
+
+/// 2〜3ターン暴れ続け、その間は他の行動を選べない技
+const LOCK_IN_MOVES: [&str; 4] = ["outrage", "petal-dance", "thrash", "raging-fury"];
+
+/// 使った次のターンは反動で動けない技
+const RECHARGE_MOVES: [&str; 10] = [
+    "hyper-beam",
+    "giga-impact",
+    "blast-burn",
+    "frenzy-plant",
+    "hydro-cannon",
+    "rock-wrecker",
+    "roar-of-time",
+    "prismatic-laser",
+    "eternabeam",
+    "meteor-assault",
+];
+
+/// 当たると相手を4〜5ターン逃げられなくし、毎ターン最大HPの1/8を削る技
+const BINDING_MOVES: [&str; 10] = [
+    "bind",
+    "wrap",
+    "fire-spin",
+    "whirlpool",
+    "sand-tomb",
+    "clamp",
+    "infestation",
+    "magma-storm",
+    "snap-trap",
+    "thunder-cage",
+];
+
+/// 決着がつかない場合に判定へ移るターン数
+pub const DEFAULT_TURN_LIMIT: u32 = 1000;
+
+/// 急所ランクごとの急所率の分母（1/24, 1/8, 1/2, 確定）
+const CRITICAL_HIT_DENOMINATORS: [u32; 4] = [24, 8, 2, 1];
+
+/// 1ターンの処理中に持ち回る状態
+struct TurnState<'a> {
+    rng: &'a mut dyn BattleRng,
+    queue: VecDeque<ChosenAction>,
+    /// 現在の行動でひんしになったポケモン（発生順）
+    fainted: Vec<SlotPosition>,
+    /// 現在の行動で両者が同時に全滅した場合の扱い
+    tiebreak: FaintTiebreak,
+    /// このターンに発生した出来事（発生順）
+    events: Vec<BattleEvent>,
+}
+
+/// 選択された行動に従ってバトルを1ターンずつ進める
+#[derive(Debug, Clone)]
+pub struct BattleEngine {
+    type_chart: TypeChart,
+    turn_limit: u32,
+}
+
+impl BattleEngine {
+    pub fn new(type_chart: TypeChart) -> Self {
+        Self {
+            type_chart,
+            turn_limit: DEFAULT_TURN_LIMIT,
+        }
+    }
+
+    pub fn with_turn_limit(mut self, turn_limit: u32) -> Self {
+        self.turn_limit = turn_limit;
+        self
+    }
+
+    pub fn turn_limit(&self) -> u32 {
+        self.turn_limit
+    }
+
+    /// バトルに記録された乱数を使って1ターンを実行し、発生した出来事を返す
+    pub fn execute_turn(
+        &self,
+        battle: &mut Battle,
+        actions: Vec<ChosenAction>,
+    ) -> Result<Vec<BattleEvent>, BattleError> {
+        let mut rng = battle.rng.clone();
+        let result = self.execute_turn_with_rng(battle, actions, &mut rng);
+        battle.rng = rng;
+        result
+    }
+
+    /// 指定した乱数源で1ターンを実行する。行動ごとにひんしを処理し、決着した時点でターンを打ち切る
+    pub fn execute_turn_with_rng(
+        &self,
+        battle: &mut Battle,
+        actions: Vec<ChosenAction>,
+        rng: &mut dyn BattleRng,
+    ) -> Result<Vec<BattleEvent>, BattleError> {
+        if battle.is_over() {
+            return Err(BattleError::BattleFinished);
+        }
+        if !battle.replacement_requests().is_empty() {
+            return Err(BattleError::ReplacementPending);
+        }
+        self.validate_actions(battle, &actions)?;
+        battle.turn += 1;
+
+        let queue = TurnOrderResolver::sort(battle, actions, rng).into();
+        let mut state = TurnState {
+            rng,
+            queue,
+            fainted: Vec::new(),
+            tiebreak: FaintTiebreak::Draw,
+            events: vec![BattleEvent::TurnStarted { turn: battle.turn }],
+        };
+        self.execute_terastallization(battle, &mut state);
+        while let Some(action) = state.queue.pop_front() {
+            match action.action {
+                BattleAction::Switch { party_index } => {
+                    self.execute_switch(battle, &mut state, action.user, party_index)
+                }
+                BattleAction::UseMove {
+                    move_index, target, ..
+                } => self.execute_move(battle, &mut state, action.user, move_index, target),
+                BattleAction::Struggle => self.execute_struggle(battle, &mut state, action.user),
+                BattleAction::Recharge => self.execute_recharge(battle, &mut state, action.user),
+            }
+            if self.process_faints(battle, &mut state) {
+                return Ok(state.events);
+            }
+        }
+
+        self.apply_residual_damage(battle, &mut state);
+        if self.process_faints(battle, &mut state) {
+            return Ok(state.events);
+        }
+        self.apply_field_effects(battle, &mut state);
+        self.apply_item_effects(battle, &mut state);
+        if self.process_faints(battle, &mut state) {
+            return Ok(state.events);
+        }
+
+        for side in &mut battle.sides {
+            for pokemon in &mut side.party {
+                pokemon.volatiles.end_turn();
+            }
+            side.clear_fainted_slots();
+        }
+        if battle.turn >= self.turn_limit {
+            let result = BattleJudge::judge_by_turn_limit(battle);
+            battle.result = Some(result);
+            state.events.push(BattleEvent::BattleEnded { result });
+        }
+        Ok(state.events)
+    }
+
+    /// ひんしで空いた枠に控えを繰り出す。同時に出る場合は繰り出すポケモンのすばやさ順に処理する
+    pub fn submit_replacements(
+        &self,
+        battle: &mut Battle,
+        choices: Vec<(SlotPosition, usize)>,
+    ) -> Result<Vec<BattleEvent>, BattleError> {
+        let requests = battle.replacement_requests();
+        for (position, party_index) in &choices {
+            let valid = requests.iter().any(|request| {
+                request.side == position.side
+                    && request.slots.contains(&position.slot)
+                    && request.candidates.contains(party_index)
+            });
+            let duplicated = choices
+                .iter()
+                .filter(|(other, index)| {
+                    other == position || (other.side == position.side && index == party_index)
+                })
+                .count()
+                > 1;
+            if !valid || duplicated {
+                return Err(BattleError::InvalidReplacement {
+                    position: *position,
+                    party_index: *party_index,
+                });
+            }
+        }
+        for request in &requests {
+            let count = choices
+                .iter()
+                .filter(|(position, _)| position.side == request.side)
+                .count();
+            if count != request.required_count() {
+                return Err(BattleError::ReplacementCountMismatch { side: request.side });
+            }
+        }
+
+        let mut ordered = choices;
+        ordered.sort_by_key(|(position, party_index)| {
+            std::cmp::Reverse(TurnOrderResolver::effective_speed(
+                &battle.sides[position.side].party[*party_index],
+            ))
+        });
+        let mut events = Vec::new();
+        for (position, party_index) in ordered {
+            battle.sides[position.side].switch_in(position.slot, party_index);
+            events.push(Self::switched_event(battle, position, party_index));
+        }
+        for side in &mut battle.sides {
+            side.clear_fainted_slots();
+        }
+        Ok(events)
+    }
+
+    /// 行動1回分で発生したひんしをまとめて処理し、決着したら true を返す
+    fn process_faints(&self, battle: &mut Battle, state: &mut TurnState) -> bool {
+        for position in state.fainted.drain(..) {
+            if let Some(pokemon) = battle.pokemon_at_mut(position) {
+                pokemon.volatiles = Default::default();
+                state.events.push(BattleEvent::Fainted {
+                    position,
+                    pokemon: pokemon.name.clone(),
+                });
+            }
+        }
+        let tiebreak = std::mem::replace(&mut state.tiebreak, FaintTiebreak::Draw);
+        if let Some(result) = BattleJudge::judge(battle, tiebreak) {
+            battle.result = Some(result);
+            state.events.push(BattleEvent::BattleEnded { result });
+            return true;
+        }
+        false
+    }
+
+    /// ターン終了時のやけど・どくのダメージ（すばやさ順）
+    fn apply_residual_damage(&self, battle: &mut Battle, state: &mut TurnState) {
+        let mut positions = battle.active_positions();
+        positions.sort_by_key(|position| {
+            std::cmp::Reverse(
+                battle
+                    .pokemon_at(*position)
+                    .map_or(0, TurnOrderResolver::effective_speed),
+            )
+        });
+        for position in positions {
+            let Some(pokemon) = battle.pokemon_at(position) else {
+                continue;
+            };
+            let bound = pokemon.volatiles.bound_turns > 0;
+            let max_hp = pokemon.max_hp;
+            let damage = match pokemon.status {
+                Some(StatusCondition::Burn) => Some(max_hp / 16),
+                Some(StatusCondition::Poison) => Some(max_hp / 8),
+                _ => None,
+            };
+            if let Some(damage) = damage {
+                self.apply_damage(battle, state, position, damage.max(1), DamageSource::Status);
+            }
+            if bound {
+                let damage = (max_hp / 8).max(1);
+                self.apply_damage(battle, state, position, damage, DamageSource::Binding);
+            }
+        }
+    }
+
+    /// ターン終了時の天気・フィールドの効果と経過（すばやさ順）
+    fn apply_field_effects(&self, battle: &mut Battle, state: &mut TurnState) {
+        let mut positions = battle.active_positions();
+        positions.sort_by_key(|position| {
+            std::cmp::Reverse(
+                battle
+                    .pokemon_at(*position)
+                    .map_or(0, TurnOrderResolver::effective_speed),
+            )
+        });
+        if battle.field.weather == Some(Weather::Sandstorm) {
+            for position in &positions {
+                let Some(pokemon) = battle.pokemon_at(*position) else {
+                    continue;
+                };
+                if ["rock", "ground", "steel"]
+                    .iter()
+                    .any(|type_name| pokemon.has_type(type_name))
+                {
+                    continue;
+                }
+                let damage = (pokemon.max_hp / 16).max(1);
+                self.apply_damage(battle, state, *position, damage, DamageSource::Weather);
+            }
+        }
+        if battle.field.terrain == Some(Terrain::Grassy) {
+            for position in &positions {
+                let Some(pokemon) = battle.pokemon_at(*position) else {
+                    continue;
+                };
+                if pokemon.is_fainted() || !pokemon.is_grounded() {
+                    continue;
+                }
+                let amount = (pokemon.max_hp / 16).max(1);
+                self.heal(battle, state, *position, amount);
+            }
+        }
+        if let Some(weather) = battle.field.tick_weather() {
+            state.events.push(BattleEvent::WeatherEnded { weather });
+        }
+        if let Some(terrain) = battle.field.tick_terrain() {
+            state.events.push(BattleEvent::TerrainEnded { terrain });
+        }
+    }
+
+    /// ターン終了時の持ち物の効果（すばやさ順）
+    fn apply_item_effects(&self, battle: &mut Battle, state: &mut TurnState) {
+        let mut positions = battle.active_positions();
+        positions.sort_by_key(|position| {
+            std::cmp::Reverse(
+                battle
+                    .pokemon_at(*position)
+                    .map_or(0, TurnOrderResolver::effective_speed),
+            )
+        });
+        for position in positions {
+            let Some(pokemon) = battle.pokemon_at(position) else {
+                continue;
+            };
+            if pokemon.is_fainted()
+                || !pokemon.has_item("leftovers")
+                || pokemon.current_hp == pokemon.max_hp
+            {
+                continue;
+            }
+            let amount = (pokemon.max_hp / 16).max(1);
+            Self::activate_item(battle, state, position, "leftovers");
+            self.heal(battle, state, position, amount);
+        }
+    }
+
+    /// HPを回復する。実際に回復した場合のみ出来事を記録する
+    fn heal(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        position: SlotPosition,
+        amount: u32,
+    ) {
+        let Some(pokemon) = battle.pokemon_at_mut(position) else {
+            return;
+        };
+        let healed = amount.min(pokemon.max_hp - pokemon.current_hp);
+        if healed == 0 {
+            return;
+        }
+        pokemon.current_hp += healed;
+        state.events.push(BattleEvent::Healed {
+            target: position,
+            amount: healed,
+            remaining_hp: pokemon.current_hp,
+        });
+    }
+
+    /// ダメージを与え、ひんしになった場合は記録する
+    fn apply_damage(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        position: SlotPosition,
+        damage: u32,
+        source: DamageSource,
+    ) -> u32 {
+        let Some(pokemon) = battle.pokemon_at_mut(position) else {
+            return 0;
+        };
+        if pokemon.is_fainted() {
+            return 0;
+        }
+        let dealt = damage.min(pokemon.current_hp);
+        pokemon.take_damage(dealt);
+        let percentage = if dealt > 0 {
+            (dealt * 100 / pokemon.max_hp.max(1)).max(1)
+        } else {
+            0
+        };
+        state.events.push(BattleEvent::Damage {
+            target: position,
+            pokemon: pokemon.name.clone(),
+            source,
+            amount: dealt,
+            percentage,
+            remaining_hp: pokemon.current_hp,
+        });
+        if pokemon.is_fainted() {
+            state.fainted.push(position);
+        } else if source == DamageSource::Move
+            && dealt > 0
+            && pokemon.has_ability("illusion")
+            && !pokemon.volatiles.illusion_broken
+        {
+            pokemon.volatiles.illusion_broken = true;
+            Self::activate_ability(battle, state, position, "illusion");
+        }
+        dealt
+    }
+
+    /// 特性の発動を知らせ、相手に特性が明らかになったことを記録する
+    fn activate_ability(
+        battle: &mut Battle,
+        state: &mut TurnState,
+        position: SlotPosition,
+        ability: &str,
+    ) {
+        if let Some(pokemon) = battle.pokemon_at_mut(position) {
+            pokemon.revealed.ability = true;
+        }
+        state.events.push(BattleEvent::AbilityActivated {
+            position,
+            ability: ability.to_string(),
+        });
+    }
+
+    /// 持ち物の発動を知らせ、相手に持ち物が明らかになったことを記録する
+    fn activate_item(
+        battle: &mut Battle,
+        state: &mut TurnState,
+        position: SlotPosition,
+        item: &str,
+    ) {
+        if let Some(pokemon) = battle.pokemon_at_mut(position) {
+            pokemon.revealed.item = true;
+        }
+        state.events.push(BattleEvent::ItemActivated {
+            position,
+            item: item.to_string(),
+        });
+    }
+
+    fn validate_actions(
+        &self,
+        battle: &Battle,
+        actions: &[ChosenAction],
+    ) -> Result<(), BattleError> {
+        let mut switch_targets = Vec::new();
+        let mut tera_sides = Vec::new();
+        for action in actions {
+            let user = action.user;
+            let illegal = |result: Result<(), IllegalActionReason>| {
+                result.map_err(|reason| BattleError::IllegalAction { user, reason })
+            };
+            let pokemon = battle
+                .pokemon_at(user)
+                .filter(|pokemon| !pokemon.is_fainted())
+                .ok_or(BattleError::PokemonNotActive(user))?;
+
+            match &action.action {
+                BattleAction::UseMove {
+                    move_index,
+                    target,
+                    terastallize,
+                } => {
+                    let move_slot =
+                        pokemon
+                            .moves
+                            .get(*move_index)
+                            .ok_or(BattleError::InvalidMove {
+                                user,
+                                move_index: *move_index,
+                            })?;
+                    illegal(LegalActions::check(battle, user, &action.action))?;
+                    if *terastallize {
+                        if tera_sides.contains(&user.side) {
+                            illegal(Err(IllegalActionReason::TeraUnavailable))?;
+                        }
+                        tera_sides.push(user.side);
+                    }
+                    if pokemon.volatiles.locked_move.is_none() {
+                        TargetResolver::validate(battle.format, user, &move_slot.target, *target)
+                            .map_err(|error| BattleError::InvalidTarget { user, error })?;
+                    }
+                }
+                BattleAction::Switch { party_index } => {
+                    let available = battle.sides[user.side].bench().contains(party_index);
+                    if !available || switch_targets.contains(&(user.side, *party_index)) {
+                        return Err(BattleError::InvalidSwitch {
+                            user,
+                            party_index: *party_index,
+                        });
+                    }
+                    illegal(LegalActions::check(battle, user, &action.action))?;
+                    switch_targets.push((user.side, *party_index));
+                }
+                BattleAction::Struggle | BattleAction::Recharge => {
+                    illegal(LegalActions::check(battle, user, &action.action))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// テラスタルを選んだポケモンは、ターンの最初に行動順でテラスタルする
+    fn execute_terastallization(&self, battle: &mut Battle, state: &mut TurnState) {
+        for action in &state.queue {
+            if !matches!(
+                action.action,
+                BattleAction::UseMove {
+                    terastallize: true,
+                    ..
+                }
+            ) {
+                continue;
+            }
+            let position = action.user;
+            battle.sides[position.side].tera_used = true;
+            let Some(pokemon) = battle.pokemon_at_mut(position) else {
+                continue;
+            };
+            pokemon.terastallized = true;
+            if let Some(tera_type) = &pokemon.tera_type {
+                state.events.push(BattleEvent::Terastallized {
+                    position,
+                    tera_type: tera_type.name.clone(),
+                });
+            }
+        }
+    }
+
+    fn execute_switch(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        user: SlotPosition,
+        party_index: usize,
+    ) {
+        if !battle.is_alive(user) || !battle.sides[user.side].bench().contains(&party_index) {
+            return;
+        }
+        if let Some(outgoing) = battle.pokemon_at_mut(user) {
+            outgoing.reset_on_switch_out();
+        }
+        battle.sides[user.side].switch_in(user.slot, party_index);
+        state
+            .events
+            .push(Self::switched_event(battle, user, party_index));
+    }
+
+    fn switched_event(battle: &Battle, position: SlotPosition, party_index: usize) -> BattleEvent {
+        BattleEvent::Switched {
+            position,
+            pokemon: battle.sides[position.side].party[party_index].name.clone(),
+            party_index,
+        }
+    }
+
+    fn execute_move(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        user: SlotPosition,
+        move_index: usize,
+        target: Option<SlotPosition>,
+    ) {
+        if !battle.is_alive(user) || !self.can_act(battle, state, user) {
+            return;
+        }
+        let Some(pokemon) = battle.pokemon_at_mut(user) else {
+            return;
+        };
+        let Some(move_slot) = pokemon.moves.get_mut(move_index) else {
+            return;
+        };
+        // 暴れる技の2ターン目以降はPPを消費しない
+        let continuing = pokemon.volatiles.locked_move.is_some();
+        if !continuing {
+            move_slot.consume_pp();
+        }
+        let move_slot = move_slot.clone();
+        let holds_choice_item = CHOICE_ITEMS.iter().any(|item| pokemon.has_item(item));
+        let volatiles = &mut pokemon.volatiles;
+        if move_slot.name != "ally-switch" {
+            volatiles.ally_switch_count = 0;
+        }
+        volatiles.last_move = Some(LastMove { move_index, target });
+        pokemon.revealed.reveal_move(move_index);
+        if holds_choice_item && volatiles.choice_lock.is_none() {
+            volatiles.choice_lock = Some(move_index);
+        }
+        match volatiles.locked_move {
+            Some(locked) if locked.turns > 1 => {
+                volatiles.locked_move = Some(MoveRestriction {
+                    turns: locked.turns - 1,
+                    ..locked
+                });
+            }
+            Some(_) => volatiles.locked_move = None,
+            None if LOCK_IN_MOVES.contains(&move_slot.name.as_str()) => {
+                volatiles.locked_move = Some(MoveRestriction {
+                    move_index,
+                    turns: 1 + state.rng.next_below(2),
+                });
+            }
+            None => {}
+        }
+        state.events.push(BattleEvent::MoveUsed {
+            user,
+            pokemon: pokemon.name.clone(),
+            move_name: move_slot.name.clone(),
+        });
+
+        let hit = self.use_move(battle, state, user, &move_slot, target);
+        if hit
+            && RECHARGE_MOVES.contains(&move_slot.name.as_str())
+            && let Some(pokemon) = battle.pokemon_at_mut(user)
+        {
+            pokemon.volatiles.must_recharge = true;
+        }
+    }
+
+    /// 反動で動けないターン
+    fn execute_recharge(&self, battle: &mut Battle, state: &mut TurnState, user: SlotPosition) {
+        if !battle.is_alive(user) {
+            return;
+        }
+        if let Some(pokemon) = battle.pokemon_at_mut(user) {
+            pokemon.volatiles.must_recharge = false;
+        }
+        state.events.push(BattleEvent::CantMove {
+            user,
+            reason: CantMoveReason::Recharging,
+        });
+    }
+
+    /// 出せる技がない場合の「わるあがき」。使用者は最大HPの1/4の反動を受ける
+    fn execute_struggle(&self, battle: &mut Battle, state: &mut TurnState, user: SlotPosition) {
+        if !battle.is_alive(user) || !self.can_act(battle, state, user) {
+            return;
+        }
+        let Some(pokemon) = battle.pokemon_at_mut(user) else {
+            return;
+        };
+        pokemon.volatiles.last_move = None;
+        let move_slot = MoveSlot::struggle();
+        state.events.push(BattleEvent::MoveUsed {
+            user,
+            pokemon: pokemon.name.clone(),
+            move_name: move_slot.name.clone(),
+        });
+        let max_hp = pokemon.max_hp;
+
+        self.use_move(battle, state, user, &move_slot, None);
+        self.apply_damage(
+            battle,
+            state,
+            user,
+            (max_hp / 4).max(1),
+            DamageSource::Recoil,
+        );
+        if !battle.is_alive(user) {
+            state.tiebreak = FaintTiebreak::Recoil {
+                user_side: user.side,
+            };
+        }
+    }
+
+    /// 技の効果を対象に適用する。いずれかの対象にダメージを与えた場合は true を返す
+    fn use_move(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        user: SlotPosition,
+        move_slot: &MoveSlot,
+        target: Option<SlotPosition>,
+    ) -> bool {
+        let move_slot = move_slot.clone();
+        if self.execute_field_move(battle, state, &move_slot) {
+            return false;
+        }
+        let targets = TargetResolver::resolve(battle, user, &move_slot.target, target, state.rng);
+        let targets = RedirectionResolver::redirect(battle, user, &move_slot, targets);
+        if self.execute_ally_interaction(battle, state, user, &move_slot, &targets) {
+            return false;
+        }
+        if targets.is_empty() {
+            state.events.push(BattleEvent::MoveFailed { user });
+        }
+        let is_spread = TargetResolver::is_spread(&move_slot.target, &targets);
+        let weather = battle.field.weather;
+        let terrain = battle.field.terrain;
+        let mut hit = false;
+
+        for target in targets {
+            if AllySupport::avoids_ally_attack(battle, user, target, &move_slot) {
+                Self::activate_ability(battle, state, target, "telepathy");
+                continue;
+            }
+            if target != user && self.absorbs_move(battle, target, &move_slot) {
+                if let Some(ability) = RedirectionResolver::absorbing_ability(&move_slot.type_name)
+                {
+                    Self::activate_ability(battle, state, target, ability);
+                }
+                self.change_rank(battle, state, target, StatName::SpecialAttack, 1);
+                continue;
+            }
+            if target != user && !self.hits(battle, state, user, target, &move_slot) {
+                state.events.push(BattleEvent::Missed { user, target });
+                continue;
+            }
+            if move_slot.damage_class.is_status() {
+                for (stat, change) in &move_slot.stat_changes {
+                    self.change_rank(battle, state, target, stat.clone(), *change);
+                }
+                if move_slot.meta.ailment_chance == 0 {
+                    self.inflict_ailment(battle, state, target, &move_slot);
+                }
+                self.apply_move_restriction(battle, state, user, target, &move_slot);
+                continue;
+            }
+
+            let (Some(attacker), Some(defender)) =
+                (battle.pokemon_at(user), battle.pokemon_at(target))
+            else {
+                continue;
+            };
+            let critical_stage = (move_slot.meta.crit_rate as usize).min(3);
+            let context = DamageContext {
+                is_spread,
+                is_critical: state
+                    .rng
+                    .chance(1, CRITICAL_HIT_DENOMINATORS[critical_stage]),
+                random_roll: 85 + state.rng.next_below(16),
+                base_power_modifier: AllySupport::base_power_modifier(battle, user, &move_slot),
+                final_modifier: AllySupport::final_modifier(battle, target),
+                weather,
+                terrain,
+            };
+            let result = DamageCalculator::calculate(
+                attacker,
+                defender,
+                &move_slot,
+                &self.type_chart,
+                &context,
+            );
+            let effectiveness = if result.effectiveness == 0.0 {
+                Some(Effectiveness::NoEffect)
+            } else if result.effectiveness < 1.0 {
+                Some(Effectiveness::NotVeryEffective)
+            } else if result.effectiveness > 1.0 {
+                Some(Effectiveness::SuperEffective)
+            } else {
+                None
+            };
+            if let Some(effectiveness) = effectiveness {
+                state.events.push(BattleEvent::Effectiveness {
+                    target,
+                    effectiveness,
+                });
+            }
+            if effectiveness == Some(Effectiveness::NoEffect) {
+                continue;
+            }
+            if context.is_critical {
+                state.events.push(BattleEvent::CriticalHit { target });
+            }
+            let dealt = self.apply_damage(battle, state, target, result.damage, DamageSource::Move);
+            hit |= dealt > 0;
+            self.apply_drain(battle, state, user, &move_slot, dealt);
+            if dealt > 0 && BINDING_MOVES.contains(&move_slot.name.as_str()) {
+                self.apply_binding(battle, state, target);
+            }
+            if dealt > 0
+                && move_slot.meta.ailment_chance > 0
+                && state.rng.chance(move_slot.meta.ailment_chance, 100)
+            {
+                self.inflict_ailment(battle, state, target, &move_slot);
+            }
+        }
+
+        if SELF_KNOCK_OUT_MOVES.contains(&move_slot.name.as_str()) {
+            let max_hp = battle.pokemon_at(user).map_or(0, |pokemon| pokemon.max_hp);
+            self.apply_damage(battle, state, user, max_hp, DamageSource::Move);
+            state.tiebreak = FaintTiebreak::SelfKnockOut {
+                user_side: user.side,
+            };
+        }
+        hit
+    }
+
+    /// 「ちょうはつ」「アンコール」「かなしばり」「いちゃもん」で対象の技を縛る
+    fn apply_move_restriction(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        user: SlotPosition,
+        target: SlotPosition,
+        move_slot: &MoveSlot,
+    ) {
+        let Some(pokemon) = battle.pokemon_at_mut(target) else {
+            return;
+        };
+        let volatiles = &mut pokemon.volatiles;
+        let last_move_index = volatiles.last_move.map(|last_move| last_move.move_index);
+        let applied = match move_slot.name.as_str() {
+            "taunt" if volatiles.taunt_turns == 0 => {
+                volatiles.taunt_turns = 3;
+                true
+            }
+            "encore" if volatiles.encore.is_none() => {
+                volatiles.encore = last_move_index.map(|move_index| MoveRestriction {
+                    move_index,
+                    turns: 3,
+                });
+                volatiles.encore.is_some()
+            }
+            "disable" if volatiles.disable.is_none() => {
+                volatiles.disable = last_move_index.map(|move_index| MoveRestriction {
+                    move_index,
+                    turns: 4,
+                });
+                volatiles.disable.is_some()
+            }
+            "torment" if !volatiles.tormented => {
+                volatiles.tormented = true;
+                true
+            }
+            "taunt" | "encore" | "disable" | "torment" => false,
+            _ => return,
+        };
+        if applied {
+            state.events.push(BattleEvent::EffectStarted {
+                target,
+                effect: move_slot.name.clone(),
+            });
+        } else {
+            state.events.push(BattleEvent::MoveFailed { user });
+        }
+    }
+
+    /// バインド技で対象を4〜5ターン逃げられなくする
+    fn apply_binding(&self, battle: &mut Battle, state: &mut TurnState, target: SlotPosition) {
+        let Some(pokemon) = battle.pokemon_at_mut(target) else {
+            return;
+        };
+        if pokemon.is_fainted() || pokemon.volatiles.bound_turns > 0 {
+            return;
+        }
+        pokemon.volatiles.bound_turns = 4 + state.rng.next_below(2);
+        state.events.push(BattleEvent::EffectStarted {
+            target,
+            effect: "binding".to_string(),
+        });
+    }
+
+    /// ねむり・こおり・まひで行動できないかを判定する
+    fn can_act(&self, battle: &mut Battle, state: &mut TurnState, user: SlotPosition) -> bool {
+        let Some(pokemon) = battle.pokemon_at_mut(user) else {
+            return false;
+        };
+        let (cured, reason) = match pokemon.status {
+            Some(StatusCondition::Sleep) => {
+                if pokemon.sleep_turns == 0 {
+                    (true, None)
+                } else {
+                    pokemon.sleep_turns -= 1;
+                    (false, Some(CantMoveReason::Asleep))
+                }
+            }
+            Some(StatusCondition::Freeze) => {
+                if state.rng.chance(1, 5) {
+                    (true, None)
+                } else {
+                    (false, Some(CantMoveReason::Frozen))
+                }
+            }
+            Some(StatusCondition::Paralysis) if state.rng.chance(1, 4) => {
+                (false, Some(CantMoveReason::Paralyzed))
+            }
+            _ => (false, None),
+        };
+        if cured && let Some(status) = pokemon.status.take() {
+            state.events.push(BattleEvent::StatusCured {
+                target: user,
+                status,
+            });
+        }
+        match reason {
+            Some(reason) => {
+                state.events.push(BattleEvent::CantMove { user, reason });
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// 能力ランクを変化させ、実際に変化した場合のみ出来事を記録する
+    fn change_rank(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        target: SlotPosition,
+        stat: StatName,
+        change: i32,
+    ) {
+        let Some(pokemon) = battle.pokemon_at_mut(target) else {
+            return;
+        };
+        let change = pokemon.change_rank(stat.clone(), change);
+        if change != 0 {
+            state.events.push(BattleEvent::StatChanged {
+                target,
+                stat,
+                change,
+            });
+        }
+    }
+
+    /// 命中判定。命中・回避ランクを反映する
+    fn hits(
+        &self,
+        battle: &Battle,
+        state: &mut TurnState,
+        user: SlotPosition,
+        target: SlotPosition,
+        move_slot: &MoveSlot,
+    ) -> bool {
+        let Some(accuracy) = move_slot.accuracy else {
+            return true;
+        };
+        let (Some(attacker), Some(defender)) = (battle.pokemon_at(user), battle.pokemon_at(target))
+        else {
+            return false;
+        };
+        let stage = (attacker.accuracy_rank as i32 - defender.evasion_rank as i32).clamp(-6, 6);
+        let (numerator, denominator) = if stage >= 0 {
+            (3 + stage, 3)
+        } else {
+            (3, 3 - stage)
+        };
+        let accuracy = accuracy * numerator as u32 / denominator as u32;
+        state.rng.chance(accuracy, 100)
+    }
+
+    /// 技の追加効果・変化技による状態異常
+    fn inflict_ailment(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        target: SlotPosition,
+        move_slot: &MoveSlot,
+    ) {
+        let Some(status) = StatusEffects::from_ailment(&move_slot.meta.ailment) else {
+            return;
+        };
+        if !battle
+            .pokemon_at(target)
+            .is_some_and(|pokemon| StatusEffects::can_inflict(pokemon, &status))
+        {
+            return;
+        }
+        // ねむり状態の制限: 同じサイドにすでに眠っているポケモンがいれば眠らない
+        if status == StatusCondition::Sleep
+            && battle.rules.sleep_clause
+            && battle.sides[target.side]
+                .party
+                .iter()
+                .any(|member| !member.is_fainted() && member.status == Some(StatusCondition::Sleep))
+        {
+            return;
+        }
+        let Some(pokemon) = battle.pokemon_at_mut(target) else {
+            return;
+        };
+        if status == StatusCondition::Sleep {
+            pokemon.sleep_turns = 1 + state.rng.next_below(3);
+        }
+        pokemon.set_status(status.clone());
+        state
+            .events
+            .push(BattleEvent::StatusApplied { target, status });
+    }
+
+    /// 与えたダメージに応じたHP吸収・反動ダメージ
+    fn apply_drain(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        user: SlotPosition,
+        move_slot: &MoveSlot,
+        dealt: u32,
+    ) {
+        let drain = move_slot.meta.drain;
+        if dealt == 0 || drain == 0 || !battle.is_alive(user) {
+            return;
+        }
+        let amount = (dealt * drain.unsigned_abs() / 100).max(1);
+        if drain > 0 {
+            self.heal(battle, state, user, amount);
+            return;
+        }
+        self.apply_damage(battle, state, user, amount, DamageSource::Recoil);
+        if !battle.is_alive(user) {
+            state.tiebreak = FaintTiebreak::Recoil {
+                user_side: user.side,
+            };
+        }
+    }
+
+    /// ダブルバトルで味方や行動順に作用する技を処理する。処理した場合は true を返す
+    fn execute_ally_interaction(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        user: SlotPosition,
+        move_slot: &MoveSlot,
+        targets: &[SlotPosition],
+    ) -> bool {
+        match move_slot.name.as_str() {
+            "follow-me" | "rage-powder" => {
+                let center = if move_slot.name == "follow-me" {
+                    CenterOfAttention::FollowMe
+                } else {
+                    CenterOfAttention::RagePowder
+                };
+                if battle.format.is_doubles()
+                    && let Some(pokemon) = battle.pokemon_at_mut(user)
+                {
+                    pokemon.volatiles.center_of_attention = Some(center);
+                }
+            }
+            "helping-hand" => {
+                for target in targets {
+                    if let Some(ally) = battle.pokemon_at_mut(*target) {
+                        ally.volatiles.helping_hand_count += 1;
+                    }
+                }
+            }
+            "ally-switch" => self.execute_ally_switch(battle, state, user),
+            "after-you" | "quash" => {
+                let queue = &mut state.queue;
+                let position = targets
+                    .first()
+                    .and_then(|target| queue.iter().position(|action| action.user == *target));
+                match position.and_then(|position| queue.remove(position)) {
+                    Some(action) if move_slot.name == "after-you" => queue.push_front(action),
+                    Some(action) => queue.push_back(action),
+                    None => state.events.push(BattleEvent::MoveFailed { user }),
+                }
+            }
+            "instruct" => {
+                let Some(&target) = targets.first() else {
+                    state.events.push(BattleEvent::MoveFailed { user });
+                    return true;
+                };
+                let last_move = battle
+                    .pokemon_at(target)
+                    .and_then(|pokemon| {
+                        pokemon
+                            .volatiles
+                            .last_move
+                            .map(|last_move| (pokemon, last_move))
+                    })
+                    .filter(|(pokemon, last_move)| {
+                        pokemon
+                            .moves
+                            .get(last_move.move_index)
+                            .is_some_and(|slot| slot.has_pp() && slot.name != "instruct")
+                    })
+                    .map(|(_, last_move)| last_move);
+                let Some(last_move) = last_move else {
+                    state.events.push(BattleEvent::MoveFailed { user });
+                    return true;
+                };
+                self.execute_move(
+                    battle,
+                    state,
+                    target,
+                    last_move.move_index,
+                    last_move.target,
+                );
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// 味方と位置を入れ替える。第9世代では「まもる」と同様に、連続で使うと成功率が 1/3 ずつ下がる
+    fn execute_ally_switch(&self, battle: &mut Battle, state: &mut TurnState, user: SlotPosition) {
+        let ally = battle.ally_positions(user).first().copied();
+        let Some(pokemon) = battle.pokemon_at_mut(user) else {
+            return;
+        };
+        let Some(ally) = ally else {
+            pokemon.volatiles.ally_switch_count = 0;
+            state.events.push(BattleEvent::MoveFailed { user });
+            return;
+        };
+        let denominator = 3u32.saturating_pow(pokemon.volatiles.ally_switch_count);
+        if !state.rng.chance(1, denominator) {
+            pokemon.volatiles.ally_switch_count = 0;
+            state.events.push(BattleEvent::MoveFailed { user });
+            return;
+        }
+        pokemon.volatiles.ally_switch_count += 1;
+
+        battle.sides[user.side].active.swap(user.slot, ally.slot);
+        for action in state.queue.iter_mut() {
+            if action.user == ally {
+                action.user = user;
+            } else if action.user == user {
+                action.user = ally;
+            }
+        }
+    }
+
+    /// 天気・フィールドを変える技を処理する。処理した場合は true を返す
+    fn execute_field_move(
+        &self,
+        battle: &mut Battle,
+        state: &mut TurnState,
+        move_slot: &MoveSlot,
+    ) -> bool {
+        let weather = match move_slot.name.as_str() {
+            "sunny-day" => Some(Weather::Sun),
+            "rain-dance" => Some(Weather::Rain),
+            "sandstorm" => Some(Weather::Sandstorm),
+            "snowscape" => Some(Weather::Snow),
+            _ => None,
+        };
+        let terrain = match move_slot.name.as_str() {
+            "electric-terrain" => Some(Terrain::Electric),
+            "grassy-terrain" => Some(Terrain::Grassy),
+            "misty-terrain" => Some(Terrain::Misty),
+            "psychic-terrain" => Some(Terrain::Psychic),
+            _ => None,
+        };
+        if let Some(weather) = weather {
+            battle.field.set_weather(weather);
+            state.events.push(BattleEvent::WeatherStarted { weather });
+        } else if let Some(terrain) = terrain {
+            battle.field.set_terrain(terrain);
+            state.events.push(BattleEvent::TerrainStarted { terrain });
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// 「ひらいしん」「よびみず」で技を無効化するか
+    fn absorbs_move(&self, battle: &Battle, target: SlotPosition, move_slot: &MoveSlot) -> bool {
+        RedirectionResolver::absorbing_ability(&move_slot.type_name).is_some_and(|ability| {
+            battle
+                .pokemon_at(target)
+                .is_some_and(|pokemon| pokemon.has_ability(ability))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_result::BattleResult;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::model::format::BattleRules;
+    use crate::domain::model::r#move::Move;
+    use crate::domain::test_support::{
+        ScriptedRng, test_active_pokemon, test_doubles_battle, test_move,
+    };
+
+    fn create_singles_battle(player_party_size: usize) -> Battle {
+        let moves = vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move(
+                "explosion",
+                "normal",
+                "physical",
+                Some(250),
+                "all-other-pokemon",
+            ),
+        ];
+        let party = (0..player_party_size)
+            .map(|_| test_active_pokemon("a", &["normal"], &moves))
+            .collect();
+        Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), party, 1),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+    }
+
+    /// 乱数を最大値に固定して1ターン実行する（命中・急所なし・乱数100%）
+    fn execute(
+        engine: &BattleEngine,
+        battle: &mut Battle,
+        actions: Vec<ChosenAction>,
+    ) -> Result<Vec<BattleEvent>, BattleError> {
+        engine.execute_turn_with_rng(battle, actions, &mut ScriptedRng::new(vec![]))
+    }
+
+    fn use_move(side: usize, move_index: usize) -> ChosenAction {
+        ChosenAction::new(
+            SlotPosition::new(side, 0),
+            BattleAction::UseMove {
+                move_index,
+                target: None,
+                terastallize: false,
+            },
+        )
+    }
+
+    /// ダブルバトルのテストで両サイドが覚えている技
+    fn doubles_moves() -> Vec<Move> {
+        vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move("surf", "water", "special", Some(90), "all-other-pokemon"),
+            test_move("ally-switch", "psychic", "status", None, "user"),
+            test_move("helping-hand", "normal", "status", None, "all-allies"),
+        ]
+    }
+
+    #[test]
+    fn test_execute_doubles_turn() {
+        let mut battle = test_doubles_battle(&doubles_moves());
+        let engine = BattleEngine::new(TypeChart::default());
+
+        let actions = vec![
+            ChosenAction::new(
+                SlotPosition::new(0, 0),
+                BattleAction::UseMove {
+                    move_index: 1,
+                    target: None,
+                    terastallize: false,
+                },
+            ),
+            ChosenAction::new(
+                SlotPosition::new(1, 0),
+                BattleAction::UseMove {
+                    move_index: 0,
+                    target: Some(SlotPosition::new(0, 1)),
+                    terastallize: false,
+                },
+            ),
+        ];
+        execute(&engine, &mut battle, actions).unwrap();
+
+        assert_eq!(battle.turn, 1);
+        // 全体技は0.75倍（31ダメージ）で味方にも当たる
+        assert_eq!(
+            battle
+                .pokemon_at(SlotPosition::new(1, 1))
+                .unwrap()
+                .current_hp,
+            69
+        );
+        // 単体技は通常どおり（28ダメージ）、0-1 は全体技も受けている
+        assert_eq!(
+            battle
+                .pokemon_at(SlotPosition::new(0, 1))
+                .unwrap()
+                .current_hp,
+            41
+        );
+    }
+
+    #[test]
+    fn test_helping_hand_boosts_ally() {
+        let mut battle = test_doubles_battle(&doubles_moves());
+        let engine = BattleEngine::new(TypeChart::default());
+        battle
+            .pokemon_at_mut(SlotPosition::new(0, 1))
+            .unwrap()
+            .speed = 200;
+
+        let actions = vec![
+            ChosenAction::new(
+                SlotPosition::new(0, 0),
+                BattleAction::UseMove {
+                    move_index: 0,
+                    target: Some(SlotPosition::new(1, 0)),
+                    terastallize: false,
+                },
+            ),
+            ChosenAction::new(
+                SlotPosition::new(0, 1),
+                BattleAction::UseMove {
+                    move_index: 3,
+                    target: None,
+                    terastallize: false,
+                },
+            ),
+        ];
+        execute(&engine, &mut battle, actions).unwrap();
+
+        // 威力60のたいあたり相当（42ダメージ）
+        assert_eq!(
+            battle
+                .pokemon_at(SlotPosition::new(1, 0))
+                .unwrap()
+                .current_hp,
+            58
+        );
+    }
+
+    #[test]
+    fn test_ally_switch_swaps_positions() {
+        let mut battle = test_doubles_battle(&doubles_moves());
+        let engine = BattleEngine::new(TypeChart::default());
+        let actions = vec![ChosenAction::new(
+            SlotPosition::new(0, 0),
+            BattleAction::UseMove {
+                move_index: 2,
+                target: None,
+                terastallize: false,
+            },
+        )];
+
+        execute(&engine, &mut battle, actions).unwrap();
+        assert_eq!(battle.sides[0].active, vec![Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_faint_and_replacement() {
+        let mut battle = create_singles_battle(2);
+        let engine = BattleEngine::new(TypeChart::default());
+        battle.sides[0].party[0].current_hp = 1;
+
+        execute(&engine, &mut battle, vec![use_move(1, 0), use_move(0, 0)]).unwrap();
+        assert!(!battle.is_over());
+        assert_eq!(
+            execute(&engine, &mut battle, vec![use_move(1, 0)]),
+            Err(BattleError::ReplacementPending)
+        );
+
+        let events = engine
+            .submit_replacements(&mut battle, vec![(SlotPosition::new(0, 0), 1)])
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![BattleEvent::Switched {
+                position: SlotPosition::new(0, 0),
+                pokemon: "a".to_string(),
+                party_index: 1,
+            }]
+        );
+        assert_eq!(battle.sides[0].active, vec![Some(1)]);
+    }
+
+    #[test]
+    fn test_win_when_last_pokemon_faints() {
+        let mut battle = create_singles_battle(1);
+        let engine = BattleEngine::new(TypeChart::default());
+        battle.sides[1].party[0].current_hp = 1;
+
+        execute(&engine, &mut battle, vec![use_move(0, 0), use_move(1, 0)]).unwrap();
+        assert_eq!(battle.result, Some(BattleResult::Win(0)));
+        assert_eq!(
+            execute(&engine, &mut battle, vec![use_move(0, 0)]),
+            Err(BattleError::BattleFinished)
+        );
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_battle() {
+        let engine = BattleEngine::new(TypeChart::default());
+        let run = |seed: u64| {
+            let mut battle = create_singles_battle(1).with_seed(seed);
+            for _ in 0..3 {
+                engine
+                    .execute_turn(&mut battle, vec![use_move(0, 0), use_move(1, 0)])
+                    .unwrap();
+            }
+            (
+                battle.sides[0].party[0].current_hp,
+                battle.sides[1].party[0].current_hp,
+                battle.rng,
+            )
+        };
+
+        assert_eq!(run(2024), run(2024));
+    }
+
+    #[test]
+    fn test_scripted_critical_hit_and_damage_roll() {
+        let mut battle = create_singles_battle(1);
+        let engine = BattleEngine::new(TypeChart::default());
+
+        // 命中判定 0、急所判定 0（急所）、乱数 0（85%）
+        engine
+            .execute_turn_with_rng(
+                &mut battle,
+                vec![use_move(0, 0)],
+                &mut ScriptedRng::new(vec![0, 0, 0]),
+            )
+            .unwrap();
+        // 19 × 1.5 = 28 → 85% で 23 → タイプ一致で 34
+        assert_eq!(battle.sides[1].party[0].current_hp, 66);
+    }
+
+    #[test]
+    fn test_turn_emits_events_in_order() {
+        let mut battle = create_singles_battle(1);
+        let engine = BattleEngine::new(TypeChart::default());
+
+        let events = execute(&engine, &mut battle, vec![use_move(0, 0), use_move(1, 0)]).unwrap();
+        assert_eq!(events[0], BattleEvent::TurnStarted { turn: 1 });
+        assert_eq!(
+            events[1],
+            BattleEvent::MoveUsed {
+                user: SlotPosition::new(0, 0),
+                pokemon: "a".to_string(),
+                move_name: "tackle".to_string(),
+            }
+        );
+        assert!(matches!(
+            events[2],
+            BattleEvent::Damage {
+                source: DamageSource::Move,
+                ..
+            }
+        ));
+        assert_eq!(events.len(), 5);
+    }
+
+    #[test]
+    fn test_weather_move_emits_weather_started() {
+        let moves = vec![test_move(
+            "rain-dance",
+            "water",
+            "status",
+            None,
+            "entire-battlefield",
+        )];
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["water"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["water"], &moves)],
+                1,
+            ),
+        );
+        let engine = BattleEngine::new(TypeChart::default());
+
+        let events = engine
+            .execute_turn(&mut battle, vec![use_move(0, 0)])
+            .unwrap();
+        assert_eq!(battle.field.weather, Some(Weather::Rain));
+        assert!(events.contains(&BattleEvent::WeatherStarted {
+            weather: Weather::Rain
+        }));
+    }
+
+    #[test]
+    fn test_choice_lock_rejects_other_move_and_struggle() {
+        let mut battle = create_singles_battle(1);
+        battle.sides[0].party[0].held_item = Some("choice-band".to_string());
+        let engine = BattleEngine::new(TypeChart::default());
+
+        execute(&engine, &mut battle, vec![use_move(0, 0), use_move(1, 0)]).unwrap();
+        assert_eq!(
+            execute(&engine, &mut battle, vec![use_move(0, 1), use_move(1, 0)]),
+            Err(BattleError::IllegalAction {
+                user: SlotPosition::new(0, 0),
+                reason: IllegalActionReason::ChoiceLocked,
+            })
+        );
+
+        // PPが尽きると「わるあがき」しか選べず、反動を受ける
+        for move_slot in &mut battle.sides[0].party[0].moves {
+            move_slot.current_pp = 0;
+        }
+        let struggle = ChosenAction::new(SlotPosition::new(0, 0), BattleAction::Struggle);
+        let events = execute(&engine, &mut battle, vec![struggle, use_move(1, 0)]).unwrap();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            BattleEvent::Damage {
+                target: SlotPosition { side: 0, slot: 0 },
+                source: DamageSource::Recoil,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_leftovers_heal_and_reveal_item() {
+        let mut battle = create_singles_battle(1);
+        battle.sides[1].party[0].held_item = Some("leftovers".to_string());
+        let engine = BattleEngine::new(TypeChart::default());
+
+        let events = execute(&engine, &mut battle, vec![use_move(0, 0), use_move(1, 0)]).unwrap();
+        let opponent = SlotPosition::new(1, 0);
+        assert!(events.contains(&BattleEvent::ItemActivated {
+            position: opponent,
+            item: "leftovers".to_string(),
+        }));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            BattleEvent::Healed { target, .. } if *target == opponent
+        )));
+        assert!(battle.sides[1].party[0].revealed.item);
+    }
+
+    #[test]
+    fn test_sleep_clause_blocks_second_sleeper() {
+        let mut battle = create_singles_battle(1).with_rules(BattleRules {
+            sleep_clause: true,
+            ..Default::default()
+        });
+        let mut spore = MoveSlot::new(&test_move(
+            "spore",
+            "grass",
+            "status",
+            None,
+            "selected-pokemon",
+        ));
+        spore.meta.ailment = "sleep".to_string();
+        battle.sides[0].party[0].moves[0] = spore;
+        let mut sleeper = battle.sides[1].party[0].clone();
+        sleeper.set_status(StatusCondition::Sleep);
+        battle.sides[1].party.push(sleeper);
+        let engine = BattleEngine::new(TypeChart::default());
+
+        let events = execute(&engine, &mut battle, vec![use_move(0, 0), use_move(1, 0)]).unwrap();
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, BattleEvent::StatusApplied { .. }))
+        );
+        assert_eq!(battle.sides[1].party[0].status, None);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_side::BattleSide;
+use crate::domain::service::turn_order_resolver::TurnOrderResolver;
+
+/// 決着がついた局面の評価値（勝ちは +、負けは -）
+pub const WIN_SCORE: f64 = 1.0;
+
+/// 探索の末端で局面の有利不利を数値にする評価関数。
+/// 値は side から見たもので、決着していない局面は -WIN_SCORE〜WIN_SCORE の範囲に収める
+pub trait BattleEvaluator {
+    fn evaluate(&self, battle: &Battle, side: usize) -> f64;
+}
+
+/// 残りHPの割合・残りの頭数・すばやさの上を取れているかを重み付けして合計する評価関数。
+/// 重みの合計が 1.0 を超えないように設定する。
+/// ステルスロックなどの設置技はバトルエンジンが扱わないため評価に含めない
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeuristicEvaluator {
+    pub hp_weight: f64,
+    pub remaining_weight: f64,
+    pub speed_weight: f64,
+}
+
+impl Default for HeuristicEvaluator {
+    fn default() -> Self {
+        Self {
+            hp_weight: 0.6,
+            remaining_weight: 0.3,
+            speed_weight: 0.1,
+        }
+    }
+}
+
+impl HeuristicEvaluator {
+    /// パーティ全体の残りHPの割合の平均（0.0〜1.0）
+    fn hp_ratio(side: &BattleSide) -> f64 {
+        if side.party.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = side
+            .party
+            .iter()
+            .map(|pokemon| pokemon.current_hp as f64 / pokemon.max_hp.max(1) as f64)
+            .sum();
+        total / side.party.len() as f64
+    }
+
+    /// ひんしでないポケモンの割合（0.0〜1.0）
+    fn remaining_ratio(side: &BattleSide) -> f64 {
+        if side.party.is_empty() {
+            return 0.0;
+        }
+        let remaining = side
+            .party
+            .iter()
+            .filter(|pokemon| !pokemon.is_fainted())
+            .count();
+        remaining as f64 / side.party.len() as f64
+    }
+
+    /// 場のポケモン同士の組ごとに、先に動ける組と動かれる組の差（-1.0〜1.0）
+    fn speed_control(battle: &Battle, side: usize) -> f64 {
+        let speeds = |side: usize| -> Vec<u32> {
+            battle
+                .active_positions()
+                .into_iter()
+                .filter(|position| position.side == side)
+                .filter_map(|position| battle.pokemon_at(position))
+                .map(TurnOrderResolver::effective_speed)
+                .collect()
+        };
+        let own = speeds(side);
+        let opponent = speeds(1 - side);
+        let pairs = own.len() * opponent.len();
+        if pairs == 0 {
+            return 0.0;
+        }
+        let balance: i32 = own
+            .iter()
+            .flat_map(|own| {
+                opponent
+                    .iter()
+                    .map(move |opponent| own.cmp(opponent) as i32)
+            })
+            .sum();
+        balance as f64 / pairs as f64
+    }
+}
+
+impl BattleEvaluator for HeuristicEvaluator {
+    fn evaluate(&self, battle: &Battle, side: usize) -> f64 {
+        if let Some(result) = battle.result {
+            return match result.winner() {
+                Some(winner) if winner == side => WIN_SCORE,
+                Some(_) => -WIN_SCORE,
+                None => 0.0,
+            };
+        }
+        let own = &battle.sides[side];
+        let opponent = &battle.sides[1 - side];
+        self.hp_weight * (Self::hp_ratio(own) - Self::hp_ratio(opponent))
+            + self.remaining_weight * (Self::remaining_ratio(own) - Self::remaining_ratio(opponent))
+            + self.speed_weight * Self::speed_control(battle, side)
+    }
+}
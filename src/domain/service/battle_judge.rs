@@ -0,0 +1,102 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_result::BattleResult;
+
+/// 両サイドの最後のポケモンが同時にひんしになった場合の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaintTiebreak {
+    /// 天候・状態異常のダメージなどによる同時ひんしは引き分け
+    Draw,
+    /// だいばくはつ等の自滅技を使った側の負け
+    SelfKnockOut { user_side: usize },
+    /// 反動ダメージでひんしになった側の勝ち
+    Recoil { user_side: usize },
+}
+
+/// 勝敗の判定
+pub struct BattleJudge;
+
+impl BattleJudge {
+    /// ひんしの処理後に決着しているかを判定する
+    pub fn judge(battle: &Battle, tiebreak: FaintTiebreak) -> Option<BattleResult> {
+        let remaining: Vec<bool> = battle
+            .sides
+            .iter()
+            .map(|side| side.has_remaining_pokemon())
+            .collect();
+
+        match remaining[..] {
+            [true, true] => None,
+            [true, false] => Some(BattleResult::Win(0)),
+            [false, true] => Some(BattleResult::Win(1)),
+            _ => Some(match tiebreak {
+                FaintTiebreak::Draw => BattleResult::Draw,
+                FaintTiebreak::SelfKnockOut { user_side } => BattleResult::Win(1 - user_side),
+                FaintTiebreak::Recoil { user_side } => BattleResult::Win(user_side),
+            }),
+        }
+    }
+
+    /// ターン上限に達した場合の判定。残りのポケモンの数、HPの割合の順に比べ、同じなら引き分け
+    pub fn judge_by_turn_limit(battle: &Battle) -> BattleResult {
+        let scores: Vec<(usize, u64)> = battle
+            .sides
+            .iter()
+            .map(|side| {
+                let remaining = side
+                    .party
+                    .iter()
+                    .filter(|pokemon| !pokemon.is_fainted())
+                    .count();
+                // 比較を整数で行うため、HP割合を百万分率で合計する
+                let hp_ratio = side
+                    .party
+                    .iter()
+                    .map(|pokemon| {
+                        pokemon.current_hp as u64 * 1_000_000 / pokemon.max_hp.max(1) as u64
+                    })
+                    .sum();
+                (remaining, hp_ratio)
+            })
+            .collect();
+
+        match scores[0].cmp(&scores[1]) {
+            std::cmp::Ordering::Greater => BattleResult::Win(0),
+            std::cmp::Ordering::Less => BattleResult::Win(1),
+            std::cmp::Ordering::Equal => BattleResult::Draw,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::test_support::test_active_pokemon;
+
+    fn create_singles_battle() -> Battle {
+        Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &[])],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &[])],
+                1,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_judge_by_turn_limit() {
+        let mut battle = create_singles_battle();
+        battle.sides[1].party[0].take_damage(10);
+        assert_eq!(
+            BattleJudge::judge_by_turn_limit(&battle),
+            BattleResult::Win(0)
+        );
+    }
+}
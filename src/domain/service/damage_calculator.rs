@@ -0,0 +1,297 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::field::{Terrain, Weather};
+use crate::domain::model::r#move::DamageClass;
+use crate::domain::model::move_slot::MoveSlot;
+use crate::domain::model::stats::StatName;
+use crate::domain::model::status::StatusCondition;
+use crate::domain::service::type_chart::TypeChart;
+
+/// 補正値の基準（4096 = 1.0倍）
+pub const MODIFIER_BASE: u32 = 4096;
+/// ダブルバトルで複数を対象にとる技の威力補正（0.75倍）
+pub const SPREAD_MODIFIER: u32 = 3072;
+const CRITICAL_MODIFIER: u32 = 6144;
+const STAB_MODIFIER: u32 = 6144;
+/// 元のタイプと同じテラスタイプの技は2倍
+const TERA_STAB_MODIFIER: u32 = 8192;
+const BURN_MODIFIER: u32 = 2048;
+const BOOST_MODIFIER: u32 = 6144;
+const HALF_MODIFIER: u32 = 2048;
+const TERRAIN_MODIFIER: u32 = 5325;
+
+/// ダメージ計算時の状況
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamageContext {
+    /// 技の対象が複数いるか（ダブルバトルの全体技）
+    pub is_spread: bool,
+    pub is_critical: bool,
+    /// 乱数（85〜100）
+    pub random_roll: u32,
+    /// 技の威力にかかる補正（てだすけ等）
+    pub base_power_modifier: u32,
+    /// 最終ダメージにかかる補正（フレンドガード等）
+    pub final_modifier: u32,
+    pub weather: Option<Weather>,
+    pub terrain: Option<Terrain>,
+}
+
+impl Default for DamageContext {
+    fn default() -> Self {
+        Self {
+            is_spread: false,
+            is_critical: false,
+            random_roll: 100,
+            base_power_modifier: MODIFIER_BASE,
+            final_modifier: MODIFIER_BASE,
+            weather: None,
+            terrain: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageResult {
+    pub damage: u32,
+    pub effectiveness: f64,
+}
+
+/// 第9世代のダメージ計算式。端数処理は整数演算で行い、環境によらず同じ結果になる
+pub struct DamageCalculator;
+
+impl DamageCalculator {
+    pub fn calculate(
+        attacker: &ActivePokemon,
+        defender: &ActivePokemon,
+        move_slot: &MoveSlot,
+        type_chart: &TypeChart,
+        context: &DamageContext,
+    ) -> DamageResult {
+        let effectiveness =
+            type_chart.effectiveness(&move_slot.type_name, &defender.current_types());
+        let power = match (&move_slot.damage_class, move_slot.power) {
+            (DamageClass::Status, _) | (_, None) => {
+                return DamageResult {
+                    damage: 0,
+                    effectiveness,
+                };
+            }
+            (_, Some(power)) => {
+                let modifier = chain_modifiers(
+                    context.base_power_modifier,
+                    Self::terrain_modifier(attacker, defender, move_slot, context.terrain),
+                );
+                apply_modifier(power, modifier).max(1)
+            }
+        };
+        if effectiveness == 0.0 {
+            return DamageResult {
+                damage: 0,
+                effectiveness,
+            };
+        }
+
+        let (attack_stat, defense_stat) = if move_slot.damage_class.is_physical() {
+            (StatName::Attack, StatName::Defense)
+        } else {
+            (StatName::SpecialAttack, StatName::SpecialDefense)
+        };
+        let attack = Self::attack_value(attacker, attack_stat, context.is_critical);
+        let mut defense = Self::defense_value(defender, defense_stat, context.is_critical);
+        let weather_defense_boost = match context.weather {
+            Some(Weather::Sandstorm) => {
+                defender.has_type("rock") && move_slot.damage_class.is_special()
+            }
+            Some(Weather::Snow) => defender.has_type("ice") && move_slot.damage_class.is_physical(),
+            _ => false,
+        };
+        if weather_defense_boost {
+            defense = apply_modifier(defense, BOOST_MODIFIER);
+        }
+
+        let level_factor = 2 * attacker.level / 5 + 2;
+        let mut damage = (level_factor as u64 * power as u64 * attack as u64
+            / defense.max(1) as u64
+            / 50) as u32
+            + 2;
+
+        if context.is_spread {
+            damage = apply_modifier(damage, SPREAD_MODIFIER);
+        }
+        match (context.weather, move_slot.type_name.as_str()) {
+            (Some(Weather::Sun), "fire") | (Some(Weather::Rain), "water") => {
+                damage = apply_modifier(damage, BOOST_MODIFIER);
+            }
+            (Some(Weather::Sun), "water") | (Some(Weather::Rain), "fire") => {
+                damage = apply_modifier(damage, HALF_MODIFIER);
+            }
+            _ => {}
+        }
+        if context.is_critical {
+            damage = apply_modifier(damage, CRITICAL_MODIFIER);
+        }
+        damage = damage * context.random_roll / 100;
+        if let Some(stab) = Self::stab_modifier(attacker, move_slot) {
+            damage = apply_modifier(damage, stab);
+        }
+        damage = (damage as f64 * effectiveness).floor() as u32;
+        if move_slot.damage_class.is_physical() && attacker.status == Some(StatusCondition::Burn) {
+            damage = apply_modifier(damage, BURN_MODIFIER);
+        }
+        damage = apply_modifier(damage, context.final_modifier);
+
+        DamageResult {
+            damage: damage.max(1),
+            effectiveness,
+        }
+    }
+
+    /// タイプ一致補正。テラスタル中は元のタイプとテラスタイプの両方が一致の対象になる
+    fn stab_modifier(attacker: &ActivePokemon, move_slot: &MoveSlot) -> Option<u32> {
+        let original = attacker.types.iter().any(|t| t.name == move_slot.type_name);
+        let tera = attacker.terastallized
+            && attacker
+                .tera_type
+                .as_ref()
+                .is_some_and(|t| t.name == move_slot.type_name);
+        match (original, tera) {
+            (true, true) => Some(TERA_STAB_MODIFIER),
+            (true, false) | (false, true) => Some(STAB_MODIFIER),
+            (false, false) => None,
+        }
+    }
+
+    /// 地面に接しているポケモンへのフィールドの威力補正
+    fn terrain_modifier(
+        attacker: &ActivePokemon,
+        defender: &ActivePokemon,
+        move_slot: &MoveSlot,
+        terrain: Option<Terrain>,
+    ) -> u32 {
+        match (terrain, move_slot.type_name.as_str()) {
+            (Some(Terrain::Electric), "electric")
+            | (Some(Terrain::Grassy), "grass")
+            | (Some(Terrain::Psychic), "psychic")
+                if attacker.is_grounded() =>
+            {
+                TERRAIN_MODIFIER
+            }
+            (Some(Terrain::Misty), "dragon") if defender.is_grounded() => HALF_MODIFIER,
+            _ => MODIFIER_BASE,
+        }
+    }
+
+    /// 急所時は攻撃側の下降ランクを無視する
+    fn attack_value(attacker: &ActivePokemon, stat: StatName, is_critical: bool) -> u32 {
+        let effective = attacker.effective_stat(stat.clone());
+        if is_critical {
+            effective.max(raw_stat(attacker, stat))
+        } else {
+            effective
+        }
+    }
+
+    /// 急所時は防御側の上昇ランクを無視する
+    fn defense_value(defender: &ActivePokemon, stat: StatName, is_critical: bool) -> u32 {
+        let effective = defender.effective_stat(stat.clone());
+        if is_critical {
+            effective.min(raw_stat(defender, stat))
+        } else {
+            effective
+        }
+    }
+}
+
+fn raw_stat(pokemon: &ActivePokemon, stat: StatName) -> u32 {
+    match stat {
+        StatName::Hp => pokemon.max_hp,
+        StatName::Attack => pokemon.attack,
+        StatName::Defense => pokemon.defense,
+        StatName::SpecialAttack => pokemon.special_attack,
+        StatName::SpecialDefense => pokemon.special_defense,
+        StatName::Speed => pokemon.speed,
+    }
+}
+
+/// 4096基準の補正を適用する（五捨五超入）
+pub fn apply_modifier(value: u32, modifier: u32) -> u32 {
+    ((value as u64 * modifier as u64 + (MODIFIER_BASE as u64 / 2 - 1)) / MODIFIER_BASE as u64)
+        as u32
+}
+
+/// 補正同士を掛け合わせる（4096基準、四捨五入）
+pub fn chain_modifiers(first: u32, second: u32) -> u32 {
+    ((first as u64 * second as u64 + MODIFIER_BASE as u64 / 2) / MODIFIER_BASE as u64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    #[test]
+    fn test_calculate_damage() {
+        let tackle = test_move("tackle", "normal", "physical", Some(40), "selected-pokemon");
+        let attacker = test_active_pokemon("attacker", &["normal"], &[tackle]);
+        let defender = test_active_pokemon("defender", &["water"], &[]);
+
+        let result = DamageCalculator::calculate(
+            &attacker,
+            &defender,
+            &attacker.moves[0],
+            &TypeChart::default(),
+            &DamageContext::default(),
+        );
+
+        // ((22 * 40 * 100 / 100) / 50 + 2) = 19, STAB 1.5倍で 28
+        assert_eq!(result.damage, 28);
+        assert_eq!(result.effectiveness, 1.0);
+    }
+
+    #[test]
+    fn test_spread_damage_is_reduced() {
+        let surf = test_move("surf", "water", "special", Some(90), "all-other-pokemon");
+        let attacker = test_active_pokemon("attacker", &["normal"], &[surf]);
+        let defender = test_active_pokemon("defender", &["normal"], &[]);
+        let chart = TypeChart::default();
+
+        let single = DamageCalculator::calculate(
+            &attacker,
+            &defender,
+            &attacker.moves[0],
+            &chart,
+            &DamageContext::default(),
+        );
+        let spread = DamageCalculator::calculate(
+            &attacker,
+            &defender,
+            &attacker.moves[0],
+            &chart,
+            &DamageContext {
+                is_spread: true,
+                ..DamageContext::default()
+            },
+        );
+
+        assert_eq!(single.damage, 41);
+        assert_eq!(spread.damage, 31);
+    }
+
+    #[test]
+    fn test_rain_boosts_water_move() {
+        let surf = test_move("surf", "water", "special", Some(90), "all-other-pokemon");
+        let attacker = test_active_pokemon("attacker", &["normal"], &[surf]);
+        let defender = test_active_pokemon("defender", &["normal"], &[]);
+
+        let result = DamageCalculator::calculate(
+            &attacker,
+            &defender,
+            &attacker.moves[0],
+            &TypeChart::default(),
+            &DamageContext {
+                weather: Some(Weather::Rain),
+                ..DamageContext::default()
+            },
+        );
+        assert_eq!(result.damage, 61);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::domain::model::ability::Ability;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_rng::{BattleRng, SeededRng};
+use crate::domain::model::item::Item;
+use crate::domain::model::r#move::Move;
+use crate::domain::model::move_slot::MoveSlot;
+use crate::domain::model::player_view::{PlayerView, UNKNOWN_ABILITY};
+use crate::domain::model::pokemon::Pokemon;
+use crate::domain::model::pokemon_build::MAX_MOVES;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 見えていない持ち物を抽選する候補（対戦でよく使われ、技や特性を問わないもの）
+const CANDIDATE_ITEMS: [&str; 14] = [
+    "leftovers",
+    "life-orb",
+    "focus-sash",
+    "choice-band",
+    "choice-specs",
+    "choice-scarf",
+    "assault-vest",
+    "sitrus-berry",
+    "lum-berry",
+    "rocky-helmet",
+    "expert-belt",
+    "clear-amulet",
+    "covert-cloak",
+    "safety-goggles",
+];
+
+/// 相手の見えていない情報（型・今後の乱数）を、ありうる内容で埋めたバトルを作る。
+/// 不完全情報の探索では、埋め方を変えた複数のバトルで試行して結果をまとめる
+pub trait Determinizer {
+    fn determinize(&self, view: &PlayerView, rng: &mut SeededRng) -> Battle;
+}
+
+/// 今後の乱数だけを引き直し、相手の型は見えているものをそのまま使う
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomFutureDeterminizer;
+
+impl Determinizer for RandomFutureDeterminizer {
+    fn determinize(&self, view: &PlayerView, rng: &mut SeededRng) -> Battle {
+        let mut battle = view.battle.clone();
+        battle.rng = SeededRng::new(next_seed(rng));
+        battle
+    }
+}
+
+/// 1つのポケモンが覚えられる技と、持ちうる特性
+#[derive(Debug, Clone, Default)]
+struct Learnset {
+    moves: Vec<MoveSlot>,
+    abilities: Vec<Arc<Ability>>,
+}
+
+/// 図鑑データの覚える技・特性から、相手の見えていない技・特性・持ち物を抽選する。
+/// 見えている技はそのまま残し、残りの枠を覚えられる技から重複なく埋める
+#[derive(Debug, Clone, Default)]
+pub struct LearnsetDeterminizer {
+    learnsets: HashMap<String, Learnset>,
+    items: Vec<String>,
+}
+
+impl LearnsetDeterminizer {
+    pub fn new(
+        pokemons: &[Pokemon],
+        moves: &[Move],
+        abilities: &[Ability],
+        items: &[Item],
+    ) -> Self {
+        let moves_by_name: HashMap<&str, &Move> = moves
+            .iter()
+            .map(|move_data| (move_data.name.as_str(), move_data))
+            .collect();
+        let abilities_by_name: HashMap<&str, Arc<Ability>> = abilities
+            .iter()
+            .map(|ability| (ability.name.as_str(), Arc::new(ability.clone())))
+            .collect();
+        let learnsets = pokemons
+            .iter()
+            .map(|pokemon| {
+                let learnset = Learnset {
+                    moves: pokemon
+                        .moves
+                        .iter()
+                        .filter(|learnable| pokemon.can_learn(&learnable.r#move.name))
+                        .filter_map(|learnable| moves_by_name.get(learnable.r#move.name.as_str()))
+                        .map(|move_data| MoveSlot::new(move_data))
+                        .collect(),
+                    abilities: pokemon
+                        .abilities
+                        .iter()
+                        .filter_map(|ability| abilities_by_name.get(ability.ability.name.as_str()))
+                        .cloned()
+                        .collect(),
+                };
+                (pokemon.name.clone(), learnset)
+            })
+            .collect();
+        Self {
+            learnsets,
+            items: CANDIDATE_ITEMS
+                .iter()
+                .filter(|name| items.iter().any(|item| item.name == **name))
+                .map(|name| name.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Determinizer for LearnsetDeterminizer {
+    fn determinize(&self, view: &PlayerView, rng: &mut SeededRng) -> Battle {
+        let mut battle = RandomFutureDeterminizer.determinize(view, rng);
+        let mut items = self.items.clone();
+        let opponent = &mut battle.sides[view.opponent_side()];
+        // 見えている持ち物とは重ならないように抽選する
+        items.retain(|item| !opponent.party.iter().any(|pokemon| pokemon.has_item(item)));
+        for pokemon in &mut opponent.party {
+            if !pokemon.revealed.item && !items.is_empty() {
+                let item = items.swap_remove(rng.next_below(items.len() as u32) as usize);
+                pokemon.held_item = Some(item);
+            }
+            let Some(learnset) = self.learnsets.get(&pokemon.name) else {
+                continue;
+            };
+            if pokemon.active_ability.name == UNKNOWN_ABILITY && !learnset.abilities.is_empty() {
+                let index = rng.next_below(learnset.abilities.len() as u32) as usize;
+                pokemon.active_ability = learnset.abilities[index].clone();
+            }
+            let mut candidates: Vec<&MoveSlot> = learnset
+                .moves
+                .iter()
+                .filter(|candidate| {
+                    !pokemon
+                        .moves
+                        .iter()
+                        .any(|known| known.name == candidate.name)
+                })
+                .collect();
+            while pokemon.moves.len() < MAX_MOVES && !candidates.is_empty() {
+                let index = rng.next_below(candidates.len() as u32) as usize;
+                pokemon.moves.push(candidates.swap_remove(index).clone());
+            }
+        }
+        battle
+    }
+}
+
+fn next_seed(rng: &mut SeededRng) -> u64 {
+    ((rng.next_u32() as u64) << 32) | rng.next_u32() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::test_support::{
+        test_ability, test_active_pokemon, test_item, test_learnset, test_move, test_pokemon,
+    };
+
+    #[test]
+    fn test_learnset_fills_hidden_moves_and_ability() {
+        let moves: Vec<Move> = ["tackle", "surf", "ice-beam", "protect", "scald"]
+            .iter()
+            .map(|name| test_move(name, "water", "special", Some(80), "selected-pokemon"))
+            .collect();
+        let mut vaporeon = test_pokemon("vaporeon", &["water"], 130, 65);
+        let move_names: Vec<&str> = moves.iter().map(|m| m.name.as_str()).collect();
+        test_learnset(&mut vaporeon, "water-absorb", &move_names);
+        let mut opponent = test_active_pokemon("vaporeon", &["water"], &moves[1..2]);
+        opponent.revealed.reveal_move(0);
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves[..1])],
+                1,
+            ),
+            BattleSide::new("opponent".to_string(), vec![opponent], 1),
+        );
+        let determinizer = LearnsetDeterminizer::new(
+            &[vaporeon],
+            &moves,
+            &[test_ability("water-absorb")],
+            &[test_item("leftovers")],
+        );
+
+        let sampled =
+            determinizer.determinize(&PlayerView::new(&battle, 0), &mut SeededRng::new(3));
+        let pokemon = &sampled.sides[1].party[0];
+        assert_eq!(pokemon.moves.len(), MAX_MOVES);
+        assert_eq!(pokemon.moves[0].name, "surf");
+        assert!(pokemon.moves[1..].iter().all(|slot| slot.name != "surf"));
+        assert!(pokemon.has_ability("water-absorb"));
+        assert!(pokemon.has_item("leftovers"));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::ChosenAction;
+use crate::domain::model::battle_rng::ChanceRng;
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::service::battle_agent::{BattleAgent, first_replacements, joint_actions};
+use crate::domain::service::battle_engine::BattleEngine;
+use crate::domain::service::battle_evaluator::BattleEvaluator;
+use crate::domain::service::legal_actions::{LegalActions, SlotOptions};
+use std::time::{Duration, Instant};
+
+/// 決着までの残りの深さ1つあたりに、決着した局面の評価値へ上乗せする割合
+const EARLY_FINISH_BONUS: f64 = 0.01;
+
+/// 探索の深さと打ち切り条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchConfig {
+    /// 先読みするターン数
+    pub depth: u32,
+    /// シミュレーションするターン数の上限
+    pub max_nodes: Option<u64>,
+    /// 1回の行動選択にかけてよい時間
+    pub time_limit: Option<Duration>,
+    /// これより起こりにくい乱数の分岐は最も起こりやすい結果にまとめる
+    pub min_probability: f64,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            depth: 2,
+            max_nodes: Some(20_000),
+            time_limit: None,
+            min_probability: 0.05,
+        }
+    }
+}
+
+/// 同時手番のターンを、自分の行動（最大化）・相手の行動（最小化）・乱数（期待値）の順に展開して
+/// 先読みするエージェント。深さ1から順に探索し、打ち切られた場合は最後に探索し終えた深さの結果を使う。
+/// ひんし後の交代は探索せず、控えをパーティの順に繰り出すものとして扱う
+#[derive(Debug, Clone)]
+pub struct ExpectiminimaxAgent<E: BattleEvaluator> {
+    engine: BattleEngine,
+    evaluator: E,
+    config: SearchConfig,
+}
+
+impl<E: BattleEvaluator> ExpectiminimaxAgent<E> {
+    pub fn new(engine: BattleEngine, evaluator: E, config: SearchConfig) -> Self {
+        Self {
+            engine,
+            evaluator,
+            config,
+        }
+    }
+}
+
+/// 1回の行動選択の探索中に持ち回る状態
+struct Search<'a, E: BattleEvaluator> {
+    agent: &'a ExpectiminimaxAgent<E>,
+    side: usize,
+    nodes: u64,
+    deadline: Option<Instant>,
+}
+
+impl<E: BattleEvaluator> Search<'_, E> {
+    fn exhausted(&self) -> bool {
+        self.agent
+            .config
+            .max_nodes
+            .is_some_and(|max_nodes| self.nodes >= max_nodes)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    fn joint_actions_of(battle: &Battle, side: usize) -> Vec<Vec<ChosenAction>> {
+        joint_actions(&LegalActions::for_side(battle, side))
+    }
+
+    /// 自分の行動の組ごとに、相手が最善の応手をとった場合の評価値。打ち切られた場合は None
+    fn max_node(
+        &mut self,
+        battle: &Battle,
+        own_joints: &[Vec<ChosenAction>],
+        depth: u32,
+    ) -> Option<(f64, usize)> {
+        let opponent_joints = Self::joint_actions_of(battle, 1 - self.side);
+        let mut best: Option<(f64, usize)> = None;
+        for (index, own) in own_joints.iter().enumerate() {
+            let mut worst = f64::INFINITY;
+            for opponent in &opponent_joints {
+                let value = self.chance_node(battle, own, opponent, depth)?;
+                if value.is_nan() {
+                    continue;
+                }
+                worst = worst.min(value);
+                // これ以上悪くなる応手があれば、すでに見つけた行動を上回らない
+                if best.is_some_and(|(best_value, _)| worst <= best_value) {
+                    break;
+                }
+            }
+            if worst.is_finite() && best.is_none_or(|(best_value, _)| worst > best_value) {
+                best = Some((worst, index));
+            }
+        }
+        best
+    }
+
+    /// 両者の行動を決めたあと、乱数の分岐ごとの評価値を確率で重み付けした期待値。
+    /// 行動の組がルール上選べない場合は NaN を返す
+    fn chance_node(
+        &mut self,
+        battle: &Battle,
+        own: &[ChosenAction],
+        opponent: &[ChosenAction],
+        depth: u32,
+    ) -> Option<f64> {
+        let actions: Vec<ChosenAction> = own.iter().chain(opponent).cloned().collect();
+        let mut choices = Some(Vec::new());
+        let mut total = 0.0;
+        let mut weight = 0.0;
+        while let Some(current) = choices {
+            if self.exhausted() {
+                return None;
+            }
+            self.nodes += 1;
+            let mut rng = ChanceRng::new(current, self.agent.config.min_probability);
+            let mut next = battle.clone();
+            choices =
+                match self
+                    .agent
+                    .engine
+                    .execute_turn_with_rng(&mut next, actions.clone(), &mut rng)
+                {
+                    Ok(_) => rng.next_choices(),
+                    Err(_) => return Some(f64::NAN),
+                };
+            self.fill_replacements(&mut next);
+            let value = self.value(&next, depth - 1)?;
+            total += value * rng.probability();
+            weight += rng.probability();
+        }
+        Some(total / weight)
+    }
+
+    fn value(&mut self, battle: &Battle, depth: u32) -> Option<f64> {
+        let score = self.agent.evaluator.evaluate(battle, self.side);
+        if battle.is_over() {
+            // 同じ勝ちなら早く、同じ負けなら遅く決着する展開を選ぶ
+            return Some(score * (1.0 + EARLY_FINISH_BONUS * depth as f64));
+        }
+        if depth == 0 {
+            return Some(score);
+        }
+        let own_joints = Self::joint_actions_of(battle, self.side);
+        self.max_node(battle, &own_joints, depth)
+            .map(|(value, _)| value)
+    }
+
+    fn fill_replacements(&self, battle: &mut Battle) {
+        if battle.is_over() {
+            return;
+        }
+        let choices = battle
+            .replacement_requests()
+            .iter()
+            .flat_map(first_replacements)
+            .collect::<Vec<_>>();
+        if !choices.is_empty() {
+            let _ = self.agent.engine.submit_replacements(battle, choices);
+        }
+    }
+}
+
+impl<E: BattleEvaluator> BattleAgent for ExpectiminimaxAgent<E> {
+    fn choose_actions(&mut self, view: &PlayerView, options: &[SlotOptions]) -> Vec<ChosenAction> {
+        let own_joints = joint_actions(options);
+        let mut search = Search {
+            agent: self,
+            side: view.side,
+            nodes: 0,
+            deadline: self.config.time_limit.map(|limit| Instant::now() + limit),
+        };
+        let mut best = 0;
+        for depth in 1..=self.config.depth.max(1) {
+            match search.max_node(&view.battle, &own_joints, depth) {
+                Some((_, index)) => best = index,
+                None => break,
+            }
+        }
+        own_joints.into_iter().nth(best).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_action::{BattleAction, SlotPosition};
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::battle_evaluator::HeuristicEvaluator;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    #[test]
+    fn test_search_prefers_reliable_knock_out() {
+        let moves = vec![
+            test_move("pound", "normal", "physical", Some(10), "selected-pokemon"),
+            test_move("swift", "normal", "special", Some(60), "all-opponents"),
+        ];
+        let mut player = test_active_pokemon("a", &["normal"], &moves);
+        player.speed = 200;
+        let mut opponent = test_active_pokemon("b", &["normal"], &moves);
+        opponent.current_hp = 20;
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), vec![player], 1),
+            BattleSide::new("opponent".to_string(), vec![opponent], 1),
+        );
+        let engine = BattleEngine::new(TypeChart::default());
+        let mut agent = ExpectiminimaxAgent::new(
+            engine,
+            HeuristicEvaluator::default(),
+            SearchConfig::default(),
+        );
+
+        let options = LegalActions::for_side(&battle, 0);
+        let actions = agent.choose_actions(&PlayerView::new(&battle, 0), &options);
+        assert_eq!(
+            actions,
+            vec![ChosenAction::new(
+                SlotPosition::new(0, 0),
+                BattleAction::UseMove {
+                    move_index: 1,
+                    target: None,
+                    terastallize: false,
+                }
+            )]
+        );
+    }
+}
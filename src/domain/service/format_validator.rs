@@ -0,0 +1,464 @@
+use crate::domain::model::format::{EVASION_MOVES, Format, LevelRule, OHKO_MOVES};
+use crate::domain::model::pokemon::Pokemon;
+use crate::domain::model::pokemon_build::PokemonBuild;
+use crate::domain::model::pokemon_species::PokemonSpecies;
+use crate::domain::model::team::Team;
+use std::fmt;
+
+/// パーティがルールに違反している箇所
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatViolation {
+    TooManyMembers {
+        max: usize,
+        actual: usize,
+    },
+    /// 選出する数より少ない
+    NotEnoughMembers {
+        required: usize,
+        actual: usize,
+    },
+    LevelTooHigh {
+        index: usize,
+        level: u32,
+        cap: u32,
+    },
+    BannedSpecies {
+        index: usize,
+        species: String,
+    },
+    /// 幻のポケモンは登録できない
+    Mythical {
+        index: usize,
+        species: String,
+    },
+    /// 対象の図鑑に載っていない
+    NotInPokedex {
+        index: usize,
+        species: String,
+    },
+    /// ルールの判定に必要な種族のデータが見つからない
+    UnknownSpecies {
+        index: usize,
+        species: String,
+    },
+    TooManyRestricted {
+        max: usize,
+        actual: usize,
+    },
+    /// 種族の重複（同じポケモンは1体まで）
+    DuplicateSpecies(String),
+    /// 道具の重複（同じ道具は1つまで）
+    DuplicateItem(String),
+    OhkoMove {
+        index: usize,
+        move_name: String,
+    },
+    EvasionMove {
+        index: usize,
+        move_name: String,
+    },
+}
+
+impl fmt::Display for FormatViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatViolation::TooManyMembers { max, actual } => {
+                write!(f, "登録できるのは{}体までです（{}体）", max, actual)
+            }
+            FormatViolation::NotEnoughMembers { required, actual } => {
+                write!(
+                    f,
+                    "{}体以上登録する必要があります（{}体）",
+                    required, actual
+                )
+            }
+            FormatViolation::LevelTooHigh { index, level, cap } => write!(
+                f,
+                "{}番: レベル{}は上限の{}を超えています",
+                index + 1,
+                level,
+                cap
+            ),
+            FormatViolation::BannedSpecies { index, species } => {
+                write!(f, "{}番: {}は使用できません", index + 1, species)
+            }
+            FormatViolation::Mythical { index, species } => {
+                write!(
+                    f,
+                    "{}番: 幻のポケモンは使用できません: {}",
+                    index + 1,
+                    species
+                )
+            }
+            FormatViolation::NotInPokedex { index, species } => write!(
+                f,
+                "{}番: {}は対象の図鑑に載っていません",
+                index + 1,
+                species
+            ),
+            FormatViolation::UnknownSpecies { index, species } => {
+                write!(
+                    f,
+                    "{}番: 種族のデータが見つかりません: {}",
+                    index + 1,
+                    species
+                )
+            }
+            FormatViolation::TooManyRestricted { max, actual } => {
+                write!(f, "制限のあるポケモンは{}体までです（{}体）", max, actual)
+            }
+            FormatViolation::DuplicateSpecies(species) => {
+                write!(f, "同じポケモンを2体以上登録しています: {}", species)
+            }
+            FormatViolation::DuplicateItem(item) => {
+                write!(f, "同じ道具を2つ以上持たせています: {}", item)
+            }
+            FormatViolation::OhkoMove { index, move_name } => {
+                write!(
+                    f,
+                    "{}番: 一撃必殺技は使用できません: {}",
+                    index + 1,
+                    move_name
+                )
+            }
+            FormatViolation::EvasionMove { index, move_name } => write!(
+                f,
+                "{}番: 回避率を上げる技は使用できません: {}",
+                index + 1,
+                move_name
+            ),
+        }
+    }
+}
+
+/// パーティが対戦のルールに沿っているかを確かめる
+pub struct FormatValidator;
+
+impl FormatValidator {
+    /// 違反している箇所をすべて返す。
+    /// ポケモンと種族のデータは、姿違いを種族でまとめて判定するために使う
+    pub fn validate(
+        format: &Format,
+        team: &Team,
+        pokemons: &[Pokemon],
+        species: &[PokemonSpecies],
+    ) -> Result<(), Vec<FormatViolation>> {
+        let mut violations = Vec::new();
+        let size = team.members.len();
+        if size > format.team_size {
+            violations.push(FormatViolation::TooManyMembers {
+                max: format.team_size,
+                actual: size,
+            });
+        }
+        if size < format.bring_count {
+            violations.push(FormatViolation::NotEnoughMembers {
+                required: format.bring_count,
+                actual: size,
+            });
+        }
+
+        let mut restricted = 0;
+        for (index, member) in team.members.iter().enumerate() {
+            violations.extend(Self::member_violations(
+                format, index, member, pokemons, species,
+            ));
+            if Self::is_restricted(format, member, pokemons) {
+                restricted += 1;
+            }
+            let species_name = Self::species_name(&member.species, pokemons);
+            if format.species_clause
+                && team.members[..index]
+                    .iter()
+                    .any(|other| Self::species_name(&other.species, pokemons) == species_name)
+            {
+                violations.push(FormatViolation::DuplicateSpecies(species_name.to_string()));
+            }
+            if format.item_clause
+                && let Some(item) = &member.held_item
+                && team.members[..index]
+                    .iter()
+                    .any(|other| other.held_item.as_ref() == Some(item))
+            {
+                violations.push(FormatViolation::DuplicateItem(item.clone()));
+            }
+        }
+        if restricted > format.max_restricted {
+            violations.push(FormatViolation::TooManyRestricted {
+                max: format.max_restricted,
+                actual: restricted,
+            });
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// パーティの他のポケモンに関係なく、1体だけで判定できる違反を返す
+    pub fn member_violations(
+        format: &Format,
+        index: usize,
+        member: &PokemonBuild,
+        pokemons: &[Pokemon],
+        species: &[PokemonSpecies],
+    ) -> Vec<FormatViolation> {
+        let mut violations = Vec::new();
+        if let LevelRule::Cap(cap) = format.level
+            && member.level > cap
+        {
+            violations.push(FormatViolation::LevelTooHigh {
+                index,
+                level: member.level,
+                cap,
+            });
+        }
+        if Self::is_listed(&format.banned_species, member, pokemons) {
+            violations.push(FormatViolation::BannedSpecies {
+                index,
+                species: member.species.clone(),
+            });
+        }
+        if format.requires_species_data() {
+            let species_name = Self::species_name(&member.species, pokemons);
+            match species.iter().find(|known| known.name == species_name) {
+                Some(known) => {
+                    if format.no_mythicals && known.is_mythical {
+                        violations.push(FormatViolation::Mythical {
+                            index,
+                            species: member.species.clone(),
+                        });
+                    }
+                    if !format.allowed_pokedexes.is_empty()
+                        && !format
+                            .allowed_pokedexes
+                            .iter()
+                            .any(|pokedex| known.is_in_pokedex(pokedex))
+                    {
+                        violations.push(FormatViolation::NotInPokedex {
+                            index,
+                            species: member.species.clone(),
+                        });
+                    }
+                }
+                None => violations.push(FormatViolation::UnknownSpecies {
+                    index,
+                    species: member.species.clone(),
+                }),
+            }
+        }
+        for choice in &member.moves {
+            if format.ohko_clause && OHKO_MOVES.contains(&choice.name.as_str()) {
+                violations.push(FormatViolation::OhkoMove {
+                    index,
+                    move_name: choice.name.clone(),
+                });
+            }
+            if format.evasion_clause && EVASION_MOVES.contains(&choice.name.as_str()) {
+                violations.push(FormatViolation::EvasionMove {
+                    index,
+                    move_name: choice.name.clone(),
+                });
+            }
+        }
+        violations
+    }
+
+    /// 登録できる数に制限のあるポケモンか
+    pub fn is_restricted(format: &Format, member: &PokemonBuild, pokemons: &[Pokemon]) -> bool {
+        Self::is_listed(&format.restricted_species, member, pokemons)
+    }
+
+    /// ポケモンの名前か種族の名前がリストにある
+    fn is_listed(list: &[String], member: &PokemonBuild, pokemons: &[Pokemon]) -> bool {
+        let species_name = Self::species_name(&member.species, pokemons);
+        list.iter()
+            .any(|name| *name == member.species || name == species_name)
+    }
+
+    /// ポケモンの名前（姿を含む）から種族の名前を引く。見つからなければそのまま返す
+    pub fn species_name<'a>(name: &'a str, pokemons: &'a [Pokemon]) -> &'a str {
+        pokemons
+            .iter()
+            .find(|pokemon| pokemon.name == name)
+            .and_then(|pokemon| pokemon.species.as_deref())
+            .unwrap_or(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::pokemon_species::{PokedexNumber, PokemonColor};
+    use crate::domain::test_support::test_pokemon;
+
+    fn test_species(name: &str, is_mythical: bool, pokedexes: &[&str]) -> PokemonSpecies {
+        PokemonSpecies {
+            id: 0,
+            name: name.to_string(),
+            color: PokemonColor::White,
+            egg_groups: vec![],
+            flavor_text_entries: vec![],
+            names: vec![],
+            is_legendary: !is_mythical,
+            is_mythical,
+            is_baby: false,
+            capture_rate: 3,
+            gender_rate: -1,
+            evolves_from_species: None,
+            pokedex_numbers: pokedexes
+                .iter()
+                .map(|pokedex| PokedexNumber {
+                    entry_number: 1,
+                    pokedex: pokedex.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_clauses() {
+        let format = Format {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            battle_format: BattleFormat::Doubles,
+            level: LevelRule::Flat(50),
+            team_size: 6,
+            bring_count: 2,
+            item_clause: true,
+            species_clause: true,
+            banned_species: vec!["mew".to_string()],
+            restricted_species: vec!["koraidon".to_string(), "miraidon".to_string()],
+            max_restricted: 1,
+            no_mythicals: false,
+            allowed_pokedexes: vec![],
+            sleep_clause: false,
+            ohko_clause: true,
+            evasion_clause: false,
+            no_tera: false,
+        };
+        let team = Team::new(
+            "restricted",
+            vec![
+                PokemonBuild::new("koraidon", "orichalcum-pulse")
+                    .with_item("life-orb")
+                    .with_moves(&["flare-blitz", "sheer-cold"]),
+                PokemonBuild::new("miraidon", "hadron-engine").with_item("life-orb"),
+            ],
+        );
+
+        assert_eq!(
+            FormatValidator::validate(&format, &team, &[], &[]),
+            Err(vec![
+                FormatViolation::OhkoMove {
+                    index: 0,
+                    move_name: "sheer-cold".to_string(),
+                },
+                FormatViolation::DuplicateItem("life-orb".to_string()),
+                FormatViolation::TooManyRestricted { max: 1, actual: 2 },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_species_rules() {
+        let format = Format {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            battle_format: BattleFormat::Doubles,
+            level: LevelRule::Flat(50),
+            team_size: 6,
+            bring_count: 1,
+            item_clause: false,
+            species_clause: false,
+            banned_species: vec![],
+            restricted_species: vec!["calyrex".to_string()],
+            max_restricted: 0,
+            no_mythicals: true,
+            allowed_pokedexes: vec!["paldea".to_string(), "kitakami".to_string()],
+            sleep_clause: false,
+            ohko_clause: false,
+            evasion_clause: false,
+            no_tera: false,
+        };
+        let mut calyrex = test_pokemon("calyrex-shadow", &["psychic", "ghost"], 100, 100);
+        calyrex.species = Some("calyrex".to_string());
+        let species = vec![
+            test_species("calyrex", false, &["galar"]),
+            test_species("mew", true, &["kitakami"]),
+        ];
+        let team = Team::new(
+            "species",
+            vec![
+                PokemonBuild::new("calyrex-shadow", "as-one-spectrier"),
+                PokemonBuild::new("mew", "synchronize"),
+                PokemonBuild::new("missingno", "none"),
+            ],
+        );
+
+        assert_eq!(
+            FormatValidator::validate(&format, &team, &[calyrex], &species),
+            Err(vec![
+                FormatViolation::NotInPokedex {
+                    index: 0,
+                    species: "calyrex-shadow".to_string(),
+                },
+                FormatViolation::Mythical {
+                    index: 1,
+                    species: "mew".to_string(),
+                },
+                FormatViolation::UnknownSpecies {
+                    index: 2,
+                    species: "missingno".to_string(),
+                },
+                FormatViolation::TooManyRestricted { max: 0, actual: 1 },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_species_clause_counts_forms_as_one_species() {
+        let format = Format {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            battle_format: BattleFormat::Singles,
+            level: LevelRule::Flat(50),
+            team_size: 6,
+            bring_count: 1,
+            item_clause: false,
+            species_clause: true,
+            banned_species: vec![],
+            restricted_species: vec![],
+            max_restricted: 0,
+            no_mythicals: false,
+            allowed_pokedexes: vec![],
+            sleep_clause: false,
+            ohko_clause: false,
+            evasion_clause: false,
+            no_tera: false,
+        };
+        let pokemons: Vec<Pokemon> = [("rotom-wash", "water"), ("rotom-heat", "fire")]
+            .iter()
+            .map(|(name, second_type)| {
+                let mut rotom = test_pokemon(name, &["electric", second_type], 65, 86);
+                rotom.species = Some("rotom".to_string());
+                rotom
+            })
+            .collect();
+        let team = Team::new(
+            "rotoms",
+            vec![
+                PokemonBuild::new("rotom-wash", "levitate"),
+                PokemonBuild::new("rotom-heat", "levitate"),
+            ],
+        );
+
+        assert_eq!(
+            FormatValidator::validate(&format, &team, &pokemons, &[]),
+            Err(vec![FormatViolation::DuplicateSpecies("rotom".to_string())])
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle::{Battle, ReplacementRequest};
+use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+use crate::domain::model::battle_rng::{BattleRng, SeededRng};
+use crate::domain::model::move_slot::MoveSlot;
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::service::battle_agent::{BattleAgent, is_compatible};
+use crate::domain::service::damage_calculator::{DamageCalculator, DamageContext};
+use crate::domain::service::legal_actions::SlotOptions;
+use crate::domain::service::target_resolver::TargetResolver;
+use crate::domain::service::turn_order_resolver::TurnOrderResolver;
+use crate::domain::service::type_chart::TypeChart;
+
+/// ダメージ乱数の最小値と最大値（%）
+const MIN_ROLL: u32 = 85;
+const MAX_ROLL: u32 = 100;
+
+/// 期待ダメージが最も大きい技を選ぶエージェント。
+/// 場のポケモンが相手に倒される状況で、先に倒し切れない場合だけ、より耐えられる控えに交代する。
+/// 評価が同じ行動はシード付きの乱数で選ぶ
+#[derive(Debug, Clone)]
+pub struct GreedyAgent {
+    type_chart: TypeChart,
+    rng: SeededRng,
+}
+
+impl GreedyAgent {
+    pub fn new(type_chart: TypeChart, seed: u64) -> Self {
+        Self {
+            type_chart,
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    /// 乱数 roll でのダメージ（ダメージ計算式そのまま）
+    fn damage(
+        &self,
+        battle: &Battle,
+        attacker: &ActivePokemon,
+        defender: &ActivePokemon,
+        move_slot: &MoveSlot,
+        is_spread: bool,
+        random_roll: u32,
+    ) -> u32 {
+        if move_slot.damage_class.is_status() {
+            return 0;
+        }
+        let context = DamageContext {
+            is_spread,
+            random_roll,
+            weather: battle.field.weather,
+            terrain: battle.field.terrain,
+            ..Default::default()
+        };
+        DamageCalculator::calculate(attacker, defender, move_slot, &self.type_chart, &context)
+            .damage
+    }
+
+    /// 乱数と命中率を平均した期待ダメージ。対象の残りHPを超える分は数えない
+    fn expected_damage(
+        &self,
+        battle: &Battle,
+        attacker: &ActivePokemon,
+        defender: &ActivePokemon,
+        move_slot: &MoveSlot,
+        is_spread: bool,
+    ) -> f64 {
+        let total: u32 = (MIN_ROLL..=MAX_ROLL)
+            .map(|roll| {
+                self.damage(battle, attacker, defender, move_slot, is_spread, roll)
+                    .min(defender.current_hp)
+            })
+            .sum();
+        let accuracy = move_slot.accuracy.unwrap_or(100).min(100) as f64 / 100.0;
+        total as f64 / (MAX_ROLL - MIN_ROLL + 1) as f64 * accuracy
+    }
+
+    /// 技を使った場合の評価。相手へのダメージを加え、味方を巻き込む分は差し引く
+    fn score_move(
+        &self,
+        battle: &Battle,
+        user: SlotPosition,
+        move_index: usize,
+        target: Option<SlotPosition>,
+    ) -> f64 {
+        let Some(attacker) = battle.pokemon_at(user) else {
+            return 0.0;
+        };
+        let Some(move_slot) = attacker.moves.get(move_index) else {
+            return 0.0;
+        };
+        let mut rng = self.rng.clone();
+        let targets = TargetResolver::resolve(battle, user, &move_slot.target, target, &mut rng);
+        let is_spread = TargetResolver::is_spread(&move_slot.target, &targets);
+        targets
+            .iter()
+            .filter(|position| **position != user)
+            .filter_map(|position| Some((*position, battle.pokemon_at(*position)?)))
+            .map(|(position, defender)| {
+                let damage = self.expected_damage(battle, attacker, defender, move_slot, is_spread);
+                if position.is_opponent_of(&user) {
+                    damage
+                } else {
+                    -damage
+                }
+            })
+            .sum()
+    }
+
+    /// 相手の場のポケモンが pokemon に与えうる最大ダメージ
+    fn max_threat(&self, battle: &Battle, side: usize, pokemon: &ActivePokemon) -> u32 {
+        battle
+            .active_positions()
+            .into_iter()
+            .filter(|position| position.side != side)
+            .filter_map(|position| battle.pokemon_at(position))
+            .flat_map(|opponent| {
+                opponent.moves.iter().map(move |move_slot| {
+                    self.damage(battle, opponent, pokemon, move_slot, false, MAX_ROLL)
+                })
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// pokemon が相手の場のポケモンに与えうる最大ダメージ（残りHPまで）
+    fn best_damage(&self, battle: &Battle, side: usize, pokemon: &ActivePokemon) -> u32 {
+        battle
+            .active_positions()
+            .into_iter()
+            .filter(|position| position.side != side)
+            .filter_map(|position| battle.pokemon_at(position))
+            .flat_map(|defender| {
+                pokemon.moves.iter().map(move |move_slot| {
+                    self.damage(battle, pokemon, defender, move_slot, false, MAX_ROLL)
+                        .min(defender.current_hp)
+                })
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// 倒される前に動いて、最低乱数でも相手を倒せるか
+    fn can_knock_out_first(&self, battle: &Battle, user: SlotPosition) -> bool {
+        let Some(attacker) = battle.pokemon_at(user) else {
+            return false;
+        };
+        let speed = TurnOrderResolver::effective_speed(attacker);
+        battle
+            .opponent_positions(user)
+            .into_iter()
+            .filter_map(|position| battle.pokemon_at(position))
+            .filter(|defender| !defender.is_fainted())
+            .any(|defender| {
+                speed > TurnOrderResolver::effective_speed(defender)
+                    && attacker.moves.iter().any(|move_slot| {
+                        move_slot.has_pp()
+                            && self.damage(battle, attacker, defender, move_slot, false, MIN_ROLL)
+                                >= defender.current_hp
+                    })
+            })
+    }
+
+    /// 倒されそうな場合に、受けるダメージの割合が最も小さく、倒されない控えを選ぶ
+    fn choose_switch(
+        &self,
+        battle: &Battle,
+        user: SlotPosition,
+        actions: &[&BattleAction],
+    ) -> Option<BattleAction> {
+        let pokemon = battle.pokemon_at(user)?;
+        if self.max_threat(battle, user.side, pokemon) < pokemon.current_hp
+            || self.can_knock_out_first(battle, user)
+        {
+            return None;
+        }
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                BattleAction::Switch { party_index } => {
+                    let candidate = &battle.sides[user.side].party[*party_index];
+                    let threat = self.max_threat(battle, user.side, candidate);
+                    (threat < candidate.current_hp)
+                        .then(|| (threat as f64 / candidate.current_hp as f64, *action))
+                }
+                _ => None,
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, action)| action.clone())
+    }
+
+    /// 評価が最大の行動を選ぶ。同点の場合は乱数で選ぶ
+    fn choose_best(&mut self, scored: Vec<(f64, &BattleAction)>) -> Option<BattleAction> {
+        let best = scored
+            .iter()
+            .map(|(score, _)| *score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let ties: Vec<_> = scored
+            .into_iter()
+            .filter(|(score, _)| *score == best)
+            .collect();
+        if ties.is_empty() {
+            return None;
+        }
+        let index = self.rng.next_below(ties.len() as u32) as usize;
+        Some(ties[index].1.clone())
+    }
+}
+
+impl BattleAgent for GreedyAgent {
+    fn choose_actions(&mut self, view: &PlayerView, options: &[SlotOptions]) -> Vec<ChosenAction> {
+        let battle = &view.battle;
+        let mut chosen = Vec::new();
+        for slot in options {
+            // テラスタルは使わず、技と交代だけを比べる
+            let actions: Vec<_> = slot
+                .actions
+                .iter()
+                .filter(|action| {
+                    !matches!(
+                        action,
+                        BattleAction::UseMove {
+                            terastallize: true,
+                            ..
+                        }
+                    )
+                })
+                .filter(|action| is_compatible(&chosen, action))
+                .collect();
+            let switch = self.choose_switch(battle, slot.position, &actions);
+            let scored = actions
+                .iter()
+                .filter_map(|action| match action {
+                    BattleAction::UseMove {
+                        move_index, target, ..
+                    } => Some((
+                        self.score_move(battle, slot.position, *move_index, *target),
+                        *action,
+                    )),
+                    BattleAction::Switch { .. } => None,
+                    BattleAction::Struggle | BattleAction::Recharge => Some((0.0, *action)),
+                })
+                .collect();
+            let action = switch
+                .or_else(|| self.choose_best(scored))
+                .or_else(|| actions.first().map(|action| (*action).clone()));
+            if let Some(action) = action {
+                chosen.push(ChosenAction::new(slot.position, action));
+            }
+        }
+        chosen
+    }
+
+    /// 相手の場のポケモンに最も大きなダメージを与えられる控えから順に繰り出す
+    fn choose_replacements(
+        &mut self,
+        view: &PlayerView,
+        request: &ReplacementRequest,
+    ) -> Vec<(SlotPosition, usize)> {
+        let battle = &view.battle;
+        let side = &battle.sides[request.side];
+        let mut candidates = request.candidates.clone();
+        candidates.sort_by_key(|party_index| {
+            std::cmp::Reverse(self.best_damage(battle, request.side, &side.party[*party_index]))
+        });
+        request
+            .slots
+            .iter()
+            .zip(candidates)
+            .take(request.required_count())
+            .map(|(slot, party_index)| (SlotPosition::new(request.side, *slot), party_index))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::legal_actions::LegalActions;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    #[test]
+    fn test_greedy_picks_strongest_move_and_switches_when_threatened() {
+        let moves = vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move(
+                "hyper-voice",
+                "normal",
+                "special",
+                Some(90),
+                "all-opponents",
+            ),
+        ];
+        let party = || {
+            (0..2)
+                .map(|_| test_active_pokemon("a", &["normal"], &moves))
+                .collect()
+        };
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), party(), 1),
+            BattleSide::new("opponent".to_string(), party(), 1),
+        );
+        let mut agent = GreedyAgent::new(TypeChart::default(), 1);
+
+        let options = LegalActions::for_side(&battle, 0);
+        let actions = agent.choose_actions(&PlayerView::new(&battle, 0), &options);
+        assert!(matches!(
+            actions[0].action,
+            BattleAction::UseMove { move_index: 1, .. }
+        ));
+
+        // 残りHPが少なく、相手より遅いと控えに交代する
+        battle.sides[0].party[0].current_hp = 1;
+        battle.sides[1].party[0].speed = 200;
+        battle.sides[1].party[0].revealed.reveal_move(1);
+        let options = LegalActions::for_side(&battle, 0);
+        let actions = agent.choose_actions(&PlayerView::new(&battle, 0), &options);
+        assert_eq!(actions[0].action, BattleAction::Switch { party_index: 1 });
+    }
+}
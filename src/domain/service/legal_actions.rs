@@ -0,0 +1,349 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::{BattleAction, SlotPosition};
+use crate::domain::service::target_resolver::TargetResolver;
+
+/// こだわり系の道具（最初に出した技しか選べなくなる）
+pub const CHOICE_ITEMS: [&str; 3] = ["choice-band", "choice-specs", "choice-scarf"];
+
+/// 選んだ行動が、その時点のルール上選べない理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalActionReason {
+    /// PPが残っていない
+    NoPp,
+    /// こだわり系の道具で別の技に固定されている
+    ChoiceLocked,
+    /// 「アンコール」で別の技しか出せない
+    Encored,
+    /// 「ちょうはつ」で変化技を出せない
+    Taunted,
+    /// 「かなしばり」で出せない
+    Disabled,
+    /// 「いちゃもん」で同じ技を連続で出せない
+    Tormented,
+    /// とつげきチョッキで変化技を出せない
+    AssaultVest,
+    /// 特性・バインド技で逃げられない
+    Trapped,
+    /// 反動で動けないため、反動以外の行動は選べない
+    MustRecharge,
+    /// 暴れる技の最中で、その技以外は選べない
+    LockedIn,
+    /// 出せる技が残っているため「わるあがき」は選べない
+    StruggleUnavailable,
+    /// 反動を受けていないため反動の行動は選べない
+    RechargeUnavailable,
+    /// テラスタイプがない、または使用済み
+    TeraUnavailable,
+}
+
+/// 場の1枠について、そのターンに選べる行動の一覧
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotOptions {
+    pub position: SlotPosition,
+    pub actions: Vec<BattleAction>,
+}
+
+/// 現在のバトルの状態から、選択できる行動を列挙・検証する。
+/// 第9世代のルール（ダイマックス・キョダイマックスなし、テラスタルは1バトル1回）に従う
+pub struct LegalActions;
+
+impl LegalActions {
+    /// サイドの行動できる枠ごとに、選べる行動を返す
+    pub fn for_side(battle: &Battle, side: usize) -> Vec<SlotOptions> {
+        if battle.is_over() {
+            return vec![];
+        }
+        battle
+            .active_positions()
+            .into_iter()
+            .filter(|position| position.side == side)
+            .map(|position| SlotOptions {
+                position,
+                actions: Self::for_slot(battle, position),
+            })
+            .collect()
+    }
+
+    /// 1枠で選べる行動（技と対象の組み合わせ、テラスタル、交代）
+    pub fn for_slot(battle: &Battle, position: SlotPosition) -> Vec<BattleAction> {
+        let Some(pokemon) = battle
+            .pokemon_at(position)
+            .filter(|pokemon| !pokemon.is_fainted())
+        else {
+            return vec![];
+        };
+        if pokemon.volatiles.must_recharge {
+            return vec![BattleAction::Recharge];
+        }
+        if let Some(locked) = pokemon.volatiles.locked_move {
+            return vec![BattleAction::UseMove {
+                move_index: locked.move_index,
+                target: None,
+                terastallize: false,
+            }];
+        }
+        let can_terastallize = Self::can_terastallize(battle, position);
+
+        let mut actions = Vec::new();
+        for (move_index, move_slot) in pokemon.moves.iter().enumerate() {
+            if Self::check_move(pokemon, move_index).is_err() {
+                continue;
+            }
+            let targets: Vec<Option<SlotPosition>> =
+                if TargetResolver::requires_target(battle.format, &move_slot.target) {
+                    battle
+                        .active_positions()
+                        .into_iter()
+                        .filter(|target| {
+                            TargetResolver::validate(
+                                battle.format,
+                                position,
+                                &move_slot.target,
+                                Some(*target),
+                            )
+                            .is_ok()
+                        })
+                        .map(Some)
+                        .collect()
+                } else {
+                    vec![None]
+                };
+            for target in targets {
+                for terastallize in [false, true] {
+                    if terastallize && !can_terastallize {
+                        continue;
+                    }
+                    actions.push(BattleAction::UseMove {
+                        move_index,
+                        target,
+                        terastallize,
+                    });
+                }
+            }
+        }
+        if actions.is_empty() {
+            actions.push(BattleAction::Struggle);
+        }
+        if !Self::is_trapped(battle, position) {
+            actions.extend(
+                battle.sides[position.side]
+                    .bench()
+                    .into_iter()
+                    .map(|party_index| BattleAction::Switch { party_index }),
+            );
+        }
+        actions
+    }
+
+    /// 行動がルール上選べるかを検証する（技の番号・控えの存在と対象の指定は呼び出し側で確認する）
+    pub fn check(
+        battle: &Battle,
+        position: SlotPosition,
+        action: &BattleAction,
+    ) -> Result<(), IllegalActionReason> {
+        let Some(pokemon) = battle.pokemon_at(position) else {
+            return Ok(());
+        };
+        let volatiles = &pokemon.volatiles;
+        match action {
+            BattleAction::Recharge if volatiles.must_recharge => Ok(()),
+            BattleAction::Recharge => Err(IllegalActionReason::RechargeUnavailable),
+            _ if volatiles.must_recharge => Err(IllegalActionReason::MustRecharge),
+            BattleAction::UseMove {
+                move_index,
+                terastallize,
+                ..
+            } => {
+                if let Some(locked) = volatiles.locked_move {
+                    return if locked.move_index == *move_index && !terastallize {
+                        Ok(())
+                    } else {
+                        Err(IllegalActionReason::LockedIn)
+                    };
+                }
+                if *terastallize && !Self::can_terastallize(battle, position) {
+                    return Err(IllegalActionReason::TeraUnavailable);
+                }
+                Self::check_move(pokemon, *move_index)
+            }
+            _ if volatiles.locked_move.is_some() => Err(IllegalActionReason::LockedIn),
+            BattleAction::Struggle => {
+                if (0..pokemon.moves.len()).any(|index| Self::check_move(pokemon, index).is_ok()) {
+                    Err(IllegalActionReason::StruggleUnavailable)
+                } else {
+                    Ok(())
+                }
+            }
+            BattleAction::Switch { .. } if Self::is_trapped(battle, position) => {
+                Err(IllegalActionReason::Trapped)
+            }
+            BattleAction::Switch { .. } => Ok(()),
+        }
+    }
+
+    /// 技1つについて、PPと技を縛る状態を確認する
+    pub fn check_move(
+        pokemon: &ActivePokemon,
+        move_index: usize,
+    ) -> Result<(), IllegalActionReason> {
+        let Some(move_slot) = pokemon.moves.get(move_index) else {
+            return Err(IllegalActionReason::NoPp);
+        };
+        let volatiles = &pokemon.volatiles;
+        if !move_slot.has_pp() {
+            return Err(IllegalActionReason::NoPp);
+        }
+        if volatiles
+            .encore
+            .is_some_and(|encore| encore.move_index != move_index)
+        {
+            return Err(IllegalActionReason::Encored);
+        }
+        if volatiles
+            .choice_lock
+            .is_some_and(|locked| locked != move_index)
+            && CHOICE_ITEMS.iter().any(|item| pokemon.has_item(item))
+        {
+            return Err(IllegalActionReason::ChoiceLocked);
+        }
+        if volatiles
+            .disable
+            .is_some_and(|disable| disable.move_index == move_index)
+        {
+            return Err(IllegalActionReason::Disabled);
+        }
+        if volatiles.tormented
+            && volatiles
+                .last_move
+                .is_some_and(|last_move| last_move.move_index == move_index)
+        {
+            return Err(IllegalActionReason::Tormented);
+        }
+        if move_slot.damage_class.is_status() {
+            if volatiles.taunt_turns > 0 {
+                return Err(IllegalActionReason::Taunted);
+            }
+            if pokemon.has_item("assault-vest") {
+                return Err(IllegalActionReason::AssaultVest);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn can_terastallize(battle: &Battle, position: SlotPosition) -> bool {
+        battle.rules.terastallization
+            && !battle.sides[position.side].tera_used
+            && battle
+                .pokemon_at(position)
+                .is_some_and(ActivePokemon::can_terastallize)
+    }
+
+    /// 交代できない状態か。ゴーストタイプときれいなぬけがらを持つポケモンは常に交代できる
+    pub fn is_trapped(battle: &Battle, position: SlotPosition) -> bool {
+        let Some(pokemon) = battle.pokemon_at(position) else {
+            return false;
+        };
+        if pokemon.has_type("ghost") || pokemon.has_item("shed-shell") {
+            return false;
+        }
+        if pokemon.volatiles.bound_turns > 0 {
+            return true;
+        }
+        battle
+            .opponent_positions(position)
+            .into_iter()
+            .filter_map(|opponent| battle.pokemon_at(opponent))
+            .filter(|opponent| !opponent.is_fainted())
+            .any(|opponent| {
+                (opponent.has_ability("shadow-tag") && !pokemon.has_ability("shadow-tag"))
+                    || (opponent.has_ability("arena-trap") && pokemon.is_grounded())
+                    || (opponent.has_ability("magnet-pull") && pokemon.has_type("steel"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::model::volatile_status::MoveRestriction;
+    use crate::domain::test_support::{test_ability, test_active_pokemon, test_move};
+
+    #[test]
+    fn test_enumerate_doubles_actions() {
+        let moves = vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move("surf", "water", "special", Some(90), "all-other-pokemon"),
+        ];
+        let party = || {
+            (0..3)
+                .map(|_| test_active_pokemon("a", &["normal"], &moves))
+                .collect()
+        };
+        let battle = Battle::new(
+            BattleFormat::Doubles,
+            BattleSide::new("player".to_string(), party(), 2),
+            BattleSide::new("opponent".to_string(), party(), 2),
+        );
+
+        let options = LegalActions::for_side(&battle, 0);
+        assert_eq!(options.len(), 2);
+        // たいあたり: 味方1体・相手2体の3通り、なみのり: 1通り、交代: 1通り
+        assert_eq!(options[0].actions.len(), 5);
+        assert!(
+            options[0]
+                .actions
+                .contains(&BattleAction::Switch { party_index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_restrictions_limit_actions() {
+        let moves = vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move("growl", "normal", "status", None, "all-opponents"),
+            test_move("ember", "fire", "special", Some(40), "selected-pokemon"),
+        ];
+        let party = || {
+            (0..2)
+                .map(|_| test_active_pokemon("a", &["normal"], &moves))
+                .collect()
+        };
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), party(), 1),
+            BattleSide::new("opponent".to_string(), party(), 1),
+        );
+        let user = SlotPosition::new(0, 0);
+        {
+            let pokemon = battle.pokemon_at_mut(user).unwrap();
+            pokemon.volatiles.taunt_turns = 2;
+            pokemon.volatiles.disable = Some(MoveRestriction {
+                move_index: 2,
+                turns: 3,
+            });
+        }
+        battle.sides[1].party[0].active_ability = test_ability("shadow-tag").into();
+
+        assert_eq!(
+            LegalActions::for_slot(&battle, user),
+            vec![BattleAction::UseMove {
+                move_index: 0,
+                target: None,
+                terastallize: false,
+            }]
+        );
+        assert_eq!(
+            LegalActions::check(&battle, user, &BattleAction::Switch { party_index: 1 }),
+            Err(IllegalActionReason::Trapped)
+        );
+
+        battle.pokemon_at_mut(user).unwrap().moves[0].current_pp = 0;
+        assert_eq!(
+            LegalActions::for_slot(&battle, user),
+            vec![BattleAction::Struggle]
+        );
+    }
+}
@@ -0,0 +1,442 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::ChosenAction;
+use crate::domain::model::battle_rng::{BattleRng, SeededRng};
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::service::battle_agent::{BattleAgent, joint_actions};
+use crate::domain::service::battle_engine::BattleEngine;
+use crate::domain::service::battle_evaluator::{BattleEvaluator, HeuristicEvaluator, WIN_SCORE};
+use crate::domain::service::determinizer::Determinizer;
+use crate::domain::service::legal_actions::{LegalActions, SlotOptions};
+use crate::domain::service::random_agent::RandomAgent;
+use std::thread;
+
+/// モンテカルロ木探索の設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MctsConfig {
+    /// スレッドごとの試行回数
+    pub iterations: u32,
+    /// UCB1 の探索項の係数
+    pub exploration: f64,
+    /// 独立に探索してから訪問回数を合算するスレッドの数（ルート並列化）
+    pub threads: usize,
+    /// プレイアウトで進めるターン数の上限。決着しなければ評価関数で勝率に換算する
+    pub max_playout_turns: u32,
+    pub seed: u64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 1000,
+            exploration: std::f64::consts::SQRT_2,
+            threads: 1,
+            max_playout_turns: 30,
+            seed: 0,
+        }
+    }
+}
+
+/// 探索木の根の番号
+const ROOT: usize = 0;
+
+/// 探索木の節点。根以外の節点は、親の局面で1つのサイドが選んだ行動の組を表す。
+/// 1ターンは「自分の行動の組」→「相手の行動の組」の2段で表し、相手の段を選び終えたらターンを進める
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// この節点に至る行動の組（根は空）
+    actions: Vec<ChosenAction>,
+    /// 子の節点の番号
+    children: Vec<usize>,
+    visits: u32,
+    /// 親の局面でこの行動の組を選べた回数。決定化によって選べる行動が変わるため、UCB1 の試行回数にはこちらを使う
+    availability: u32,
+    /// 探索するサイドから見た報酬（勝ちを 1.0、負けを 0.0）の合計
+    reward: f64,
+}
+
+/// 決定化ごとに同じ木をたどる情報集合モンテカルロ木探索（ISMCTS）の探索木
+#[derive(Debug, Clone)]
+struct SearchTree {
+    nodes: Vec<Node>,
+}
+
+impl SearchTree {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+        }
+    }
+
+    /// 局面で選べる行動の組のうち、まだ木にないものを1つ追加して返す。すべて木にあれば None
+    fn expand(
+        &mut self,
+        parent: usize,
+        joints: &[Vec<ChosenAction>],
+        rng: &mut SeededRng,
+    ) -> Option<usize> {
+        let untried: Vec<&Vec<ChosenAction>> = joints
+            .iter()
+            .filter(|joint| self.child(parent, joint).is_none())
+            .collect();
+        if untried.is_empty() {
+            return None;
+        }
+        let actions = untried[rng.next_below(untried.len() as u32) as usize].clone();
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            actions,
+            availability: 1,
+            ..Node::default()
+        });
+        self.nodes[parent].children.push(index);
+        Some(index)
+    }
+
+    /// 今の局面で選べる子の、選べた回数を数える
+    fn mark_available(&mut self, parent: usize, joints: &[Vec<ChosenAction>]) {
+        for joint in joints {
+            if let Some(child) = self.child(parent, joint) {
+                self.nodes[child].availability += 1;
+            }
+        }
+    }
+
+    fn child(&self, parent: usize, joint: &[ChosenAction]) -> Option<usize> {
+        self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|child| self.nodes[*child].actions == joint)
+    }
+}
+
+/// 相手の見えない情報を試行ごとに抽選し直す（決定化）情報集合モンテカルロ木探索のエージェント。
+/// 試行ごとに決定化したバトルで、自分と相手の行動の組を交互に木から選び（選択）、
+/// 木にない行動の組に達したら節点を1つ追加して（展開）、以降をランダムエージェントで進め（プレイアウト）、
+/// 得た報酬を通った節点に加える（逆伝播）。最も多く訪問した根の行動の組を返す
+pub struct MctsAgent<D: Determinizer + Sync> {
+    engine: BattleEngine,
+    determinizer: D,
+    config: MctsConfig,
+    /// 行動を選ぶたびに探索のシードを変えるための回数
+    decisions: u64,
+}
+
+impl<D: Determinizer + Sync> MctsAgent<D> {
+    pub fn new(engine: BattleEngine, determinizer: D, config: MctsConfig) -> Self {
+        Self {
+            engine,
+            determinizer,
+            config,
+            decisions: 0,
+        }
+    }
+
+    /// 1スレッド分の探索を行い、根の行動の組ごとの訪問回数を返す
+    fn search(&self, view: &PlayerView, joints: &[Vec<ChosenAction>], seed: u64) -> Vec<u32> {
+        let mut rng = SeededRng::new(seed);
+        let mut tree = SearchTree::new();
+        for _ in 0..self.config.iterations {
+            let mut battle = self.determinizer.determinize(view, &mut rng);
+            self.iterate(&mut tree, &mut battle, view.side, &mut rng);
+        }
+        joints
+            .iter()
+            .map(|joint| {
+                tree.child(ROOT, joint)
+                    .map_or(0, |child| tree.nodes[child].visits)
+            })
+            .collect()
+    }
+
+    /// 1回の試行。選択・展開・プレイアウト・逆伝播を行う
+    fn iterate(
+        &self,
+        tree: &mut SearchTree,
+        battle: &mut Battle,
+        side: usize,
+        rng: &mut SeededRng,
+    ) {
+        let opponent = 1 - side;
+        let mut path = vec![ROOT];
+        let mut node = ROOT;
+        // 相手の段を選ぶまで保留している自分の行動の組
+        let mut pending: Option<Vec<ChosenAction>> = None;
+        while !battle.is_over() {
+            if pending.is_none() && !battle.replacement_requests().is_empty() {
+                // ひんし後の交代は木に含めず、ランダムに選ぶ
+                if !self.random_replacements(battle, rng) {
+                    break;
+                }
+                continue;
+            }
+            let acting = if pending.is_none() { side } else { opponent };
+            let joints = joint_actions(&LegalActions::for_side(battle, acting));
+            if joints.is_empty() {
+                break;
+            }
+            tree.mark_available(node, &joints);
+            let (child, expanded) = match tree.expand(node, &joints, rng) {
+                Some(child) => (child, true),
+                None => (self.select(tree, node, &joints, acting == side), false),
+            };
+            path.push(child);
+            node = child;
+            let actions = tree.nodes[child].actions.clone();
+            match pending.take() {
+                None => pending = Some(actions),
+                Some(mut own) => {
+                    own.extend(actions);
+                    if self.engine.execute_turn(battle, own).is_err() {
+                        break;
+                    }
+                }
+            }
+            if expanded {
+                break;
+            }
+        }
+
+        let reward = self.playout(battle, side, pending, rng);
+        for index in path {
+            let node = &mut tree.nodes[index];
+            node.visits += 1;
+            node.reward += reward;
+        }
+    }
+
+    /// 木にある子のうち、今の局面で選べるものから UCB1 の値が最大のものを選ぶ。
+    /// 相手の段では相手から見た報酬（1.0 から引いた値）で比べる
+    fn select(
+        &self,
+        tree: &SearchTree,
+        parent: usize,
+        joints: &[Vec<ChosenAction>],
+        maximize: bool,
+    ) -> usize {
+        let available: Vec<usize> = joints
+            .iter()
+            .filter_map(|joint| tree.child(parent, joint))
+            .collect();
+        let ucb = |node: &Node| {
+            let mean = node.reward / node.visits.max(1) as f64;
+            let mean = if maximize { mean } else { 1.0 - mean };
+            mean + self.config.exploration
+                * ((node.availability as f64).ln() / node.visits.max(1) as f64).sqrt()
+        };
+        available
+            .into_iter()
+            .max_by(|a, b| ucb(&tree.nodes[*a]).total_cmp(&ucb(&tree.nodes[*b])))
+            .unwrap_or(parent)
+    }
+
+    /// 両者の交代をランダムに選んで実行する。交代できなければ false
+    fn random_replacements(&self, battle: &mut Battle, rng: &mut SeededRng) -> bool {
+        let mut agent = RandomAgent::new(rng.next_u32() as u64);
+        let choices = battle
+            .replacement_requests()
+            .iter()
+            .flat_map(|request| {
+                agent.choose_replacements(&PlayerView::new(battle, request.side), request)
+            })
+            .collect();
+        self.engine.submit_replacements(battle, choices).is_ok()
+    }
+
+    /// 保留中の自分の行動があれば相手をランダムに行動させてターンを進め、
+    /// その後は両者ランダムに行動させて報酬（0.0〜1.0）を返す
+    fn playout(
+        &self,
+        battle: &mut Battle,
+        side: usize,
+        pending: Option<Vec<ChosenAction>>,
+        rng: &mut SeededRng,
+    ) -> f64 {
+        let mut agents = [
+            RandomAgent::new(rng.next_u32() as u64),
+            RandomAgent::new(rng.next_u32() as u64),
+        ];
+        if let Some(mut actions) = pending {
+            let opponent = 1 - side;
+            let options = LegalActions::for_side(battle, opponent);
+            actions.extend(
+                agents[opponent].choose_actions(&PlayerView::new(battle, opponent), &options),
+            );
+            if self.engine.execute_turn(battle, actions).is_err() {
+                return 0.0;
+            }
+        }
+
+        for _ in 0..self.config.max_playout_turns {
+            if battle.is_over() {
+                break;
+            }
+            if !battle.replacement_requests().is_empty() {
+                if !self.random_replacements(battle, rng) {
+                    break;
+                }
+                continue;
+            }
+            let mut actions = Vec::new();
+            for (side, agent) in agents.iter_mut().enumerate() {
+                let options = LegalActions::for_side(battle, side);
+                actions.extend(agent.choose_actions(&PlayerView::new(battle, side), &options));
+            }
+            if self.engine.execute_turn(battle, actions).is_err() {
+                break;
+            }
+        }
+        let score = HeuristicEvaluator::default().evaluate(battle, side);
+        (score / WIN_SCORE + 1.0) / 2.0
+    }
+}
+
+impl<D: Determinizer + Sync> BattleAgent for MctsAgent<D> {
+    fn choose_actions(&mut self, view: &PlayerView, options: &[SlotOptions]) -> Vec<ChosenAction> {
+        let joints = joint_actions(options);
+        if joints.len() <= 1 {
+            return joints.into_iter().next().unwrap_or_default();
+        }
+        self.decisions += 1;
+        let base_seed = self.config.seed ^ self.decisions.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let this = &*self;
+        let joints_ref = &joints;
+        let results: Vec<Vec<u32>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..this.config.threads.max(1) as u64)
+                .map(|thread_index| {
+                    scope.spawn(move || this.search(view, joints_ref, base_seed + thread_index))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("MCTS thread panicked"))
+                .collect()
+        });
+
+        let mut visits = vec![0u32; joints.len()];
+        for counts in &results {
+            for (total, count) in visits.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+        let best = visits
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, visits)| (**visits, std::cmp::Reverse(*index)))
+            .map_or(0, |(index, _)| index);
+        joints.into_iter().nth(best).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_action::{BattleAction, SlotPosition};
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::determinizer::RandomFutureDeterminizer;
+    use crate::domain::service::type_chart::TypeChart;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    #[test]
+    fn test_mcts_finds_knock_out_with_threads() {
+        let moves = vec![
+            test_move("pound", "normal", "physical", Some(10), "selected-pokemon"),
+            test_move("swift", "normal", "special", Some(60), "all-opponents"),
+        ];
+        let mut player = test_active_pokemon("a", &["normal"], &moves);
+        player.speed = 200;
+        player.current_hp = 30;
+        let mut opponent = test_active_pokemon("b", &["normal"], &moves);
+        opponent.current_hp = 10;
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), vec![player], 1),
+            BattleSide::new("opponent".to_string(), vec![opponent], 1),
+        );
+        let config = MctsConfig {
+            iterations: 200,
+            threads: 2,
+            ..Default::default()
+        };
+        let mut agent = MctsAgent::new(
+            BattleEngine::new(TypeChart::default()),
+            RandomFutureDeterminizer,
+            config,
+        );
+
+        let options = LegalActions::for_side(&battle, 0);
+        let actions = agent.choose_actions(&PlayerView::new(&battle, 0), &options);
+        assert_eq!(
+            actions,
+            vec![ChosenAction::new(
+                SlotPosition::new(0, 0),
+                BattleAction::UseMove {
+                    move_index: 1,
+                    target: None,
+                    terastallize: false,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_search_tree_expands_opponent_replies_and_next_turns() {
+        let moves = vec![
+            test_move("pound", "normal", "physical", Some(10), "selected-pokemon"),
+            test_move("swift", "normal", "special", Some(60), "all-opponents"),
+        ];
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        );
+        let agent = MctsAgent::new(
+            BattleEngine::new(TypeChart::default()),
+            RandomFutureDeterminizer,
+            MctsConfig::default(),
+        );
+        let view = PlayerView::new(&battle, 0);
+        let mut rng = SeededRng::new(1);
+        let mut tree = SearchTree::new();
+        for _ in 0..100 {
+            let mut determinized = agent.determinizer.determinize(&view, &mut rng);
+            agent.iterate(&mut tree, &mut determinized, 0, &mut rng);
+        }
+
+        let depth = |mut index: usize| {
+            let mut depth = 0;
+            while let Some(child) = tree.nodes[index]
+                .children
+                .iter()
+                .copied()
+                .max_by_key(|child| tree.nodes[*child].visits)
+            {
+                index = child;
+                depth += 1;
+            }
+            depth
+        };
+        // 自分の行動・相手の応手・次のターンの自分の行動まで木が伸びる
+        assert!(depth(ROOT) >= 3);
+        assert_eq!(tree.nodes[ROOT].visits, 100);
+        let own = tree.nodes[ROOT].children[0];
+        assert_eq!(
+            tree.nodes[own].visits,
+            tree.nodes[own]
+                .children
+                .iter()
+                .map(|child| tree.nodes[*child].visits)
+                .sum::<u32>()
+                + 1
+        );
+    }
+}
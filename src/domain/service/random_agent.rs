@@ -0,0 +1,103 @@
+use crate::domain::model::battle::ReplacementRequest;
+use crate::domain::model::battle_action::{ChosenAction, SlotPosition};
+use crate::domain::model::battle_rng::{BattleRng, SeededRng};
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::service::battle_agent::{BattleAgent, is_compatible};
+use crate::domain::service::legal_actions::SlotOptions;
+
+/// 選べる行動の中から一様ランダムに選ぶエージェント。
+/// シードが同じなら同じ選択を繰り返すため、対戦の比較やモンテカルロ試行の基準に使う
+#[derive(Debug, Clone)]
+pub struct RandomAgent {
+    rng: SeededRng,
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+        }
+    }
+}
+
+impl BattleAgent for RandomAgent {
+    fn choose_actions(&mut self, _view: &PlayerView, options: &[SlotOptions]) -> Vec<ChosenAction> {
+        let mut chosen = Vec::new();
+        for slot in options {
+            let candidates: Vec<_> = slot
+                .actions
+                .iter()
+                .filter(|action| is_compatible(&chosen, action))
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            let index = self.rng.next_below(candidates.len() as u32) as usize;
+            chosen.push(ChosenAction::new(slot.position, candidates[index].clone()));
+        }
+        chosen
+    }
+
+    fn choose_replacements(
+        &mut self,
+        _view: &PlayerView,
+        request: &ReplacementRequest,
+    ) -> Vec<(SlotPosition, usize)> {
+        let mut candidates = request.candidates.clone();
+        request
+            .slots
+            .iter()
+            .take(request.required_count())
+            .map(|slot| {
+                let index = self.rng.next_below(candidates.len() as u32) as usize;
+                (
+                    SlotPosition::new(request.side, *slot),
+                    candidates.remove(index),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle::Battle;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::legal_actions::LegalActions;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    #[test]
+    fn test_same_seed_chooses_same_legal_actions() {
+        let moves = vec![
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move("swift", "normal", "special", Some(60), "all-opponents"),
+        ];
+        let party = || {
+            (0..3)
+                .map(|_| test_active_pokemon("a", &["normal"], &moves))
+                .collect()
+        };
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), party(), 1),
+            BattleSide::new("opponent".to_string(), party(), 1),
+        );
+        let options = LegalActions::for_side(&battle, 0);
+        let view = PlayerView::new(&battle, 0);
+
+        let choose = |seed| {
+            let mut agent = RandomAgent::new(seed);
+            (0..10)
+                .map(|_| agent.choose_actions(&view, &options))
+                .collect::<Vec<_>>()
+        };
+        let first = choose(3);
+        assert_eq!(first, choose(3));
+        for actions in &first {
+            assert_eq!(actions.len(), 1);
+            assert!(options[0].actions.contains(&actions[0].action));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::SlotPosition;
+use crate::domain::model::r#move::MoveTarget;
+use crate::domain::model::move_slot::MoveSlot;
+use crate::domain::model::volatile_status::CenterOfAttention;
+
+/// 技の引き寄せを無視する特性
+const REDIRECTION_IGNORING_ABILITIES: [&str; 2] = ["stalwart", "propeller-tail"];
+/// 技の引き寄せを無視する技
+const REDIRECTION_IGNORING_MOVES: [&str; 1] = ["snipe-shot"];
+
+/// 「このゆびとまれ」「いかりのこな」「ひらいしん」「よびみず」による対象の引き寄せ
+pub struct RedirectionResolver;
+
+impl RedirectionResolver {
+    /// 単体技の対象を引き寄せ先に差し替える。
+    /// 注目状態（このゆびとまれ等）が特性による引き寄せより優先される
+    pub fn redirect(
+        battle: &Battle,
+        user: SlotPosition,
+        move_slot: &MoveSlot,
+        targets: Vec<SlotPosition>,
+    ) -> Vec<SlotPosition> {
+        let [original] = targets[..] else {
+            return targets;
+        };
+        let Some(attacker) = battle.pokemon_at(user) else {
+            return targets;
+        };
+        if !matches!(
+            move_slot.target,
+            MoveTarget::SelectedPokemon | MoveTarget::RandomOpponent
+        ) || Self::ignores_redirection(attacker, move_slot)
+        {
+            return targets;
+        }
+
+        if original.is_opponent_of(&user) {
+            let center = battle
+                .opponent_positions(user)
+                .into_iter()
+                .filter(|position| battle.is_alive(*position))
+                .find(|position| {
+                    battle
+                        .pokemon_at(*position)
+                        .and_then(|pokemon| pokemon.volatiles.center_of_attention)
+                        .is_some_and(|center| Self::is_drawn_by(attacker, center))
+                });
+            if let Some(center) = center {
+                return vec![center];
+            }
+        }
+
+        if let Some(ability) = Self::absorbing_ability(&move_slot.type_name) {
+            let target_has_ability = battle
+                .pokemon_at(original)
+                .is_some_and(|pokemon| pokemon.has_ability(ability));
+            if !target_has_ability {
+                let holder = battle.active_positions().into_iter().find(|position| {
+                    *position != user
+                        && battle
+                            .pokemon_at(*position)
+                            .is_some_and(|pokemon| pokemon.has_ability(ability))
+                });
+                if let Some(holder) = holder {
+                    return vec![holder];
+                }
+            }
+        }
+
+        targets
+    }
+
+    /// 技のタイプを引き寄せて無効化する特性（ひらいしん / よびみず）
+    pub fn absorbing_ability(type_name: &str) -> Option<&'static str> {
+        match type_name {
+            "electric" => Some("lightning-rod"),
+            "water" => Some("storm-drain"),
+            _ => None,
+        }
+    }
+
+    fn ignores_redirection(attacker: &ActivePokemon, move_slot: &MoveSlot) -> bool {
+        REDIRECTION_IGNORING_ABILITIES
+            .iter()
+            .any(|ability| attacker.has_ability(ability))
+            || REDIRECTION_IGNORING_MOVES.contains(&move_slot.name.as_str())
+    }
+
+    /// 「いかりのこな」は粉技を受けないポケモンを引き寄せられない
+    fn is_drawn_by(attacker: &ActivePokemon, center: CenterOfAttention) -> bool {
+        match center {
+            CenterOfAttention::FollowMe => true,
+            CenterOfAttention::RagePowder => {
+                !attacker.has_type("grass") && !attacker.has_ability("overcoat")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{test_ability, test_doubles_battle, test_move};
+
+    fn create_doubles_battle() -> Battle {
+        test_doubles_battle(&[
+            test_move("tackle", "normal", "physical", Some(40), "selected-pokemon"),
+            test_move(
+                "thunderbolt",
+                "electric",
+                "special",
+                Some(90),
+                "selected-pokemon",
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_follow_me_redirects_single_target_move() {
+        let mut battle = create_doubles_battle();
+        battle
+            .pokemon_at_mut(SlotPosition::new(1, 1))
+            .unwrap()
+            .volatiles
+            .center_of_attention = Some(CenterOfAttention::FollowMe);
+        let user = SlotPosition::new(0, 0);
+        let move_slot = battle.pokemon_at(user).unwrap().moves[0].clone();
+
+        let targets =
+            RedirectionResolver::redirect(&battle, user, &move_slot, vec![SlotPosition::new(1, 0)]);
+        assert_eq!(targets, vec![SlotPosition::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_lightning_rod_draws_electric_move() {
+        let mut battle = create_doubles_battle();
+        battle
+            .pokemon_at_mut(SlotPosition::new(0, 1))
+            .unwrap()
+            .active_ability = test_ability("lightning-rod").into();
+        let user = SlotPosition::new(0, 0);
+        let move_slot = battle.pokemon_at(user).unwrap().moves[1].clone();
+
+        let targets =
+            RedirectionResolver::redirect(&battle, user, &move_slot, vec![SlotPosition::new(1, 0)]);
+        assert_eq!(targets, vec![SlotPosition::new(0, 1)]);
+    }
+}
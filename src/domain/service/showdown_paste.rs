@@ -0,0 +1,594 @@
+use crate::domain::model::ability::Ability;
+use crate::domain::model::active_pokemon::DEFAULT_LEVEL;
+use crate::domain::model::item::Item;
+use crate::domain::model::r#move::Move;
+use crate::domain::model::nature::Nature;
+use crate::domain::model::poke_type::PokeType;
+use crate::domain::model::pokemon::Pokemon;
+use crate::domain::model::pokemon_build::{Gender, MAX_IV, MoveChoice, PokemonBuild, StatSpread};
+use crate::domain::model::pokemon_species::PokemonSpecies;
+use crate::domain::model::stats::StatName;
+use crate::domain::model::team::Team;
+use std::collections::HashMap;
+use std::fmt;
+
+/// 能力の略称（Showdown の表記）
+const STAT_LABELS: [(StatName, &str); 6] = [
+    (StatName::Hp, "HP"),
+    (StatName::Attack, "Atk"),
+    (StatName::Defense, "Def"),
+    (StatName::SpecialAttack, "SpA"),
+    (StatName::SpecialDefense, "SpD"),
+    (StatName::Speed, "Spe"),
+];
+
+/// 読み込んでも型に反映しない行の見出し
+const IGNORED_KEYS: [&str; 5] = [
+    "Happiness",
+    "Pokeball",
+    "Dynamax Level",
+    "Gigantamax",
+    "Hidden Power",
+];
+
+/// 英語の名前を探す言語コード
+const ENGLISH: &str = "en";
+
+/// Showdown でのフォルム名の書き方が data/ のスラッグと異なるもの（スラッグの末尾, Showdown の末尾）
+const FORM_SUFFIXES: [(&str, &str); 4] = [
+    ("-female", "-f"),
+    ("-male", "-m"),
+    ("-breed", ""),
+    ("-mask", ""),
+];
+
+/// 表示名を data/ のスラッグに変換する辞書。
+/// 英数字以外を除き小文字にした名前で引くため、"Choice Scarf" も "choice-scarf" も同じスラッグになる。
+/// 書き出しには英語の名前を使い、英語の名前がなければスラッグから作る
+#[derive(Debug, Clone, Default)]
+struct NameTable {
+    slugs: HashMap<String, String>,
+    english: HashMap<String, String>,
+}
+
+impl NameTable {
+    fn key(name: &str) -> String {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    fn add<'a>(
+        &mut self,
+        slug: &str,
+        english: Option<&str>,
+        names: impl IntoIterator<Item = &'a str>,
+    ) {
+        self.slugs.insert(Self::key(slug), slug.to_string());
+        if let Some(english) = english {
+            self.alias(slug, english);
+            self.english.insert(slug.to_string(), english.to_string());
+        }
+        for name in names {
+            self.alias(slug, name);
+        }
+    }
+
+    /// スラッグや他の名前と重ならない場合だけ別名を登録する
+    fn alias(&mut self, slug: &str, name: &str) {
+        self.slugs
+            .entry(Self::key(name))
+            .or_insert_with(|| slug.to_string());
+    }
+
+    fn resolve(&self, name: &str) -> Option<String> {
+        self.slugs.get(&Self::key(name)).cloned()
+    }
+
+    fn display(&self, slug: &str, separator: &str) -> String {
+        self.english
+            .get(slug)
+            .cloned()
+            .unwrap_or_else(|| display_name(slug, separator))
+    }
+}
+
+/// Showdown の表記と data/ のスラッグを対応付ける名前の辞書
+#[derive(Debug, Clone, Default)]
+pub struct ShowdownNames {
+    species: NameTable,
+    items: NameTable,
+    abilities: NameTable,
+    moves: NameTable,
+    types: NameTable,
+}
+
+impl ShowdownNames {
+    /// ポケモンは種族の英語名を既定の姿（種族で最も番号の小さい姿）に対応付け、
+    /// それ以外の姿は「種族名-フォルム名」（"Rotom-Wash"、"Indeedee-F"）で引けるようにする
+    pub fn new(
+        pokemons: &[Pokemon],
+        species: &[PokemonSpecies],
+        items: &[Item],
+        abilities: &[Ability],
+        moves: &[Move],
+        types: &[PokeType],
+    ) -> Self {
+        let mut names = Self::default();
+        for pokemon in pokemons {
+            let species_name = pokemon.species.as_deref().unwrap_or(&pokemon.name);
+            let Some(species) = species.iter().find(|species| species.name == species_name) else {
+                names.species.add(&pokemon.name, None, []);
+                continue;
+            };
+            let english = species
+                .names
+                .iter()
+                .find(|name| name.language == ENGLISH)
+                .map_or_else(|| display_name(species_name, " "), |name| name.name.clone());
+            let is_default = pokemons
+                .iter()
+                .filter(|other| other.species.as_deref() == Some(species_name))
+                .all(|other| other.id >= pokemon.id);
+            let shown = if is_default {
+                english
+            } else {
+                let form = pokemon
+                    .name
+                    .strip_prefix(species_name)
+                    .unwrap_or(&pokemon.name);
+                let form =
+                    FORM_SUFFIXES
+                        .iter()
+                        .fold(form.to_string(), |form, (suffix, showdown)| {
+                            match form.strip_suffix(suffix) {
+                                Some(rest) => format!("{}{}", rest, showdown),
+                                None => form,
+                            }
+                        });
+                format!("{}{}", english, display_name(&form, "-"))
+            };
+            names.species.add(&pokemon.name, Some(&shown), []);
+        }
+        for item in items {
+            let english = item.names.iter().find(|n| n.language.name == ENGLISH);
+            names.items.add(
+                &item.name,
+                english.map(|n| n.name.as_str()),
+                item.names.iter().map(|n| n.name.as_str()),
+            );
+        }
+        for ability in abilities {
+            let english = ability.names.iter().find(|n| n.language == ENGLISH);
+            names.abilities.add(
+                &ability.name,
+                english.map(|n| n.name.as_str()),
+                ability.names.iter().map(|n| n.name.as_str()),
+            );
+        }
+        for move_data in moves {
+            let english = move_data.names.iter().find(|n| n.language == ENGLISH);
+            names.moves.add(
+                &move_data.name,
+                english.map(|n| n.name.as_str()),
+                move_data.names.iter().map(|n| n.name.as_str()),
+            );
+        }
+        for poke_type in types {
+            let english = poke_type.names.iter().find(|n| n.language.name == ENGLISH);
+            names.types.add(
+                &poke_type.name,
+                english.map(|n| n.name.as_str()),
+                poke_type.names.iter().map(|n| n.name.as_str()),
+            );
+        }
+        names
+    }
+}
+
+/// 貼り付けられたテキストのどこが読めなかったか
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasteErrorKind {
+    UnknownSpecies(String),
+    UnknownItem(String),
+    UnknownAbility(String),
+    UnknownMove(String),
+    UnknownType(String),
+    UnknownNature(String),
+    /// 「252 Atk」の形になっていない能力値
+    InvalidStat(String),
+    /// 特性の行がない
+    MissingAbility,
+    /// 解釈できない行
+    InvalidLine(String),
+}
+
+/// テキストを読み込めなかった場合のエラー（行番号は1始まり）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasteError {
+    pub line: usize,
+    pub kind: PasteErrorKind,
+}
+
+impl fmt::Display for PasteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}行目: ", self.line)?;
+        match &self.kind {
+            PasteErrorKind::UnknownSpecies(name) => write!(f, "ポケモンが見つかりません: {}", name),
+            PasteErrorKind::UnknownItem(name) => write!(f, "道具が見つかりません: {}", name),
+            PasteErrorKind::UnknownAbility(name) => write!(f, "特性が見つかりません: {}", name),
+            PasteErrorKind::UnknownMove(name) => write!(f, "技が見つかりません: {}", name),
+            PasteErrorKind::UnknownType(name) => write!(f, "タイプが見つかりません: {}", name),
+            PasteErrorKind::UnknownNature(name) => write!(f, "性格が見つかりません: {}", name),
+            PasteErrorKind::InvalidStat(text) => write!(f, "能力値を読めません: {}", text),
+            PasteErrorKind::MissingAbility => write!(f, "特性の指定がありません"),
+            PasteErrorKind::InvalidLine(text) => write!(f, "解釈できない行です: {}", text),
+        }
+    }
+}
+
+/// Pokémon Showdown のチームのテキスト（空行区切りで1体ずつ）を読み込み、パーティにする。
+/// ここでは名前の解決と書式だけを確かめ、覚える技などの検証はパーティの編集側で行う
+pub fn parse_team(name: &str, text: &str, names: &ShowdownNames) -> Result<Team, PasteError> {
+    let mut members = Vec::new();
+    let mut block: Vec<(usize, &str)> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !block.is_empty() {
+                members.push(parse_member(&block, names)?);
+                block.clear();
+            }
+        } else {
+            block.push((index + 1, line));
+        }
+    }
+    if !block.is_empty() {
+        members.push(parse_member(&block, names)?);
+    }
+    Ok(Team::new(name, members))
+}
+
+fn parse_member(
+    block: &[(usize, &str)],
+    names: &ShowdownNames,
+) -> Result<PokemonBuild, PasteError> {
+    let (header_line, header) = block[0];
+    let error = |line: usize, kind: PasteErrorKind| PasteError { line, kind };
+
+    // 「ニックネーム (ポケモン) (M) @ 道具」
+    let (left, item) = match header.split_once(" @ ") {
+        Some((left, item)) => (left.trim(), Some(item.trim())),
+        None => (header, None),
+    };
+    let (left, gender) = if let Some(left) = left.strip_suffix(" (M)") {
+        (left, Gender::Male)
+    } else if let Some(left) = left.strip_suffix(" (F)") {
+        (left, Gender::Female)
+    } else {
+        (left, Gender::Genderless)
+    };
+    let (nickname, species_name) = match left
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+    {
+        Some((nickname, species)) => (Some(nickname.trim()), species.trim()),
+        None => (None, left.trim()),
+    };
+    let species = names.species.resolve(species_name).ok_or_else(|| {
+        error(
+            header_line,
+            PasteErrorKind::UnknownSpecies(species_name.to_string()),
+        )
+    })?;
+
+    let mut build = PokemonBuild::new(&species, "");
+    build.nickname = nickname.map(str::to_string);
+    build.gender = gender;
+    if let Some(item) = item {
+        build.held_item =
+            Some(names.items.resolve(item).ok_or_else(|| {
+                error(header_line, PasteErrorKind::UnknownItem(item.to_string()))
+            })?);
+    }
+
+    let mut has_ability = false;
+    for &(line_number, line) in &block[1..] {
+        if let Some(move_name) = line.strip_prefix('-') {
+            let move_name = move_name.trim();
+            let slug = names.moves.resolve(move_name).ok_or_else(|| {
+                error(
+                    line_number,
+                    PasteErrorKind::UnknownMove(move_name.to_string()),
+                )
+            })?;
+            build.moves.push(MoveChoice::new(&slug));
+        } else if let Some(nature) = line.strip_suffix(" Nature") {
+            build.nature = Nature::from_name(nature).ok_or_else(|| {
+                error(
+                    line_number,
+                    PasteErrorKind::UnknownNature(nature.to_string()),
+                )
+            })?;
+        } else if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            match key.trim() {
+                "Ability" => {
+                    build.ability = names.abilities.resolve(value).ok_or_else(|| {
+                        error(
+                            line_number,
+                            PasteErrorKind::UnknownAbility(value.to_string()),
+                        )
+                    })?;
+                    has_ability = true;
+                }
+                "Tera Type" => {
+                    build.tera_type = Some(names.types.resolve(value).ok_or_else(|| {
+                        error(line_number, PasteErrorKind::UnknownType(value.to_string()))
+                    })?);
+                }
+                "Level" => {
+                    build.level = value.parse().map_err(|_| {
+                        error(line_number, PasteErrorKind::InvalidLine(line.to_string()))
+                    })?;
+                }
+                "Shiny" => build.shiny = value.eq_ignore_ascii_case("yes"),
+                "EVs" => build.evs = parse_spread(value, StatSpread::default(), line_number)?,
+                "IVs" => build.ivs = parse_spread(value, StatSpread::uniform(MAX_IV), line_number)?,
+                key if IGNORED_KEYS.contains(&key) => {}
+                _ => {
+                    return Err(error(
+                        line_number,
+                        PasteErrorKind::InvalidLine(line.to_string()),
+                    ));
+                }
+            }
+        } else {
+            return Err(error(
+                line_number,
+                PasteErrorKind::InvalidLine(line.to_string()),
+            ));
+        }
+    }
+    if !has_ability {
+        return Err(error(header_line, PasteErrorKind::MissingAbility));
+    }
+    Ok(build)
+}
+
+/// 「252 Atk / 4 SpD / 252 Spe」を読み、書かれていない能力は base の値のままにする
+fn parse_spread(text: &str, base: StatSpread, line: usize) -> Result<StatSpread, PasteError> {
+    let mut spread = base;
+    for part in text.split('/') {
+        let invalid = || PasteError {
+            line,
+            kind: PasteErrorKind::InvalidStat(part.trim().to_string()),
+        };
+        let (value, label) = part.trim().split_once(' ').ok_or_else(invalid)?;
+        let value: u32 = value.parse().map_err(|_| invalid())?;
+        let (stat, _) = STAT_LABELS
+            .iter()
+            .find(|(_, known)| known.eq_ignore_ascii_case(label.trim()))
+            .ok_or_else(invalid)?;
+        spread.set(stat, value);
+    }
+    Ok(spread)
+}
+
+/// スラッグを単語ごとに大文字で始めた表示名にする（"choice-scarf" → "Choice Scarf"）
+fn display_name(slug: &str, separator: &str) -> String {
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 書かれた能力だけを「252 Atk / 4 SpD」の形に並べる
+fn format_spread(spread: &StatSpread, default: u32) -> Option<String> {
+    let parts: Vec<String> = STAT_LABELS
+        .iter()
+        .filter(|(stat, _)| spread.get(stat) != default)
+        .map(|(stat, label)| format!("{} {}", spread.get(stat), label))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" / "))
+}
+
+/// パーティを Pokémon Showdown のチームのテキストに書き出す。
+/// 名前は図鑑データの英語名を使う（"Landorus"、"U-turn"）。英語名がなければスラッグから作り、
+/// ポケモンはフォルム名をハイフンでつなぎ（"Rotom-Wash"）、それ以外は単語を空白で区切る（"Choice Scarf"）
+pub fn export_team(team: &Team, names: &ShowdownNames) -> String {
+    team.members
+        .iter()
+        .map(|build| export_member(build, names))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn export_member(build: &PokemonBuild, names: &ShowdownNames) -> String {
+    let species = names.species.display(&build.species, "-");
+    let mut header = match &build.nickname {
+        Some(nickname) => format!("{} ({})", nickname, species),
+        None => species,
+    };
+    match build.gender {
+        Gender::Male => header.push_str(" (M)"),
+        Gender::Female => header.push_str(" (F)"),
+        Gender::Genderless => {}
+    }
+    if let Some(item) = &build.held_item {
+        header.push_str(&format!(" @ {}", names.items.display(item, " ")));
+    }
+
+    let mut lines = vec![
+        header,
+        format!("Ability: {}", names.abilities.display(&build.ability, " ")),
+    ];
+    if build.level != DEFAULT_LEVEL {
+        lines.push(format!("Level: {}", build.level));
+    }
+    if build.shiny {
+        lines.push("Shiny: Yes".to_string());
+    }
+    if let Some(tera_type) = &build.tera_type {
+        lines.push(format!(
+            "Tera Type: {}",
+            names.types.display(tera_type, " ")
+        ));
+    }
+    if let Some(evs) = format_spread(&build.evs, 0) {
+        lines.push(format!("EVs: {}", evs));
+    }
+    lines.push(format!("{} Nature", display_name(build.nature.name(), " ")));
+    if let Some(ivs) = format_spread(&build.ivs, MAX_IV) {
+        lines.push(format!("IVs: {}", ivs));
+    }
+    for choice in &build.moves {
+        lines.push(format!("- {}", names.moves.display(&choice.name, " ")));
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::r#move::Name as MoveName;
+    use crate::domain::test_support::{test_ability, test_item, test_move, test_pokemon};
+
+    fn species(name: &str, english: &str) -> PokemonSpecies {
+        serde_json::from_str(&format!(
+            r#"{{ "id": 1, "name": "{}", "color": {{ "name": "blue", "url": "" }},
+                 "egg_groups": [], "flavor_text_entries": [],
+                 "names": [{{ "name": "{}", "language": {{ "name": "en", "url": "" }} }}],
+                 "is_legendary": false, "is_mythical": false, "is_baby": false,
+                 "capture_rate": 45, "gender_rate": 4, "evolves_from_species": null,
+                 "pokedex_numbers": [] }}"#,
+            name, english
+        ))
+        .unwrap()
+    }
+
+    fn form(name: &str, id: u32, species: &str) -> Pokemon {
+        let mut pokemon = test_pokemon(name, &["normal"], 100, 100);
+        pokemon.id = id;
+        pokemon.species = Some(species.to_string());
+        pokemon
+    }
+
+    fn names() -> ShowdownNames {
+        let fire: PokeType = serde_json::from_str(
+            r#"{ "id": 10, "name": "fire", "names": [],
+                 "damage_relations": { "no_damage_to": [], "half_damage_to": [],
+                 "double_damage_to": [], "no_damage_from": [], "half_damage_from": [],
+                 "double_damage_from": [] } }"#,
+        )
+        .unwrap();
+        let mut u_turn = test_move("u-turn", "bug", "physical", Some(70), "selected-pokemon");
+        u_turn.names = vec![MoveName {
+            name: "U-turn".to_string(),
+            language: ENGLISH.to_string(),
+        }];
+        ShowdownNames::new(
+            &[
+                form("garchomp", 445, "garchomp"),
+                form("landorus-incarnate", 645, "landorus"),
+                form("landorus-therian", 10021, "landorus"),
+                form("indeedee-male", 876, "indeedee"),
+                form("indeedee-female", 10186, "indeedee"),
+                form("rotom", 479, "rotom"),
+                form("rotom-wash", 10009, "rotom"),
+            ],
+            &[
+                species("garchomp", "Garchomp"),
+                species("landorus", "Landorus"),
+                species("indeedee", "Indeedee"),
+                species("rotom", "Rotom"),
+            ],
+            &[test_item("choice-scarf")],
+            &[test_ability("rough-skin")],
+            &[
+                test_move(
+                    "earthquake",
+                    "ground",
+                    "physical",
+                    Some(100),
+                    "all-other-pokemon",
+                ),
+                u_turn,
+            ],
+            &[fire],
+        )
+    }
+
+    #[test]
+    fn test_parse_and_export_round_trip() {
+        let text = "Chompy (Garchomp) (M) @ Choice Scarf\n\
+                    Ability: Rough Skin\n\
+                    Tera Type: Fire\n\
+                    EVs: 252 Atk / 4 SpD / 252 Spe\n\
+                    Jolly Nature\n\
+                    - Earthquake\n\
+                    - U-turn\n";
+        let names = names();
+
+        let team = parse_team("sand", text, &names).unwrap();
+        let build = &team.members[0];
+        assert_eq!(build.species, "garchomp");
+        assert_eq!(build.nickname.as_deref(), Some("Chompy"));
+        assert_eq!(build.held_item.as_deref(), Some("choice-scarf"));
+        assert_eq!(build.ability, "rough-skin");
+        assert_eq!(build.tera_type.as_deref(), Some("fire"));
+        assert_eq!(build.nature, Nature::Jolly);
+        assert_eq!(build.evs.speed, 252);
+        assert_eq!(build.moves[1].name, "u-turn");
+        let exported = export_team(&team, &names);
+        assert!(exported.contains("- U-turn\n"));
+        assert_eq!(parse_team("sand", &exported, &names), Ok(team));
+
+        let error = parse_team("sand", "Garchomp\nAbility: Rough Skin\n- Surf\n", &names);
+        assert_eq!(
+            error,
+            Err(PasteError {
+                line: 3,
+                kind: PasteErrorKind::UnknownMove("Surf".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_resolve_showdown_species_names() {
+        let names = names();
+        let resolve = |text: &str| {
+            parse_team(
+                "forms",
+                &format!("{}\nAbility: Rough Skin\n- Earthquake\n", text),
+                &names,
+            )
+            .map(|team| team.members[0].species.clone())
+        };
+
+        assert_eq!(resolve("Landorus").as_deref(), Ok("landorus-incarnate"));
+        assert_eq!(
+            resolve("Landorus-Therian").as_deref(),
+            Ok("landorus-therian")
+        );
+        assert_eq!(resolve("Indeedee-F").as_deref(), Ok("indeedee-female"));
+        assert_eq!(resolve("Indeedee").as_deref(), Ok("indeedee-male"));
+        assert_eq!(resolve("Rotom-Wash").as_deref(), Ok("rotom-wash"));
+
+        let team = parse_team(
+            "forms",
+            "Landorus\nAbility: Rough Skin\n- Earthquake\n\nIndeedee-F\nAbility: Rough Skin\n- Earthquake\n",
+            &names,
+        )
+        .unwrap();
+        let exported = export_team(&team, &names);
+        assert!(exported.starts_with("Landorus\n"));
+        assert!(exported.contains("\nIndeedee-F\n"));
+    }
+}
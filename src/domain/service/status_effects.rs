@@ -0,0 +1,55 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::status::StatusCondition;
+
+/// 状態異常のかかりやすさに関するルール
+pub struct StatusEffects;
+
+impl StatusEffects {
+    /// PokeAPI の ailment 名から状態異常へ変換する（状態異常以外は None）
+    pub fn from_ailment(ailment: &str) -> Option<StatusCondition> {
+        match ailment {
+            "poison" => Some(StatusCondition::Poison),
+            "paralysis" => Some(StatusCondition::Paralysis),
+            "burn" => Some(StatusCondition::Burn),
+            "freeze" => Some(StatusCondition::Freeze),
+            "sleep" => Some(StatusCondition::Sleep),
+            _ => None,
+        }
+    }
+
+    /// すでに状態異常でなく、タイプによる無効もない場合にかかる
+    pub fn can_inflict(pokemon: &ActivePokemon, status: &StatusCondition) -> bool {
+        if pokemon.is_fainted() || pokemon.status.is_some() {
+            return false;
+        }
+        let immune_types: &[&str] = match status {
+            StatusCondition::Poison => &["poison", "steel"],
+            StatusCondition::Paralysis => &["electric"],
+            StatusCondition::Burn => &["fire"],
+            StatusCondition::Freeze => &["ice"],
+            StatusCondition::Sleep => &[],
+        };
+        !immune_types
+            .iter()
+            .any(|type_name| pokemon.has_type(type_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::test_active_pokemon;
+
+    #[test]
+    fn test_can_inflict() {
+        let fire = test_active_pokemon("fire", &["fire"], &[]);
+        let normal = test_active_pokemon("normal", &["normal"], &[]);
+
+        assert_eq!(
+            StatusEffects::from_ailment("burn"),
+            Some(StatusCondition::Burn)
+        );
+        assert!(!StatusEffects::can_inflict(&fire, &StatusCondition::Burn));
+        assert!(StatusEffects::can_inflict(&normal, &StatusCondition::Burn));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::SlotPosition;
+use crate::domain::model::battle_format::BattleFormat;
+use crate::domain::model::battle_rng::BattleRng;
+use crate::domain::model::r#move::MoveTarget;
+
+/// 技の対象選択に関するエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetError {
+    /// 対象を選ぶ技なのに対象が指定されていない
+    TargetRequired,
+    /// 対象を選ばない技に対象が指定された
+    TargetNotAllowed,
+    /// 技の対象範囲に含まれない位置が指定された
+    InvalidTarget(SlotPosition),
+}
+
+/// MoveTarget に基づく技の対象の検証と解決
+pub struct TargetResolver;
+
+impl TargetResolver {
+    /// 行動選択時に対象の指定が必要か（シングルでは常に自動で決まる）
+    pub fn requires_target(format: BattleFormat, move_target: &MoveTarget) -> bool {
+        format.is_doubles()
+            && matches!(
+                move_target,
+                MoveTarget::SelectedPokemon | MoveTarget::UserOrAlly
+            )
+    }
+
+    /// 行動選択時の対象指定を検証する
+    pub fn validate(
+        format: BattleFormat,
+        user: SlotPosition,
+        move_target: &MoveTarget,
+        target: Option<SlotPosition>,
+    ) -> Result<(), TargetError> {
+        let Some(target) = target else {
+            return if Self::requires_target(format, move_target) {
+                Err(TargetError::TargetRequired)
+            } else {
+                Ok(())
+            };
+        };
+
+        let in_range = target.side < 2 && target.slot < format.active_slots();
+        let allowed = match move_target {
+            MoveTarget::SelectedPokemon => target != user,
+            MoveTarget::UserOrAlly => target.side == user.side,
+            _ => return Err(TargetError::TargetNotAllowed),
+        };
+        if in_range && allowed {
+            Ok(())
+        } else {
+            Err(TargetError::InvalidTarget(target))
+        }
+    }
+
+    /// 技の実行時点での実際の対象を解決する。
+    /// 選んだ相手がひんしの場合は残った相手に対象を移し、味方がひんしの場合は対象なしとなる
+    pub fn resolve(
+        battle: &Battle,
+        user: SlotPosition,
+        move_target: &MoveTarget,
+        target: Option<SlotPosition>,
+        rng: &mut dyn BattleRng,
+    ) -> Vec<SlotPosition> {
+        let alive = |positions: Vec<SlotPosition>| -> Vec<SlotPosition> {
+            positions
+                .into_iter()
+                .filter(|position| battle.is_alive(*position))
+                .collect()
+        };
+        let opponents = alive(battle.opponent_positions(user));
+        let allies = alive(battle.ally_positions(user));
+
+        match move_target {
+            MoveTarget::SelectedPokemon => {
+                let chosen = target.or_else(|| battle.opponent_positions(user).first().copied());
+                match chosen {
+                    Some(chosen) if battle.is_alive(chosen) => vec![chosen],
+                    Some(chosen) if chosen.is_opponent_of(&user) => {
+                        opponents.into_iter().take(1).collect()
+                    }
+                    _ => vec![],
+                }
+            }
+            MoveTarget::UserOrAlly => {
+                let chosen = target.unwrap_or(user);
+                if battle.is_alive(chosen) {
+                    vec![chosen]
+                } else {
+                    vec![]
+                }
+            }
+            MoveTarget::RandomOpponent if opponents.len() > 1 => {
+                let index = rng.next_below(opponents.len() as u32) as usize;
+                vec![opponents[index]]
+            }
+            MoveTarget::RandomOpponent | MoveTarget::SpecificMove => {
+                opponents.into_iter().take(1).collect()
+            }
+            MoveTarget::AllOpponents | MoveTarget::AllFoes => opponents,
+            MoveTarget::AllOtherPokemon => allies.into_iter().chain(opponents).collect(),
+            MoveTarget::AllAllies => allies,
+            MoveTarget::UserAndAllies => std::iter::once(user).chain(allies).collect(),
+            MoveTarget::AllPokemon | MoveTarget::AllPokemonMaybeOthers => battle.active_positions(),
+            MoveTarget::User | MoveTarget::UserField | MoveTarget::EntireBattlefield => {
+                vec![user]
+            }
+            MoveTarget::OpponentsField | MoveTarget::AllOpponentsField => vec![],
+        }
+    }
+
+    /// 実行時に複数の対象がいる全体技か（ダメージ0.75倍の対象）
+    pub fn is_spread(move_target: &MoveTarget, targets: &[SlotPosition]) -> bool {
+        targets.len() > 1
+            && matches!(
+                move_target,
+                MoveTarget::AllOpponents | MoveTarget::AllOtherPokemon | MoveTarget::AllFoes
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{ScriptedRng, test_doubles_battle};
+
+    #[test]
+    fn test_validate_doubles_target() {
+        let user = SlotPosition::new(0, 0);
+        let format = BattleFormat::Doubles;
+
+        assert!(
+            TargetResolver::validate(
+                format,
+                user,
+                &MoveTarget::SelectedPokemon,
+                Some(SlotPosition::new(1, 1))
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_resolve_spread_targets() {
+        let battle = test_doubles_battle(&[]);
+        let user = SlotPosition::new(0, 0);
+
+        let targets = TargetResolver::resolve(
+            &battle,
+            user,
+            &MoveTarget::AllOtherPokemon,
+            None,
+            &mut ScriptedRng::new(vec![]),
+        );
+        assert_eq!(targets.len(), 3);
+        assert!(TargetResolver::is_spread(
+            &MoveTarget::AllOtherPokemon,
+            &targets
+        ));
+    }
+}
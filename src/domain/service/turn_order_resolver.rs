@@ -0,0 +1,146 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::{BattleAction, ChosenAction};
+use crate::domain::model::battle_rng::BattleRng;
+use crate::domain::model::stats::StatName;
+use crate::domain::model::status::StatusCondition;
+use std::cmp::Reverse;
+
+/// 交代は技より先に行われる
+const SWITCH_PRIORITY: i32 = 7;
+
+/// ターン内の行動順を決める
+pub struct TurnOrderResolver;
+
+impl TurnOrderResolver {
+    /// 優先度 → すばやさの順に並べる。同速の行動同士は乱数で順番を決める
+    pub fn sort(
+        battle: &Battle,
+        mut actions: Vec<ChosenAction>,
+        rng: &mut dyn BattleRng,
+    ) -> Vec<ChosenAction> {
+        let order_key = |action: &ChosenAction| {
+            (
+                Reverse(Self::action_priority(battle, action)),
+                Reverse(Self::action_speed(battle, action)),
+            )
+        };
+        actions.sort_by_key(order_key);
+
+        let mut start = 0;
+        while start < actions.len() {
+            let key = order_key(&actions[start]);
+            let end = start
+                + actions[start..]
+                    .iter()
+                    .take_while(|action| order_key(action) == key)
+                    .count();
+            // 同速グループ内を Fisher-Yates でシャッフルする
+            for index in (start + 1..end).rev() {
+                let swap_with = start + rng.next_below((index - start + 1) as u32) as usize;
+                actions.swap(index, swap_with);
+            }
+            start = end;
+        }
+        actions
+    }
+
+    pub fn action_priority(battle: &Battle, action: &ChosenAction) -> i32 {
+        match &action.action {
+            BattleAction::Switch { .. } => SWITCH_PRIORITY,
+            BattleAction::UseMove { move_index, .. } => battle
+                .pokemon_at(action.user)
+                .and_then(|pokemon| pokemon.moves.get(*move_index))
+                .map_or(0, |move_slot| move_slot.priority),
+            BattleAction::Struggle | BattleAction::Recharge => 0,
+        }
+    }
+
+    pub fn action_speed(battle: &Battle, action: &ChosenAction) -> u32 {
+        battle
+            .pokemon_at(action.user)
+            .map_or(0, Self::effective_speed)
+    }
+
+    /// ランク補正とまひの半減を反映したすばやさ
+    pub fn effective_speed(pokemon: &ActivePokemon) -> u32 {
+        let speed = pokemon.effective_stat(StatName::Speed);
+        if pokemon.status == Some(StatusCondition::Paralysis) {
+            speed / 2
+        } else {
+            speed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_action::SlotPosition;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::test_support::{ScriptedRng, test_active_pokemon, test_move};
+
+    #[test]
+    fn test_sort_by_priority_and_speed() {
+        let tackle = test_move("tackle", "normal", "physical", Some(40), "selected-pokemon");
+        let mut fast = test_active_pokemon("fast", &["normal"], &[tackle]);
+        fast.speed = 150;
+        let slow = test_active_pokemon("slow", &["normal"], &[]);
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), vec![slow.clone(), slow.clone()], 1),
+            BattleSide::new("opponent".to_string(), vec![fast], 1),
+        );
+
+        let tackle_action = ChosenAction::new(
+            SlotPosition::new(1, 0),
+            BattleAction::UseMove {
+                move_index: 0,
+                target: Some(SlotPosition::new(0, 0)),
+                terastallize: false,
+            },
+        );
+        let switch_action = ChosenAction::new(
+            SlotPosition::new(0, 0),
+            BattleAction::Switch { party_index: 1 },
+        );
+
+        let sorted = TurnOrderResolver::sort(
+            &battle,
+            vec![tackle_action, switch_action],
+            &mut ScriptedRng::new(vec![]),
+        );
+        assert_eq!(sorted[0].user, SlotPosition::new(0, 0));
+        assert_eq!(sorted[1].user, SlotPosition::new(1, 0));
+    }
+
+    #[test]
+    fn test_speed_tie_uses_rng() {
+        let tackle = test_move("tackle", "normal", "physical", Some(40), "selected-pokemon");
+        let pokemon = test_active_pokemon("testmon", &["normal"], &[tackle]);
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), vec![pokemon.clone()], 1),
+            BattleSide::new("opponent".to_string(), vec![pokemon], 1),
+        );
+        let actions: Vec<ChosenAction> = (0..2)
+            .map(|side| {
+                ChosenAction::new(
+                    SlotPosition::new(side, 0),
+                    BattleAction::UseMove {
+                        move_index: 0,
+                        target: None,
+                        terastallize: false,
+                    },
+                )
+            })
+            .collect();
+
+        let kept =
+            TurnOrderResolver::sort(&battle, actions.clone(), &mut ScriptedRng::new(vec![1]));
+        assert_eq!(kept[0].user.side, 0);
+        let swapped = TurnOrderResolver::sort(&battle, actions, &mut ScriptedRng::new(vec![0]));
+        assert_eq!(swapped[0].user.side, 1);
+    }
+}
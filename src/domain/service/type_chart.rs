@@ -0,0 +1,87 @@
+use crate::domain::model::common::PokemonTypeName;
+use crate::domain::model::poke_type::PokeType;
+use std::collections::HashMap;
+
+/// タイプ相性表。PokeAPI の damage_relations から構築する
+#[derive(Debug, Clone, Default)]
+pub struct TypeChart {
+    /// (攻撃タイプ, 防御タイプ) -> 倍率。記載のない組み合わせは等倍
+    multipliers: HashMap<(String, String), f64>,
+}
+
+impl TypeChart {
+    pub fn new(types: &[PokeType]) -> Self {
+        let mut multipliers = HashMap::new();
+        for poke_type in types {
+            let relations = &poke_type.damage_relations;
+            let entries = [
+                (&relations.double_damage_to, 2.0),
+                (&relations.half_damage_to, 0.5),
+                (&relations.no_damage_to, 0.0),
+            ];
+            for (defenders, multiplier) in entries {
+                for defender in defenders {
+                    multipliers.insert((poke_type.name.clone(), defender.name.clone()), multiplier);
+                }
+            }
+        }
+        Self { multipliers }
+    }
+
+    /// 単タイプに対する倍率
+    pub fn multiplier(&self, attack_type: &str, defense_type: &str) -> f64 {
+        self.multipliers
+            .get(&(attack_type.to_string(), defense_type.to_string()))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// 複合タイプを含めた最終的な倍率（0, 0.25, 0.5, 1, 2, 4）
+    pub fn effectiveness(&self, attack_type: &str, defender_types: &[PokemonTypeName]) -> f64 {
+        defender_types
+            .iter()
+            .map(|defense_type| self.multiplier(attack_type, &defense_type.name))
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_types() -> Vec<PokeType> {
+        let json_data = r#"
+        [
+            {
+                "id": 13,
+                "name": "electric",
+                "damage_relations": {
+                    "no_damage_to": [{ "name": "ground", "url": "" }],
+                    "half_damage_to": [{ "name": "grass", "url": "" }],
+                    "double_damage_to": [{ "name": "water", "url": "" }, { "name": "flying", "url": "" }],
+                    "no_damage_from": [],
+                    "half_damage_from": [],
+                    "double_damage_from": []
+                },
+                "names": []
+            }
+        ]
+        "#;
+        serde_json::from_str(json_data).unwrap()
+    }
+
+    #[test]
+    fn test_effectiveness() {
+        let chart = TypeChart::new(&create_test_types());
+        let water_flying = vec![
+            PokemonTypeName::new("water".to_string()),
+            PokemonTypeName::new("flying".to_string()),
+        ];
+        let ground = vec![PokemonTypeName::new("ground".to_string())];
+
+        assert_eq!(chart.effectiveness("electric", &water_flying), 4.0);
+        assert_eq!(chart.effectiveness("electric", &ground), 0.0);
+        assert_eq!(chart.multiplier("electric", "grass"), 0.5);
+        assert_eq!(chart.multiplier("normal", "grass"), 1.0);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::poke_type::PokeType;
+use crate::domain::service::type_chart::TypeChart;
+
+/// 対戦で使われないタイプ（相性表の対象外）
+const EXCLUDED_TYPES: [&str; 3] = ["unknown", "shadow", "stellar"];
+
+/// 特性で無効化するタイプ
+const ABILITY_IMMUNITIES: [(&str, &str); 11] = [
+    ("levitate", "ground"),
+    ("earth-eater", "ground"),
+    ("flash-fire", "fire"),
+    ("well-baked-body", "fire"),
+    ("volt-absorb", "electric"),
+    ("lightning-rod", "electric"),
+    ("motor-drive", "electric"),
+    ("water-absorb", "water"),
+    ("storm-drain", "water"),
+    ("dry-skin", "water"),
+    ("sap-sipper", "grass"),
+];
+
+/// 1つの攻撃タイプを、パーティの各ポケモンがどれだけの倍率で受けるか
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefensiveRow {
+    pub attack_type: String,
+    /// パーティの並び順での倍率
+    pub multipliers: Vec<f64>,
+}
+
+impl DefensiveRow {
+    /// 弱点（2倍以上）のポケモンの数
+    pub fn weak_count(&self) -> usize {
+        self.multipliers.iter().filter(|m| **m > 1.0).count()
+    }
+
+    /// 半減以下（無効を除く）のポケモンの数
+    pub fn resist_count(&self) -> usize {
+        self.multipliers
+            .iter()
+            .filter(|m| **m > 0.0 && **m < 1.0)
+            .count()
+    }
+
+    /// 無効のポケモンの数
+    pub fn immune_count(&self) -> usize {
+        self.multipliers.iter().filter(|m| **m == 0.0).count()
+    }
+}
+
+/// 1つの防御タイプ（単タイプ）に、パーティの攻撃技がどれだけ通るか
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffensiveRow {
+    pub defense_type: String,
+    /// パーティの攻撃技で出せる最大の倍率（攻撃技がなければ 0）
+    pub best_multiplier: f64,
+    /// 効果抜群の技を持つポケモンの名前
+    pub super_effective_by: Vec<String>,
+}
+
+impl OffensiveRow {
+    pub fn is_covered(&self) -> bool {
+        !self.super_effective_by.is_empty()
+    }
+}
+
+/// パーティのタイプ相性の一覧。受け（攻撃タイプ × ポケモン）と攻め（攻撃技 × 防御タイプ）をまとめる
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeCoverageReport {
+    pub members: Vec<String>,
+    pub defensive: Vec<DefensiveRow>,
+    pub offensive: Vec<OffensiveRow>,
+}
+
+impl TypeCoverageReport {
+    pub fn new(party: &[ActivePokemon], types: &[PokeType]) -> Self {
+        let chart = TypeChart::new(types);
+        let type_names: Vec<&str> = types
+            .iter()
+            .map(|poke_type| poke_type.name.as_str())
+            .filter(|name| !EXCLUDED_TYPES.contains(name))
+            .collect();
+
+        let defensive = type_names
+            .iter()
+            .map(|attack_type| DefensiveRow {
+                attack_type: attack_type.to_string(),
+                multipliers: party
+                    .iter()
+                    .map(|pokemon| Self::defensive_multiplier(&chart, attack_type, pokemon))
+                    .collect(),
+            })
+            .collect();
+
+        let offensive = type_names
+            .iter()
+            .map(|defense_type| {
+                let mut best_multiplier: f64 = 0.0;
+                let mut super_effective_by = Vec::new();
+                for pokemon in party {
+                    let best = pokemon
+                        .moves
+                        .iter()
+                        .filter(|move_slot| !move_slot.damage_class.is_status())
+                        .map(|move_slot| chart.multiplier(&move_slot.type_name, defense_type))
+                        .fold(0.0, f64::max);
+                    best_multiplier = best_multiplier.max(best);
+                    if best > 1.0 {
+                        super_effective_by.push(pokemon.name.clone());
+                    }
+                }
+                OffensiveRow {
+                    defense_type: defense_type.to_string(),
+                    best_multiplier,
+                    super_effective_by,
+                }
+            })
+            .collect();
+
+        Self {
+            members: party.iter().map(|pokemon| pokemon.name.clone()).collect(),
+            defensive,
+            offensive,
+        }
+    }
+
+    /// 誰も効果抜群を取れないタイプ
+    pub fn uncovered_types(&self) -> Vec<&str> {
+        self.offensive
+            .iter()
+            .filter(|row| !row.is_covered())
+            .map(|row| row.defense_type.as_str())
+            .collect()
+    }
+
+    /// 特性による無効を含めた、攻撃タイプに対する倍率
+    fn defensive_multiplier(chart: &TypeChart, attack_type: &str, pokemon: &ActivePokemon) -> f64 {
+        let immune = ABILITY_IMMUNITIES.iter().any(|(ability, immune_type)| {
+            *immune_type == attack_type && pokemon.has_ability(ability)
+        });
+        if immune {
+            0.0
+        } else {
+            chart.effectiveness(attack_type, &pokemon.current_types())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{test_ability, test_active_pokemon, test_move};
+    use std::sync::Arc;
+
+    fn create_test_types() -> Vec<PokeType> {
+        let json_data = r#"
+        [
+            {
+                "id": 5, "name": "ground", "names": [],
+                "damage_relations": {
+                    "no_damage_to": [{ "name": "flying", "url": "" }],
+                    "half_damage_to": [],
+                    "double_damage_to": [{ "name": "electric", "url": "" }],
+                    "no_damage_from": [], "half_damage_from": [], "double_damage_from": []
+                }
+            },
+            {
+                "id": 13, "name": "electric", "names": [],
+                "damage_relations": {
+                    "no_damage_to": [{ "name": "ground", "url": "" }],
+                    "half_damage_to": [{ "name": "electric", "url": "" }],
+                    "double_damage_to": [{ "name": "flying", "url": "" }],
+                    "no_damage_from": [], "half_damage_from": [], "double_damage_from": []
+                }
+            },
+            {
+                "id": 3, "name": "flying", "names": [],
+                "damage_relations": {
+                    "no_damage_to": [],
+                    "half_damage_to": [{ "name": "electric", "url": "" }],
+                    "double_damage_to": [],
+                    "no_damage_from": [], "half_damage_from": [], "double_damage_from": []
+                }
+            },
+            {
+                "id": 10002, "name": "shadow", "names": [],
+                "damage_relations": {
+                    "no_damage_to": [], "half_damage_to": [], "double_damage_to": [],
+                    "no_damage_from": [], "half_damage_from": [], "double_damage_from": []
+                }
+            }
+        ]
+        "#;
+        serde_json::from_str(json_data).unwrap()
+    }
+
+    #[test]
+    fn test_type_coverage_report() {
+        let earthquake = test_move(
+            "earthquake",
+            "ground",
+            "physical",
+            Some(100),
+            "all-other-pokemon",
+        );
+        let mut rotom = test_active_pokemon("rotom", &["electric"], &[]);
+        rotom.active_ability = Arc::new(test_ability("levitate"));
+        let party = vec![
+            rotom,
+            test_active_pokemon("garchomp", &["ground"], &[earthquake]),
+        ];
+
+        let report = TypeCoverageReport::new(&party, &create_test_types());
+        assert_eq!(report.members, vec!["rotom", "garchomp"]);
+        assert_eq!(report.defensive.len(), 3);
+        let ground = &report.defensive[0];
+        assert_eq!(ground.multipliers, vec![0.0, 1.0]);
+        assert_eq!(ground.immune_count(), 1);
+        let electric = &report.defensive[1];
+        assert_eq!(electric.multipliers, vec![0.5, 0.0]);
+        assert_eq!(electric.resist_count(), 1);
+        assert_eq!(report.offensive[1].super_effective_by, vec!["garchomp"]);
+        assert_eq!(report.uncovered_types(), vec!["ground", "flying"]);
+    }
+}
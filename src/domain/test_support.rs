@@ -0,0 +1,239 @@
+//! 単体テスト用のフィクスチャ生成ヘルパー（data/ に依存しない）
+
+use crate::domain::model::ability::Ability;
+use crate::domain::model::active_pokemon::ActivePokemon;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_action::ChosenAction;
+use crate::domain::model::battle_format::BattleFormat;
+use crate::domain::model::battle_rng::BattleRng;
+use crate::domain::model::battle_side::BattleSide;
+use crate::domain::model::common::VersionGroup;
+use crate::domain::model::item::Item;
+use crate::domain::model::r#move::{Move, MoveName};
+use crate::domain::model::move_slot::MoveSlot;
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::model::pokemon::{
+    AbilityName, MoveLearnDetail, MoveLearnMethod, Pokemon, PokemonAbility, PokemonMove,
+};
+use crate::domain::model::pokemon_build::PokemonBuild;
+use crate::domain::service::battle_agent::{BattleAgent, is_compatible};
+use crate::domain::service::legal_actions::SlotOptions;
+use std::collections::VecDeque;
+
+pub fn test_ability(name: &str) -> Ability {
+    Ability {
+        id: 1,
+        name: name.to_string(),
+        effect_entries: vec![],
+        flavor_text_entries: vec![],
+        names: vec![],
+    }
+}
+
+pub fn test_item(name: &str) -> Item {
+    Item {
+        id: 1,
+        name: name.to_string(),
+        cost: None,
+        effect_entries: vec![],
+        flavor_text_entries: vec![],
+        names: vec![],
+    }
+}
+
+/// 全能力値の種族値を指定してポケモンを生成する
+pub fn test_pokemon(name: &str, types: &[&str], hp: u32, other_stats: u32) -> Pokemon {
+    let types: Vec<String> = types
+        .iter()
+        .enumerate()
+        .map(|(index, type_name)| {
+            format!(
+                r#"{{ "slot": {}, "type": {{ "name": "{}", "url": "" }} }}"#,
+                index + 1,
+                type_name
+            )
+        })
+        .collect();
+    let stat = |name: &str, value: u32| {
+        format!(
+            r#"{{ "base_stat": {}, "effort": 0, "stat": {{ "name": "{}", "url": "" }} }}"#,
+            value, name
+        )
+    };
+    let json = format!(
+        r#"{{
+            "id": 1, "name": "{}", "abilities": [], "forms": [], "moves": [],
+            "species": null, "types": [{}], "stats": [{}, {}, {}, {}, {}, {}]
+        }}"#,
+        name,
+        types.join(","),
+        stat("hp", hp),
+        stat("attack", other_stats),
+        stat("defense", other_stats),
+        stat("special-attack", other_stats),
+        stat("special-defense", other_stats),
+        stat("speed", other_stats),
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+/// SVで技マシンにより技を覚え、特性を1つ持つように覚える技・特性を設定する
+pub fn test_learnset(pokemon: &mut Pokemon, ability: &str, moves: &[&str]) {
+    pokemon.abilities = vec![PokemonAbility {
+        ability: AbilityName::new(ability.to_string()),
+        is_hidden: false,
+        slot: 1,
+    }];
+    pokemon.moves = moves
+        .iter()
+        .map(|name| PokemonMove {
+            r#move: MoveName::new(name.to_string()),
+            version_group_details: vec![MoveLearnDetail {
+                learn_method: MoveLearnMethod::Machine,
+                level_learned_at: 0,
+                version_group: VersionGroup::scarlet_violet(),
+            }],
+        })
+        .collect();
+}
+
+pub fn test_move(
+    name: &str,
+    type_name: &str,
+    damage_class: &str,
+    power: Option<u32>,
+    target: &str,
+) -> Move {
+    let json = format!(
+        r#"{{
+            "id": 1, "name": "{}", "accuracy": 100,
+            "damage_class": {{ "name": "{}", "url": "" }},
+            "effect_chance": null, "effect_entries": [], "flavor_text_entries": [], "names": [],
+            "power": {}, "pp": 10, "priority": 0, "stat_changes": [],
+            "target": {{ "name": "{}", "url": "" }},
+            "type": {{ "name": "{}", "url": "" }}
+        }}"#,
+        name,
+        damage_class,
+        power.map_or("null".to_string(), |p| p.to_string()),
+        target,
+        type_name,
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+/// 技を覚えたバトル用ポケモンを生成する。
+/// 計算を追いやすいよう、HPと能力の実数値はすべて100に揃える
+pub fn test_active_pokemon(name: &str, types: &[&str], moves: &[Move]) -> ActivePokemon {
+    // 型には技が1つ以上必要なため、技を指定しない場合は仮の技で作ってから外す
+    let placeholder = [test_move(
+        "tackle",
+        "normal",
+        "physical",
+        Some(40),
+        "selected-pokemon",
+    )];
+    let build_moves = if moves.is_empty() {
+        &placeholder[..]
+    } else {
+        moves
+    };
+    let move_names: Vec<&str> = build_moves.iter().map(|m| m.name.as_str()).collect();
+    let build = PokemonBuild::new(name, "no-ability").with_moves(&move_names);
+    let mut pokemon = ActivePokemon::new(
+        &build,
+        &test_pokemon(name, types, 100, 100),
+        &test_ability("no-ability"),
+        build_moves,
+    )
+    .unwrap();
+    pokemon.moves = moves.iter().map(MoveSlot::new).collect();
+    pokemon.max_hp = 100;
+    pokemon.current_hp = 100;
+    pokemon.attack = 100;
+    pokemon.defense = 100;
+    pokemon.special_attack = 100;
+    pokemon.special_defense = 100;
+    pokemon.speed = 100;
+    pokemon
+}
+
+/// 技を持たない同じポケモンを指定した数だけ並べたパーティ
+pub fn test_party(size: usize) -> Vec<ActivePokemon> {
+    (0..size)
+        .map(|_| test_active_pokemon("testmon", &["normal"], &[]))
+        .collect()
+}
+
+/// 両サイドとも、同じ技を覚えた "a" と "b" を場に出したダブルバトル
+pub fn test_doubles_battle(moves: &[Move]) -> Battle {
+    let party = vec![
+        test_active_pokemon("a", &["normal"], moves),
+        test_active_pokemon("b", &["normal"], moves),
+    ];
+    Battle::new(
+        BattleFormat::Doubles,
+        BattleSide::new("player".to_string(), party.clone(), 2),
+        BattleSide::new("opponent".to_string(), party, 2),
+    )
+}
+
+/// あらかじめ決めた出目を順に返す乱数源。
+/// 出目は next_below の結果としてそのまま使われ（範囲外は上限-1に丸める）、
+/// 使い切った後は最大値を返すため、命中100の技は当たり・急所なし・乱数最大・追加効果なしとなる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptedRng {
+    rolls: VecDeque<u32>,
+}
+
+impl ScriptedRng {
+    pub fn new(rolls: Vec<u32>) -> Self {
+        Self {
+            rolls: rolls.into(),
+        }
+    }
+}
+
+impl BattleRng for ScriptedRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rolls.pop_front().unwrap_or(u32::MAX)
+    }
+
+    fn next_below(&mut self, bound: u32) -> u32 {
+        self.next_u32().min(bound.saturating_sub(1))
+    }
+}
+
+/// あらかじめ決めた行動を順に返すエージェント。
+/// 台本を使い切った後は、各枠で最初に選べる行動を選ぶ
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedAgent {
+    turns: VecDeque<Vec<ChosenAction>>,
+}
+
+impl ScriptedAgent {
+    pub fn new(turns: Vec<Vec<ChosenAction>>) -> Self {
+        Self {
+            turns: turns.into(),
+        }
+    }
+}
+
+impl BattleAgent for ScriptedAgent {
+    fn choose_actions(&mut self, _view: &PlayerView, options: &[SlotOptions]) -> Vec<ChosenAction> {
+        if let Some(actions) = self.turns.pop_front() {
+            return actions;
+        }
+        let mut chosen = Vec::new();
+        for slot in options {
+            if let Some(action) = slot
+                .actions
+                .iter()
+                .find(|action| is_compatible(&chosen, action))
+            {
+                chosen.push(ChosenAction::new(slot.position, action.clone()));
+            }
+        }
+        chosen
+    }
+}
@@ -1,7 +1,12 @@
 pub mod file_ability_repository;
+pub mod file_battle_snapshot_repository;
+pub mod file_format_repository;
 pub mod file_item_repository;
 pub mod file_move_repository;
 pub mod file_pokemon_repository;
 pub mod file_pokemon_species_repository;
+pub mod file_replay_repository;
+pub mod file_team_repository;
 pub mod file_type_repository;
+pub mod json_file;
 pub mod json_loader;
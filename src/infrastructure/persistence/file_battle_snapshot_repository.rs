@@ -0,0 +1,107 @@
+use crate::domain::model::battle::Battle;
+use crate::domain::repository::battle_snapshot_repository::BattleSnapshotRepository;
+use crate::domain::repository::repository_error::RepositoryError;
+use crate::infrastructure::persistence::json_file::{load_json, save_json};
+use std::path::PathBuf;
+
+/// バトルの状態を保存する既定のディレクトリ
+pub const DEFAULT_SNAPSHOT_DIRECTORY: &str = "snapshots/";
+
+/// バトルの状態を `<directory>/<name>.json` に保存する
+pub struct FileBattleSnapshotRepository {
+    directory: PathBuf,
+}
+
+impl FileBattleSnapshotRepository {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// 保存名をファイル名に使うため、ディレクトリの外を指す名前は受け付けない
+    fn path(&self, name: &str) -> Result<PathBuf, RepositoryError> {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(RepositoryError::InvalidName(name.to_string()));
+        }
+        Ok(self.directory.join(format!("{}.json", name)))
+    }
+}
+
+impl Default for FileBattleSnapshotRepository {
+    fn default() -> Self {
+        Self::new(DEFAULT_SNAPSHOT_DIRECTORY)
+    }
+}
+
+impl BattleSnapshotRepository for FileBattleSnapshotRepository {
+    fn save_snapshot(&self, name: &str, battle: &Battle) -> Result<(), RepositoryError> {
+        Ok(save_json(&self.path(name)?, battle)?)
+    }
+
+    fn load_snapshot(&self, name: &str) -> Result<Battle, RepositoryError> {
+        let path = self.path(name)?;
+        if !path.is_file() {
+            return Err(RepositoryError::NotFound(name.to_string()));
+        }
+        Ok(load_json(&path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::model::status::StatusCondition;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+    use std::fs;
+
+    #[test]
+    fn test_save_and_load_snapshot() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new(
+                "player".to_string(),
+                vec![test_active_pokemon("a", &["normal"], &moves)],
+                1,
+            ),
+            BattleSide::new(
+                "opponent".to_string(),
+                vec![test_active_pokemon("b", &["normal"], &moves)],
+                1,
+            ),
+        )
+        .with_seed(3);
+        battle.turn = 4;
+        battle.sides[0].party[0].attack_rank = 2;
+        battle.sides[0].party[0].moves[0].consume_pp();
+        battle.sides[1].party[0].set_status(StatusCondition::Burn);
+
+        let repository = FileBattleSnapshotRepository::new("test_data_for_snapshot");
+        repository.save_snapshot("turn4", &battle).unwrap();
+        let loaded = repository.load_snapshot("turn4").unwrap();
+        fs::remove_dir_all("test_data_for_snapshot").unwrap();
+
+        assert_eq!(loaded.turn, 4);
+        assert_eq!(loaded.rng, battle.rng);
+        assert_eq!(loaded.sides[0].party[0].attack_rank, 2);
+        assert_eq!(loaded.sides[0].party[0].moves[0].current_pp, 9);
+        assert_eq!(loaded.sides[1].party[0].status, Some(StatusCondition::Burn));
+        assert_eq!(
+            repository.load_snapshot("missing").unwrap_err(),
+            RepositoryError::NotFound("missing".to_string())
+        );
+        assert_eq!(
+            repository.save_snapshot("../escape", &battle),
+            Err(RepositoryError::InvalidName("../escape".to_string()))
+        );
+    }
+}
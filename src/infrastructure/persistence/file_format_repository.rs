@@ -0,0 +1,64 @@
+use crate::domain::model::format::Format;
+use crate::domain::repository::format_repository::FormatRepository;
+use crate::domain::repository::repository_error::RepositoryError;
+use crate::infrastructure::persistence::json_file::load_json;
+use crate::infrastructure::persistence::json_loader::load_json_from_directory;
+use std::path::PathBuf;
+
+/// ルールの定義を置く既定のディレクトリ
+pub const DEFAULT_FORMAT_DIRECTORY: &str = "formats/";
+
+/// `<directory>/<id>.json` に置いたルールの定義を読み込む
+pub struct FileFormatRepository {
+    directory: PathBuf,
+}
+
+impl FileFormatRepository {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+}
+
+impl Default for FileFormatRepository {
+    fn default() -> Self {
+        Self::new(DEFAULT_FORMAT_DIRECTORY)
+    }
+}
+
+impl FormatRepository for FileFormatRepository {
+    fn find_all_formats(&self) -> Vec<Format> {
+        load_json_from_directory(&self.directory.to_string_lossy(), "formats")
+    }
+
+    fn find_format(&self, id: &str) -> Result<Format, RepositoryError> {
+        let path = self.directory.join(format!("{}.json", id));
+        if !path.is_file() {
+            return Err(RepositoryError::NotFound(id.to_string()));
+        }
+        Ok(load_json(&path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::format::LevelRule;
+
+    #[test]
+    fn test_find_format() {
+        let repository = FileFormatRepository::default();
+        let format = repository.find_format("sv-ranked-singles").unwrap();
+        assert_eq!(format.battle_format, BattleFormat::Singles);
+        assert_eq!(format.level, LevelRule::Flat(50));
+        assert_eq!(format.bring_count, 3);
+        assert!(format.no_mythicals);
+        assert!(repository.find_all_formats().len() >= 2);
+        assert_eq!(
+            repository.find_format("unknown").unwrap_err(),
+            RepositoryError::NotFound("unknown".to_string())
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::domain::model::replay::Replay;
+use crate::domain::repository::replay_repository::ReplayRepository;
+use crate::domain::repository::repository_error::RepositoryError;
+use crate::infrastructure::persistence::json_file::{load_json, save_json};
+use std::path::PathBuf;
+
+/// リプレイを保存する既定のディレクトリ
+pub const DEFAULT_REPLAY_DIRECTORY: &str = "replays/";
+
+/// リプレイを1つずつ `<directory>/<name>.json` に保存する
+pub struct FileReplayRepository {
+    directory: PathBuf,
+}
+
+impl FileReplayRepository {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// リプレイ名をファイル名に使うため、ディレクトリの外を指す名前は受け付けない
+    fn path(&self, name: &str) -> Result<PathBuf, RepositoryError> {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(RepositoryError::InvalidName(name.to_string()));
+        }
+        Ok(self.directory.join(format!("{}.json", name)))
+    }
+}
+
+impl Default for FileReplayRepository {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_DIRECTORY)
+    }
+}
+
+impl ReplayRepository for FileReplayRepository {
+    fn save_replay(&self, name: &str, replay: &Replay) -> Result<(), RepositoryError> {
+        Ok(save_json(&self.path(name)?, replay)?)
+    }
+
+    fn load_replay(&self, name: &str) -> Result<Replay, RepositoryError> {
+        let path = self.path(name)?;
+        if !path.is_file() {
+            return Err(RepositoryError::NotFound(name.to_string()));
+        }
+        Ok(load_json(&path)?)
+    }
+}
@@ -0,0 +1,124 @@
+use crate::domain::model::team::Team;
+use crate::domain::repository::repository_error::RepositoryError;
+use crate::domain::repository::team_repository::TeamRepository;
+use crate::infrastructure::persistence::json_file::{load_json, save_json};
+use std::fs;
+use std::path::PathBuf;
+
+/// パーティを保存する既定のディレクトリ
+pub const DEFAULT_TEAM_DIRECTORY: &str = "teams/";
+
+/// パーティを1つずつ `<directory>/<name>.json` に保存する
+pub struct FileTeamRepository {
+    directory: PathBuf,
+}
+
+impl FileTeamRepository {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// パーティ名をファイル名に使うため、ディレクトリの外を指す名前は受け付けない
+    fn path(&self, name: &str) -> Result<PathBuf, RepositoryError> {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(RepositoryError::InvalidName(name.to_string()));
+        }
+        Ok(self.directory.join(format!("{}.json", name)))
+    }
+}
+
+impl Default for FileTeamRepository {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEAM_DIRECTORY)
+    }
+}
+
+impl TeamRepository for FileTeamRepository {
+    fn find_all_teams(&self) -> Result<Vec<Team>, RepositoryError> {
+        if !self.directory.is_dir() {
+            return Ok(Vec::new());
+        }
+        let entries = fs::read_dir(&self.directory)
+            .map_err(|error| RepositoryError::Io(error.to_string()))?;
+        let mut teams = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|error| RepositoryError::Io(error.to_string()))?
+                .path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                teams.push(load_json::<Team>(&path)?);
+            }
+        }
+        teams.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(teams)
+    }
+
+    fn find_team(&self, name: &str) -> Result<Team, RepositoryError> {
+        let path = self.path(name)?;
+        if !path.is_file() {
+            return Err(RepositoryError::NotFound(name.to_string()));
+        }
+        Ok(load_json(&path)?)
+    }
+
+    fn save_team(&self, team: &Team) -> Result<(), RepositoryError> {
+        Ok(save_json(&self.path(&team.name)?, team)?)
+    }
+
+    fn delete_team(&self, name: &str) -> Result<(), RepositoryError> {
+        let path = self.path(name)?;
+        if !path.is_file() {
+            return Err(RepositoryError::NotFound(name.to_string()));
+        }
+        fs::remove_file(&path).map_err(|error| RepositoryError::Io(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::pokemon_build::PokemonBuild;
+
+    #[test]
+    fn test_save_list_and_delete_teams() {
+        let repository = FileTeamRepository::new("test_data_for_teams");
+        let rain = Team::new(
+            "rain",
+            vec![
+                PokemonBuild::new("pelipper", "drizzle").with_item("damp-rock"),
+                PokemonBuild::new("barraskewda", "swift-swim"),
+            ],
+        );
+        let sand = Team::new("sand", vec![PokemonBuild::new("tyranitar", "sand-stream")]);
+        repository.save_team(&sand).unwrap();
+        repository.save_team(&rain).unwrap();
+        repository.add_to_folder("rain", "weather").unwrap();
+
+        let teams = repository.find_all_teams().unwrap();
+        let in_folder = repository.find_teams_in_folder("weather").unwrap();
+        let loaded = repository.find_team("rain").unwrap();
+        repository.delete_team("sand").unwrap();
+        let remaining = repository.find_all_teams().unwrap();
+        fs::remove_dir_all("test_data_for_teams").unwrap();
+
+        assert_eq!(teams.len(), 2);
+        assert_eq!(teams[0].name, "rain");
+        assert_eq!(in_folder.len(), 1);
+        assert_eq!(loaded.members, rain.members);
+        assert_eq!(loaded.folders, vec!["weather".to_string()]);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(
+            repository.find_team("sand").unwrap_err(),
+            RepositoryError::NotFound("sand".to_string())
+        );
+        assert_eq!(
+            repository.save_team(&Team::new("../escape", vec![])),
+            Err(RepositoryError::InvalidName("../escape".to_string()))
+        );
+    }
+}
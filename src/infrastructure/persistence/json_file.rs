@@ -0,0 +1,86 @@
+use crate::domain::repository::repository_error::RepositoryError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// JSONファイルの読み書きに失敗した場合のエラー
+#[derive(Debug)]
+pub enum JsonFileError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for JsonFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonFileError::Io(error) => write!(f, "ファイルの入出力に失敗しました: {}", error),
+            JsonFileError::Json(error) => write!(f, "JSONの変換に失敗しました: {}", error),
+        }
+    }
+}
+
+impl From<std::io::Error> for JsonFileError {
+    fn from(error: std::io::Error) -> Self {
+        JsonFileError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for JsonFileError {
+    fn from(error: serde_json::Error) -> Self {
+        JsonFileError::Json(error)
+    }
+}
+
+impl From<JsonFileError> for RepositoryError {
+    fn from(error: JsonFileError) -> Self {
+        match error {
+            JsonFileError::Io(error) => RepositoryError::Io(error.to_string()),
+            JsonFileError::Json(error) => RepositoryError::Format(error.to_string()),
+        }
+    }
+}
+
+/// 値を整形済みのJSONとしてファイルに書き出す
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), JsonFileError> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// JSONファイルを読み込んで指定された型にデシリアライズする
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> Result<T, JsonFileError> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestData {
+        name: String,
+    }
+
+    #[test]
+    fn test_save_and_load_json() {
+        let path = Path::new("test_data_for_json_file/data.json");
+        let data = TestData {
+            name: "test".to_string(),
+        };
+
+        save_json(path, &data).unwrap();
+        let loaded: TestData = load_json(path).unwrap();
+        assert_eq!(loaded, data);
+
+        fs::remove_dir_all("test_data_for_json_file").unwrap();
+    }
+}
@@ -1 +1,2 @@
 // インターフェース層
+pub mod cli;
@@ -1 +1,5 @@
-// 空のcliモジュール
+pub mod agent_option;
+pub mod cli_agent;
+pub mod command;
+pub mod event_printer;
+pub mod type_coverage_table;
@@ -0,0 +1,160 @@
+use crate::application::dto::loaded_static_data::LoadedStaticData;
+use crate::domain::service::battle_agent::BattleAgent;
+use crate::domain::service::battle_engine::BattleEngine;
+use crate::domain::service::battle_evaluator::HeuristicEvaluator;
+use crate::domain::service::determinizer::LearnsetDeterminizer;
+use crate::domain::service::expectiminimax_agent::{ExpectiminimaxAgent, SearchConfig};
+use crate::domain::service::greedy_agent::GreedyAgent;
+use crate::domain::service::mcts_agent::{MctsAgent, MctsConfig};
+use crate::domain::service::random_agent::RandomAgent;
+use crate::domain::service::type_chart::TypeChart;
+use crate::interfaces::cli::cli_agent::CliAgent;
+use std::fmt;
+use std::str::FromStr;
+
+/// シードを省略した場合に使う値
+const DEFAULT_SEED: u64 = 0;
+
+/// コマンドライン引数で指定するエージェントの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentKind {
+    /// 端末から入力する人間
+    Human,
+    Random,
+    Greedy,
+    /// 数ターン先まで読む探索（乱数を使わないためシードは無視する）
+    Expectiminimax,
+    /// 相手の見えていない型を覚える技から抽選するモンテカルロ木探索
+    Mcts,
+}
+
+/// `human`, `random:42`, `mcts:7` のような指定を解釈した結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentOption {
+    pub kind: AgentKind,
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentOptionError {
+    UnknownKind(String),
+    InvalidSeed(String),
+}
+
+impl fmt::Display for AgentOptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownKind(kind) => write!(
+                f,
+                "不明なエージェントです: {} (human, random, greedy, expectiminimax, mcts のいずれか)",
+                kind
+            ),
+            Self::InvalidSeed(seed) => write!(f, "シードは整数で指定してください: {}", seed),
+        }
+    }
+}
+
+impl FromStr for AgentOption {
+    type Err = AgentOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, seed) = match s.split_once(':') {
+            Some((kind, seed)) => (kind, Some(seed)),
+            None => (s, None),
+        };
+        let kind = match kind {
+            "human" => AgentKind::Human,
+            "random" => AgentKind::Random,
+            "greedy" => AgentKind::Greedy,
+            "expectiminimax" => AgentKind::Expectiminimax,
+            "mcts" => AgentKind::Mcts,
+            _ => return Err(AgentOptionError::UnknownKind(kind.to_string())),
+        };
+        let seed = match seed {
+            Some(seed) => seed
+                .parse()
+                .map_err(|_| AgentOptionError::InvalidSeed(seed.to_string()))?,
+            None => DEFAULT_SEED,
+        };
+        Ok(Self { kind, seed })
+    }
+}
+
+impl AgentOption {
+    /// 指定に従ってエージェントを生成する。人間は標準入出力を使い、
+    /// 探索するエージェントはバトルと同じエンジンで先読みする
+    pub fn build(
+        &self,
+        engine: &BattleEngine,
+        type_chart: &TypeChart,
+        data: &LoadedStaticData,
+    ) -> Box<dyn BattleAgent> {
+        match self.kind {
+            AgentKind::Human => Box::new(CliAgent::new(std::io::stdin().lock(), std::io::stdout())),
+            AgentKind::Random => Box::new(RandomAgent::new(self.seed)),
+            AgentKind::Greedy => Box::new(GreedyAgent::new(type_chart.clone(), self.seed)),
+            AgentKind::Expectiminimax => Box::new(ExpectiminimaxAgent::new(
+                engine.clone(),
+                HeuristicEvaluator::default(),
+                SearchConfig::default(),
+            )),
+            AgentKind::Mcts => Box::new(MctsAgent::new(
+                engine.clone(),
+                LearnsetDeterminizer::new(
+                    &data.pokemons,
+                    &data.moves,
+                    &data.abilities,
+                    &data.items,
+                ),
+                MctsConfig {
+                    seed: self.seed,
+                    ..MctsConfig::default()
+                },
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_agent_option() {
+        assert_eq!(
+            "greedy:42".parse(),
+            Ok(AgentOption {
+                kind: AgentKind::Greedy,
+                seed: 42
+            })
+        );
+        assert_eq!(
+            "random".parse(),
+            Ok(AgentOption {
+                kind: AgentKind::Random,
+                seed: DEFAULT_SEED
+            })
+        );
+        assert_eq!(
+            "random:x".parse::<AgentOption>(),
+            Err(AgentOptionError::InvalidSeed("x".to_string()))
+        );
+        assert_eq!(
+            "mcts:3".parse(),
+            Ok(AgentOption {
+                kind: AgentKind::Mcts,
+                seed: 3
+            })
+        );
+        assert_eq!(
+            "expectiminimax"
+                .parse::<AgentOption>()
+                .map(|option| option.kind),
+            Ok(AgentKind::Expectiminimax)
+        );
+        assert_eq!(
+            "minimax".parse::<AgentOption>(),
+            Err(AgentOptionError::UnknownKind("minimax".to_string()))
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use crate::domain::model::battle::ReplacementRequest;
+use crate::domain::model::battle_action::{BattleAction, ChosenAction, SlotPosition};
+use crate::domain::model::player_view::PlayerView;
+use crate::domain::service::battle_agent::{BattleAgent, is_compatible};
+use crate::domain::service::legal_actions::SlotOptions;
+use std::io::{BufRead, Write};
+
+/// 端末から番号を入力して行動を選ぶ、人間用のエージェント
+pub struct CliAgent<R: BufRead, W: Write> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> CliAgent<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// 選択肢を表示し、範囲内の番号が入力されるまで繰り返し尋ねる。入力が終わった場合は先頭を選ぶ
+    fn ask(&mut self, prompt: &str, labels: &[String]) -> usize {
+        loop {
+            let _ = writeln!(self.output, "{}", prompt);
+            for (index, label) in labels.iter().enumerate() {
+                let _ = writeln!(self.output, "  {}: {}", index, label);
+            }
+            let _ = write!(self.output, "> ");
+            let _ = self.output.flush();
+
+            let mut line = String::new();
+            match self.input.read_line(&mut line) {
+                Ok(0) | Err(_) => return 0,
+                Ok(_) => {}
+            }
+            match line.trim().parse::<usize>() {
+                Ok(index) if index < labels.len() => return index,
+                _ => {
+                    let _ = writeln!(
+                        self.output,
+                        "0〜{} の番号を入力してください",
+                        labels.len() - 1
+                    );
+                }
+            }
+        }
+    }
+
+    fn describe(view: &PlayerView, position: SlotPosition, action: &BattleAction) -> String {
+        let battle = &view.battle;
+        match action {
+            BattleAction::UseMove {
+                move_index,
+                target,
+                terastallize,
+            } => {
+                let move_name = battle
+                    .pokemon_at(position)
+                    .and_then(|pokemon| pokemon.moves.get(*move_index))
+                    .map_or("?".to_string(), |move_slot| {
+                        format!(
+                            "{} (PP {}/{})",
+                            move_slot.name, move_slot.current_pp, move_slot.max_pp
+                        )
+                    });
+                let target = target
+                    .and_then(|target| battle.pokemon_at(target))
+                    .map_or(String::new(), |pokemon| format!(" → {}", pokemon.name));
+                let tera = if *terastallize {
+                    " [テラスタル]"
+                } else {
+                    ""
+                };
+                format!("{}{}{}", move_name, target, tera)
+            }
+            BattleAction::Struggle => "わるあがき".to_string(),
+            BattleAction::Recharge => "反動で動けない".to_string(),
+            BattleAction::Switch { party_index } => format!(
+                "交代: {}",
+                battle.sides[position.side].party[*party_index].name
+            ),
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> BattleAgent for CliAgent<R, W> {
+    fn choose_actions(&mut self, view: &PlayerView, options: &[SlotOptions]) -> Vec<ChosenAction> {
+        // 見せ合いで見えたが、まだ場に出ていない相手のポケモン
+        let opponent = &view.battle.sides[view.opponent_side()];
+        let unseen: Vec<&str> = view
+            .opponent_preview
+            .iter()
+            .filter(|name| !opponent.party.iter().any(|pokemon| pokemon.name == **name))
+            .map(String::as_str)
+            .collect();
+        if !unseen.is_empty() {
+            let _ = writeln!(self.output, "相手の控えの候補: {}", unseen.join(", "));
+        }
+        let mut chosen = Vec::new();
+        for slot in options {
+            let actions: Vec<&BattleAction> = slot
+                .actions
+                .iter()
+                .filter(|action| is_compatible(&chosen, action))
+                .collect();
+            if actions.is_empty() {
+                continue;
+            }
+            let name = view
+                .battle
+                .pokemon_at(slot.position)
+                .map_or("?", |pokemon| pokemon.name.as_str())
+                .to_string();
+            let labels: Vec<String> = actions
+                .iter()
+                .map(|action| Self::describe(view, slot.position, action))
+                .collect();
+            let index = self.ask(&format!("{} はどうする？", name), &labels);
+            chosen.push(ChosenAction::new(slot.position, actions[index].clone()));
+        }
+        chosen
+    }
+
+    fn choose_replacements(
+        &mut self,
+        view: &PlayerView,
+        request: &ReplacementRequest,
+    ) -> Vec<(SlotPosition, usize)> {
+        let mut candidates = request.candidates.clone();
+        let mut choices = Vec::new();
+        for slot in request.slots.iter().take(request.required_count()) {
+            let labels: Vec<String> = candidates
+                .iter()
+                .map(|party_index| {
+                    view.battle.sides[request.side].party[*party_index]
+                        .name
+                        .clone()
+                })
+                .collect();
+            let index = self.ask("次に繰り出すポケモンを選んでください", &labels);
+            choices.push((
+                SlotPosition::new(request.side, *slot),
+                candidates.remove(index),
+            ));
+        }
+        choices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::battle::Battle;
+    use crate::domain::model::battle_format::BattleFormat;
+    use crate::domain::model::battle_side::BattleSide;
+    use crate::domain::service::legal_actions::LegalActions;
+    use crate::domain::test_support::{test_active_pokemon, test_move};
+
+    #[test]
+    fn test_choose_action_from_input() {
+        let moves = vec![test_move(
+            "tackle",
+            "normal",
+            "physical",
+            Some(40),
+            "selected-pokemon",
+        )];
+        let party = || {
+            (0..2)
+                .map(|_| test_active_pokemon("a", &["normal"], &moves))
+                .collect()
+        };
+        let battle = Battle::new(
+            BattleFormat::Singles,
+            BattleSide::new("player".to_string(), party(), 1),
+            BattleSide::new("opponent".to_string(), party(), 1),
+        );
+        let mut output = Vec::new();
+        let mut agent = CliAgent::new("9\n1\n".as_bytes(), &mut output);
+
+        let options = LegalActions::for_side(&battle, 0);
+        let actions = agent.choose_actions(&PlayerView::new(&battle, 0), &options);
+        assert_eq!(
+            actions,
+            vec![ChosenAction::new(
+                SlotPosition::new(0, 0),
+                BattleAction::Switch { party_index: 1 }
+            )]
+        );
+    }
+}
@@ -0,0 +1,663 @@
+use crate::domain::model::pokemon_build::StatSpread;
+use crate::domain::model::team_preview::TeamSheet;
+use crate::interfaces::cli::agent_option::{AgentOption, AgentOptionError};
+use std::collections::HashMap;
+use std::fmt;
+
+/// コマンドの一覧（引数が読めなかったときに表示する）
+pub const USAGE: &str = "\
+使い方:
+  poke-simu [--include-unavailable] [--team-dir <フォルダ>] <コマンド> ...
+  poke-simu battle <チーム1> <チーム2> [--format <ルール>] [--p1 <エージェント>] [--p2 <エージェント>]
+                   [--seed <整数>] [--team-sheet <open|closed>] [--turn-limit <ターン>]
+                   [--replay <保存名>] [--snapshot <保存名>] [--snapshot-turn <ターン>]
+                   [--branch-from <ターン> | --rewind-to <ターン>]
+  poke-simu resume <保存名> [--p1 <エージェント>] [--p2 <エージェント>] [--turn-limit <ターン>]
+                   [--replay <保存名>]
+  poke-simu replay <保存名>
+  poke-simu formats
+  poke-simu team list [--folder <フォルダ>]
+  poke-simu team show <チーム>
+  poke-simu team import <チーム> <ファイル>
+  poke-simu team delete <チーム>
+  poke-simu team add <チーム> <ファイル>
+  poke-simu team remove <チーム> <番号>
+  poke-simu team lead <チーム> <番号>
+  poke-simu team set <チーム> <番号> <ability|item|tera|moves|evs> <値>
+  poke-simu team tag <チーム> <フォルダ>
+  poke-simu team untag <チーム> <フォルダ>
+  poke-simu team random <チーム> <ルール> [--seed <整数>]
+  poke-simu team coverage <チーム>
+--include-unavailable: スカーレット・バイオレットに登場しないポケモンや技も読み込む
+--team-dir: パーティを保存するフォルダ（既定は teams/）
+エージェント: human, random, greedy, expectiminimax, mcts（\"mcts:42\" のようにシードを付けられる）
+番号: パーティの先頭を 0 とする。item と tera は none で外し、moves はカンマ区切り、
+      evs は HP/攻撃/防御/特攻/特防/素早さ の順にスラッシュ区切り（252/0/0/0/4/252）";
+
+/// コマンドより前に置く、図鑑データの読み込み方を変えるフラグ
+const INCLUDE_UNAVAILABLE_FLAG: &str = "--include-unavailable";
+/// コマンドより前に置く、パーティを保存するフォルダを指定するオプション
+const TEAM_DIR_OPTION: &str = "--team-dir";
+
+/// 1人目の既定のエージェント（端末から操作する）
+const DEFAULT_FIRST_AGENT: &str = "human";
+/// 2人目の既定のエージェント
+const DEFAULT_SECOND_AGENT: &str = "greedy";
+
+/// 保存しておいたパーティ同士のバトル
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BattleCommand {
+    pub teams: [String; 2],
+    /// 対戦のルールの ID。省略した場合はシングルで、パーティの型をそのまま使う
+    pub format: Option<String>,
+    pub agents: [AgentOption; 2],
+    pub seed: u64,
+    pub team_sheet: TeamSheet,
+    /// バトルを打ち切るターン数。省略した場合はエンジンの既定値
+    pub turn_limit: Option<u32>,
+    /// 決着後にリプレイを保存する名前
+    pub replay: Option<String>,
+    /// 決着後に途中の状態を保存する名前
+    pub snapshot: Option<String>,
+    /// 途中の状態を保存するターン。省略した場合は最後のターン
+    pub snapshot_turn: Option<u32>,
+    /// 決着後、元の展開を残したままこのターンから分岐して戦い直す
+    pub branch_from: Option<u32>,
+    /// 決着後、このターンまで巻き戻し、それより後の展開を捨てて戦い直す
+    pub rewind_to: Option<u32>,
+}
+
+/// パーティの1体分の編集
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberEdit {
+    Ability(String),
+    Item(Option<String>),
+    TeraType(Option<String>),
+    Moves(Vec<String>),
+    Evs(StatSpread),
+}
+
+/// 保存しておいたパーティを扱うコマンド
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TeamCommand {
+    List {
+        folder: Option<String>,
+    },
+    /// Showdown のテキストで表示する
+    Show(String),
+    /// Showdown のテキストのファイルを読み込んで保存する
+    Import {
+        name: String,
+        path: String,
+    },
+    Delete(String),
+    /// Showdown のテキストのファイルにあるポケモンをパーティに加える
+    Add {
+        name: String,
+        path: String,
+    },
+    Remove {
+        name: String,
+        index: usize,
+    },
+    /// 指定したポケモンを先発にする
+    Lead {
+        name: String,
+        index: usize,
+    },
+    Set {
+        name: String,
+        index: usize,
+        edit: MemberEdit,
+    },
+    Tag {
+        name: String,
+        folder: String,
+    },
+    Untag {
+        name: String,
+        folder: String,
+    },
+    /// ルールに沿ったパーティをランダムに作って保存する
+    Random {
+        name: String,
+        format: String,
+        seed: u64,
+    },
+    /// タイプ相性の一覧を表示する
+    Coverage(String),
+}
+
+/// コマンドライン引数を解釈した結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Battle(BattleCommand),
+    /// 保存したバトルの途中の状態から再開する。リプレイは再開した時点からの記録になる
+    Resume {
+        snapshot: String,
+        agents: [AgentOption; 2],
+        turn_limit: Option<u32>,
+        replay: Option<String>,
+    },
+    /// 保存したリプレイを再生し、記録と同じ結果になることを確かめる
+    Replay(String),
+    /// 対戦のルールの一覧を表示する
+    Formats,
+    Team(TeamCommand),
+}
+
+/// コマンドと、すべてのコマンドに共通するフラグ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandLine {
+    pub command: Command,
+    /// スカーレット・バイオレットに登場しないデータも読み込む
+    pub include_unavailable: bool,
+    /// パーティを保存するフォルダ。省略した場合は既定のフォルダ
+    pub team_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    MissingCommand,
+    UnknownCommand(String),
+    MissingArgument(&'static str),
+    UnexpectedArgument(String),
+    UnknownOption(String),
+    MissingValue(String),
+    InvalidNumber { option: String, value: String },
+    InvalidValue { option: String, value: String },
+    ConflictingOptions(&'static str, &'static str),
+    Agent(AgentOptionError),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCommand => write!(f, "コマンドを指定してください"),
+            Self::UnknownCommand(command) => write!(f, "不明なコマンドです: {}", command),
+            Self::MissingArgument(name) => write!(f, "{} を指定してください", name),
+            Self::UnexpectedArgument(argument) => write!(f, "余分な引数があります: {}", argument),
+            Self::UnknownOption(option) => write!(f, "不明なオプションです: {}", option),
+            Self::MissingValue(option) => write!(f, "{} に値を指定してください", option),
+            Self::InvalidNumber { option, value } => {
+                write!(f, "{} は整数で指定してください: {}", option, value)
+            }
+            Self::InvalidValue { option, value } => {
+                write!(f, "{} に指定できない値です: {}", option, value)
+            }
+            Self::ConflictingOptions(first, second) => {
+                write!(f, "{} と {} は同時に指定できません", first, second)
+            }
+            Self::Agent(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<AgentOptionError> for CommandError {
+    fn from(error: AgentOptionError) -> Self {
+        Self::Agent(error)
+    }
+}
+
+/// 位置で決まる引数と `--名前 値` のオプションに分けた引数
+struct Arguments {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Arguments {
+    /// `allowed` にないオプションは受け付けない
+    fn parse(args: &[String], allowed: &[&str]) -> Result<Self, CommandError> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                positional.push(arg.clone());
+                continue;
+            }
+            if !allowed.contains(&arg.as_str()) {
+                return Err(CommandError::UnknownOption(arg.clone()));
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| CommandError::MissingValue(arg.clone()))?;
+            options.insert(arg.clone(), value.clone());
+        }
+        Ok(Self {
+            positional,
+            options,
+        })
+    }
+
+    /// 位置で決まる引数を、名前の数だけちょうど受け取る
+    fn take<const N: usize>(&self, names: [&'static str; N]) -> Result<[String; N], CommandError> {
+        if let Some(extra) = self.positional.get(N) {
+            return Err(CommandError::UnexpectedArgument(extra.clone()));
+        }
+        let mut values = names.map(|_| String::new());
+        for (index, name) in names.iter().enumerate() {
+            values[index] = self
+                .positional
+                .get(index)
+                .cloned()
+                .ok_or(CommandError::MissingArgument(name))?;
+        }
+        Ok(values)
+    }
+
+    fn option(&self, name: &str) -> Option<String> {
+        self.options.get(name).cloned()
+    }
+
+    fn number<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, CommandError> {
+        self.options
+            .get(name)
+            .map(|value| parse_number(name, value))
+            .transpose()
+    }
+
+    fn team_sheet(&self) -> Result<TeamSheet, CommandError> {
+        match self.options.get("--team-sheet").map(String::as_str) {
+            None | Some("closed") => Ok(TeamSheet::Closed),
+            Some("open") => Ok(TeamSheet::Open),
+            Some(value) => Err(CommandError::InvalidValue {
+                option: "--team-sheet".to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    fn agents(&self) -> Result<[AgentOption; 2], CommandError> {
+        let first = self.option("--p1");
+        let second = self.option("--p2");
+        Ok([
+            first.as_deref().unwrap_or(DEFAULT_FIRST_AGENT).parse()?,
+            second.as_deref().unwrap_or(DEFAULT_SECOND_AGENT).parse()?,
+        ])
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, CommandError> {
+    value.parse().map_err(|_| CommandError::InvalidNumber {
+        option: name.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// "none" は外すことを表す
+fn optional(value: String) -> Option<String> {
+    (value != "none").then_some(value)
+}
+
+impl MemberEdit {
+    fn parse(field: &str, value: String) -> Result<Self, CommandError> {
+        match field {
+            "ability" => Ok(Self::Ability(value)),
+            "item" => Ok(Self::Item(optional(value))),
+            "tera" => Ok(Self::TeraType(optional(value))),
+            "moves" => Ok(Self::Moves(
+                value
+                    .split(',')
+                    .map(|name| name.trim().to_string())
+                    .collect(),
+            )),
+            "evs" => {
+                let values = value
+                    .split('/')
+                    .map(|ev| parse_number("evs", ev.trim()))
+                    .collect::<Result<Vec<u32>, _>>()?;
+                let [hp, attack, defense, special_attack, special_defense, speed] =
+                    <[u32; 6]>::try_from(values).map_err(|_| CommandError::InvalidValue {
+                        option: "evs".to_string(),
+                        value: value.clone(),
+                    })?;
+                Ok(Self::Evs(StatSpread {
+                    hp,
+                    attack,
+                    defense,
+                    special_attack,
+                    special_defense,
+                    speed,
+                }))
+            }
+            _ => Err(CommandError::InvalidValue {
+                option: "項目".to_string(),
+                value: field.to_string(),
+            }),
+        }
+    }
+}
+
+impl CommandLine {
+    /// プログラム名を除いたコマンドライン引数を解釈する。共通のフラグとオプションはコマンドより前に置く
+    pub fn parse(args: &[String]) -> Result<Self, CommandError> {
+        let mut include_unavailable = false;
+        let mut team_dir = None;
+        let mut rest = args;
+        while let Some((first, tail)) = rest.split_first() {
+            match first.as_str() {
+                INCLUDE_UNAVAILABLE_FLAG => {
+                    include_unavailable = true;
+                    rest = tail;
+                }
+                TEAM_DIR_OPTION => {
+                    let (directory, tail) = tail
+                        .split_first()
+                        .ok_or_else(|| CommandError::MissingValue(first.clone()))?;
+                    team_dir = Some(directory.clone());
+                    rest = tail;
+                }
+                _ => break,
+            }
+        }
+        Ok(Self {
+            command: Command::parse(rest)?,
+            include_unavailable,
+            team_dir,
+        })
+    }
+}
+
+impl Command {
+    /// 共通のフラグを除いたコマンドライン引数を解釈する
+    pub fn parse(args: &[String]) -> Result<Self, CommandError> {
+        let (command, rest) = args.split_first().ok_or(CommandError::MissingCommand)?;
+        match command.as_str() {
+            "battle" => {
+                let args = Arguments::parse(
+                    rest,
+                    &[
+                        "--format",
+                        "--p1",
+                        "--p2",
+                        "--seed",
+                        "--team-sheet",
+                        "--turn-limit",
+                        "--replay",
+                        "--snapshot",
+                        "--snapshot-turn",
+                        "--branch-from",
+                        "--rewind-to",
+                    ],
+                )?;
+                let [first, second] = args.take(["チーム1", "チーム2"])?;
+                let branch_from = args.number("--branch-from")?;
+                let rewind_to = args.number("--rewind-to")?;
+                if branch_from.is_some() && rewind_to.is_some() {
+                    return Err(CommandError::ConflictingOptions(
+                        "--branch-from",
+                        "--rewind-to",
+                    ));
+                }
+                Ok(Self::Battle(BattleCommand {
+                    teams: [first, second],
+                    format: args.option("--format"),
+                    agents: args.agents()?,
+                    seed: args.number("--seed")?.unwrap_or(0),
+                    team_sheet: args.team_sheet()?,
+                    turn_limit: args.number("--turn-limit")?,
+                    replay: args.option("--replay"),
+                    snapshot: args.option("--snapshot"),
+                    snapshot_turn: args.number("--snapshot-turn")?,
+                    branch_from,
+                    rewind_to,
+                }))
+            }
+            "resume" => {
+                let args = Arguments::parse(rest, &["--p1", "--p2", "--turn-limit", "--replay"])?;
+                let [snapshot] = args.take(["保存名"])?;
+                Ok(Self::Resume {
+                    snapshot,
+                    agents: args.agents()?,
+                    turn_limit: args.number("--turn-limit")?,
+                    replay: args.option("--replay"),
+                })
+            }
+            "replay" => {
+                let [name] = Arguments::parse(rest, &[])?.take(["保存名"])?;
+                Ok(Self::Replay(name))
+            }
+            "formats" => {
+                Arguments::parse(rest, &[])?.take([])?;
+                Ok(Self::Formats)
+            }
+            "team" => Self::parse_team(rest).map(Self::Team),
+            _ => Err(CommandError::UnknownCommand(command.clone())),
+        }
+    }
+
+    fn parse_team(args: &[String]) -> Result<TeamCommand, CommandError> {
+        let (command, rest) = args
+            .split_first()
+            .ok_or(CommandError::MissingArgument("team のサブコマンド"))?;
+        match command.as_str() {
+            "list" => {
+                let args = Arguments::parse(rest, &["--folder"])?;
+                args.take([])?;
+                Ok(TeamCommand::List {
+                    folder: args.option("--folder"),
+                })
+            }
+            "show" => {
+                let [name] = Arguments::parse(rest, &[])?.take(["チーム"])?;
+                Ok(TeamCommand::Show(name))
+            }
+            "import" => {
+                let [name, path] = Arguments::parse(rest, &[])?.take(["チーム", "ファイル"])?;
+                Ok(TeamCommand::Import { name, path })
+            }
+            "delete" => {
+                let [name] = Arguments::parse(rest, &[])?.take(["チーム"])?;
+                Ok(TeamCommand::Delete(name))
+            }
+            "add" => {
+                let [name, path] = Arguments::parse(rest, &[])?.take(["チーム", "ファイル"])?;
+                Ok(TeamCommand::Add { name, path })
+            }
+            "remove" => {
+                let [name, index] = Arguments::parse(rest, &[])?.take(["チーム", "番号"])?;
+                Ok(TeamCommand::Remove {
+                    name,
+                    index: parse_number("番号", &index)?,
+                })
+            }
+            "lead" => {
+                let [name, index] = Arguments::parse(rest, &[])?.take(["チーム", "番号"])?;
+                Ok(TeamCommand::Lead {
+                    name,
+                    index: parse_number("番号", &index)?,
+                })
+            }
+            "set" => {
+                let [name, index, field, value] =
+                    Arguments::parse(rest, &[])?.take(["チーム", "番号", "項目", "値"])?;
+                Ok(TeamCommand::Set {
+                    name,
+                    index: parse_number("番号", &index)?,
+                    edit: MemberEdit::parse(&field, value)?,
+                })
+            }
+            "tag" => {
+                let [name, folder] = Arguments::parse(rest, &[])?.take(["チーム", "フォルダ"])?;
+                Ok(TeamCommand::Tag { name, folder })
+            }
+            "untag" => {
+                let [name, folder] = Arguments::parse(rest, &[])?.take(["チーム", "フォルダ"])?;
+                Ok(TeamCommand::Untag { name, folder })
+            }
+            "random" => {
+                let args = Arguments::parse(rest, &["--seed"])?;
+                let [name, format] = args.take(["チーム", "ルール"])?;
+                Ok(TeamCommand::Random {
+                    name,
+                    format,
+                    seed: args.number("--seed")?.unwrap_or(0),
+                })
+            }
+            "coverage" => {
+                let [name] = Arguments::parse(rest, &[])?.take(["チーム"])?;
+                Ok(TeamCommand::Coverage(name))
+            }
+            _ => Err(CommandError::UnknownCommand(format!("team {}", command))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::cli::agent_option::AgentKind;
+
+    fn parse(line: &str) -> Result<Command, CommandError> {
+        let args: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        Command::parse(&args)
+    }
+
+    #[test]
+    fn test_parse_battle_with_one_agent_per_side() {
+        let Ok(Command::Battle(battle)) =
+            parse("battle sand rain --p1 mcts:7 --p2 expectiminimax --seed 3 --replay final")
+        else {
+            panic!("battle コマンドとして読めること");
+        };
+        assert_eq!(battle.teams, ["sand".to_string(), "rain".to_string()]);
+        assert_eq!(battle.agents[0].kind, AgentKind::Mcts);
+        assert_eq!(battle.agents[0].seed, 7);
+        assert_eq!(battle.agents[1].kind, AgentKind::Expectiminimax);
+        assert_eq!(battle.seed, 3);
+        assert_eq!(battle.replay.as_deref(), Some("final"));
+        assert_eq!(battle.format, None);
+        assert_eq!(battle.team_sheet, TeamSheet::Closed);
+
+        let Ok(Command::Battle(battle)) =
+            parse("battle sand rain --team-sheet open --branch-from 2 --turn-limit 50")
+        else {
+            panic!("battle コマンドとして読めること");
+        };
+        assert_eq!(battle.team_sheet, TeamSheet::Open);
+        assert_eq!(battle.branch_from, Some(2));
+        assert_eq!(battle.turn_limit, Some(50));
+        assert_eq!(battle.rewind_to, None);
+
+        let Ok(Command::Resume { agents, .. }) = parse("resume halfway") else {
+            panic!("resume コマンドとして読めること");
+        };
+        assert_eq!(agents[0].kind, AgentKind::Human);
+        assert_eq!(agents[1].kind, AgentKind::Greedy);
+    }
+
+    #[test]
+    fn test_parse_team_commands() {
+        assert_eq!(
+            parse("team random sand sv-ranked-singles --seed 9"),
+            Ok(Command::Team(TeamCommand::Random {
+                name: "sand".to_string(),
+                format: "sv-ranked-singles".to_string(),
+                seed: 9
+            }))
+        );
+        assert_eq!(
+            parse("team set sand 1 evs 252/0/0/0/4/252"),
+            Ok(Command::Team(TeamCommand::Set {
+                name: "sand".to_string(),
+                index: 1,
+                edit: MemberEdit::Evs(StatSpread {
+                    hp: 252,
+                    special_defense: 4,
+                    speed: 252,
+                    ..StatSpread::default()
+                })
+            }))
+        );
+        assert_eq!(
+            parse("team set sand 0 item none"),
+            Ok(Command::Team(TeamCommand::Set {
+                name: "sand".to_string(),
+                index: 0,
+                edit: MemberEdit::Item(None)
+            }))
+        );
+        assert_eq!(
+            parse("team set sand 0 nature jolly"),
+            Err(CommandError::InvalidValue {
+                option: "項目".to_string(),
+                value: "nature".to_string()
+            })
+        );
+        assert_eq!(
+            parse("team coverage sand"),
+            Ok(Command::Team(TeamCommand::Coverage("sand".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_global_options() {
+        let args =
+            |line: &str| -> Vec<String> { line.split_whitespace().map(str::to_string).collect() };
+        assert_eq!(
+            CommandLine::parse(&args("--team-dir my-teams --include-unavailable formats")),
+            Ok(CommandLine {
+                command: Command::Formats,
+                include_unavailable: true,
+                team_dir: Some("my-teams".to_string()),
+            })
+        );
+        assert_eq!(
+            CommandLine::parse(&args("formats")),
+            Ok(CommandLine {
+                command: Command::Formats,
+                include_unavailable: false,
+                team_dir: None,
+            })
+        );
+        assert_eq!(
+            CommandLine::parse(&args("--include-unavailable")),
+            Err(CommandError::MissingCommand)
+        );
+        assert_eq!(
+            CommandLine::parse(&args("--team-dir")),
+            Err(CommandError::MissingValue("--team-dir".to_string()))
+        );
+        assert_eq!(
+            CommandLine::parse(&args("formats --include-unavailable")),
+            Err(CommandError::UnknownOption(
+                "--include-unavailable".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse(""), Err(CommandError::MissingCommand));
+        assert_eq!(
+            parse("battle sand"),
+            Err(CommandError::MissingArgument("チーム2"))
+        );
+        assert_eq!(
+            parse("replay one two"),
+            Err(CommandError::UnexpectedArgument("two".to_string()))
+        );
+        assert_eq!(
+            parse("battle sand rain --p3 human"),
+            Err(CommandError::UnknownOption("--p3".to_string()))
+        );
+        assert_eq!(
+            parse("battle sand rain --seed"),
+            Err(CommandError::MissingValue("--seed".to_string()))
+        );
+        assert_eq!(
+            parse("battle sand rain --branch-from 2 --rewind-to 3"),
+            Err(CommandError::ConflictingOptions(
+                "--branch-from",
+                "--rewind-to"
+            ))
+        );
+        assert_eq!(
+            parse("battle sand rain --p2 minimax"),
+            Err(CommandError::Agent(AgentOptionError::UnknownKind(
+                "minimax".to_string()
+            )))
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::domain::model::battle_action::SlotPosition;
+use crate::domain::model::battle_event::{
+    BattleEvent, BattleObserver, CantMoveReason, DamageSource, Effectiveness,
+};
+use crate::domain::model::battle_result::BattleResult;
+use crate::domain::model::field::{Terrain, Weather};
+use crate::domain::model::status::StatusCondition;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// バトルの出来事を1行ずつ文章にして書き出すオブザーバー。
+/// 出来事の多くは場の位置しか持たないため、繰り出されたポケモンの名前を位置ごとに覚えておく
+pub struct EventPrinter<W: Write> {
+    output: W,
+    names: HashMap<SlotPosition, String>,
+}
+
+impl<W: Write> EventPrinter<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            names: HashMap::new(),
+        }
+    }
+
+    fn name(&self, position: SlotPosition) -> String {
+        self.names
+            .get(&position)
+            .cloned()
+            .unwrap_or_else(|| format!("サイド{}の{}番目", position.side, position.slot))
+    }
+
+    fn describe(&self, event: &BattleEvent) -> String {
+        match event {
+            BattleEvent::TurnStarted { turn } => format!("=== ターン {} ===", turn),
+            BattleEvent::MoveUsed {
+                pokemon, move_name, ..
+            } => format!("{} の {}！", pokemon, move_name),
+            BattleEvent::MoveFailed { .. } => "しかし うまく きまらなかった！".to_string(),
+            BattleEvent::CantMove { user, reason } => {
+                let reason = match reason {
+                    CantMoveReason::Asleep => "ぐうぐう ねむっている",
+                    CantMoveReason::Frozen => "こおって しまって うごかない",
+                    CantMoveReason::Paralyzed => "からだが しびれて うごけない",
+                    CantMoveReason::Recharging => "こうげきの はんどうで うごけない",
+                };
+                format!("{} は {}！", self.name(*user), reason)
+            }
+            BattleEvent::Missed { target, .. } => {
+                format!("{} には あたらなかった！", self.name(*target))
+            }
+            BattleEvent::CriticalHit { .. } => "急所に 当たった！".to_string(),
+            BattleEvent::Effectiveness {
+                target,
+                effectiveness,
+            } => match effectiveness {
+                Effectiveness::NoEffect => {
+                    format!("{} には 効果が ないようだ…", self.name(*target))
+                }
+                Effectiveness::NotVeryEffective => "効果は いまひとつの ようだ".to_string(),
+                Effectiveness::SuperEffective => "効果は バツグンだ！".to_string(),
+            },
+            BattleEvent::Damage {
+                pokemon,
+                source,
+                percentage,
+                ..
+            } => {
+                let source = match source {
+                    DamageSource::Move => "",
+                    DamageSource::Recoil => "（反動）",
+                    DamageSource::Status => "（状態異常）",
+                    DamageSource::Weather => "（天気）",
+                    DamageSource::Binding => "（バインド）",
+                };
+                format!("{} に {}% のダメージ{}", pokemon, percentage, source)
+            }
+            BattleEvent::Healed {
+                target,
+                amount,
+                remaining_hp,
+            } => format!(
+                "{} は HP が {} 回復した（残り {}）",
+                self.name(*target),
+                amount,
+                remaining_hp
+            ),
+            BattleEvent::StatusApplied { target, status } => {
+                format!(
+                    "{} は {} になった！",
+                    self.name(*target),
+                    status_name(status)
+                )
+            }
+            BattleEvent::StatusCured { target, status } => {
+                format!(
+                    "{} の {} が 治った！",
+                    self.name(*target),
+                    status_name(status)
+                )
+            }
+            BattleEvent::StatChanged {
+                target,
+                stat,
+                change,
+            } => format!(
+                "{} の {} が {:+} 段階 変化した",
+                self.name(*target),
+                stat.as_str(),
+                change
+            ),
+            BattleEvent::Switched { pokemon, .. } => format!("{} を 繰り出した！", pokemon),
+            BattleEvent::Fainted { pokemon, .. } => format!("{} は たおれた！", pokemon),
+            BattleEvent::WeatherStarted { weather } => {
+                format!("{} が 始まった", weather_name(*weather))
+            }
+            BattleEvent::WeatherEnded { weather } => {
+                format!("{} が 終わった", weather_name(*weather))
+            }
+            BattleEvent::TerrainStarted { terrain } => {
+                format!("{} が 広がった", terrain_name(*terrain))
+            }
+            BattleEvent::TerrainEnded { terrain } => {
+                format!("{} が 消えた", terrain_name(*terrain))
+            }
+            BattleEvent::AbilityActivated { position, ability } => {
+                format!("[{} の {}]", self.name(*position), ability)
+            }
+            BattleEvent::ItemActivated { position, item } => {
+                format!("[{} の {}]", self.name(*position), item)
+            }
+            BattleEvent::EffectStarted { target, effect } => {
+                format!("{} は {} の 状態に なった", self.name(*target), effect)
+            }
+            BattleEvent::Terastallized {
+                position,
+                tera_type,
+            } => format!(
+                "{} は {} タイプに テラスタルした！",
+                self.name(*position),
+                tera_type
+            ),
+            BattleEvent::BattleEnded { result } => match result {
+                BattleResult::Win(side) => format!("サイド{} の 勝ち！", side),
+                BattleResult::Draw => "引き分け".to_string(),
+            },
+        }
+    }
+}
+
+fn status_name(status: &StatusCondition) -> &'static str {
+    match status {
+        StatusCondition::Poison => "どく",
+        StatusCondition::Paralysis => "まひ",
+        StatusCondition::Burn => "やけど",
+        StatusCondition::Freeze => "こおり",
+        StatusCondition::Sleep => "ねむり",
+    }
+}
+
+fn weather_name(weather: Weather) -> &'static str {
+    match weather {
+        Weather::Sun => "ひざしが つよい 状態",
+        Weather::Rain => "あめ",
+        Weather::Sandstorm => "すなあらし",
+        Weather::Snow => "ゆき",
+    }
+}
+
+fn terrain_name(terrain: Terrain) -> &'static str {
+    match terrain {
+        Terrain::Electric => "エレキフィールド",
+        Terrain::Grassy => "グラスフィールド",
+        Terrain::Misty => "ミストフィールド",
+        Terrain::Psychic => "サイコフィールド",
+    }
+}
+
+impl<W: Write> BattleObserver for EventPrinter<W> {
+    fn on_event(&mut self, event: &BattleEvent) {
+        if let BattleEvent::Switched {
+            position, pokemon, ..
+        } = event
+        {
+            self.names.insert(*position, pokemon.clone());
+        }
+        let _ = writeln!(self.output, "{}", self.describe(event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_print_events_with_switched_in_names() {
+        let position = SlotPosition::new(1, 0);
+        let mut output = Vec::new();
+        let mut printer = EventPrinter::new(&mut output);
+        printer.on_events(&[
+            BattleEvent::TurnStarted { turn: 2 },
+            BattleEvent::Switched {
+                position,
+                pokemon: "garchomp".to_string(),
+                party_index: 1,
+            },
+            BattleEvent::StatusApplied {
+                target: position,
+                status: StatusCondition::Burn,
+            },
+            BattleEvent::BattleEnded {
+                result: BattleResult::Win(0),
+            },
+        ]);
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "=== ターン 2 ===\n\
+             garchomp を 繰り出した！\n\
+             garchomp は やけど になった！\n\
+             サイド0 の 勝ち！\n"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::domain::service::type_coverage::TypeCoverageReport;
+
+/// タイプの列の幅
+const TYPE_WIDTH: usize = 10;
+
+/// 端末での表示幅（全角文字は2桁として数える）
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
+
+/// 表示幅が width になるまで右に空白を詰める。`format!` の幅指定は文字数で数えるため全角の見出しに使う
+fn pad_right(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// 倍率の表記（等倍は空欄にして弱点と耐性を目立たせる）
+fn format_multiplier(multiplier: f64) -> &'static str {
+    if multiplier >= 4.0 {
+        "4"
+    } else if multiplier >= 2.0 {
+        "2"
+    } else if multiplier == 0.0 {
+        "0"
+    } else if multiplier <= 0.25 {
+        "1/4"
+    } else if multiplier < 1.0 {
+        "1/2"
+    } else {
+        ""
+    }
+}
+
+/// タイプ相性の一覧を、受けの表・攻めの表・効果抜群を取れないタイプの順に文字列にする
+pub fn render_type_coverage(report: &TypeCoverageReport) -> String {
+    let widths: Vec<usize> = report
+        .members
+        .iter()
+        .map(|member| member.len().max(3))
+        .collect();
+    let mut lines = Vec::new();
+
+    let mut header = pad_right("受け", TYPE_WIDTH);
+    for (member, width) in report.members.iter().zip(&widths) {
+        header.push_str(&format!(" | {:^width$}", member, width = width));
+    }
+    header.push_str(" | 弱点 | 半減 | 無効");
+    lines.push(header);
+    for row in &report.defensive {
+        let mut line = format!("{:<width$}", row.attack_type, width = TYPE_WIDTH);
+        for (multiplier, width) in row.multipliers.iter().zip(&widths) {
+            line.push_str(&format!(
+                " | {:^width$}",
+                format_multiplier(*multiplier),
+                width = width
+            ));
+        }
+        line.push_str(&format!(
+            " | {:>4} | {:>4} | {:>4}",
+            row.weak_count(),
+            row.resist_count(),
+            row.immune_count()
+        ));
+        lines.push(line);
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{} | 最大 | 効果抜群を取れるポケモン",
+        pad_right("攻め", TYPE_WIDTH)
+    ));
+    for row in &report.offensive {
+        lines.push(format!(
+            "{:<width$} | {:^4} | {}",
+            row.defense_type,
+            format_multiplier(row.best_multiplier),
+            row.super_effective_by.join(", "),
+            width = TYPE_WIDTH
+        ));
+    }
+
+    let uncovered = report.uncovered_types();
+    lines.push(String::new());
+    if uncovered.is_empty() {
+        lines.push("すべてのタイプに効果抜群を取れます".to_string());
+    } else {
+        lines.push(format!(
+            "効果抜群を取れないタイプ: {}",
+            uncovered.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::service::type_coverage::{DefensiveRow, OffensiveRow};
+
+    #[test]
+    fn test_render_type_coverage() {
+        let report = TypeCoverageReport {
+            members: vec!["rotom".to_string(), "garchomp".to_string()],
+            defensive: vec![DefensiveRow {
+                attack_type: "ground".to_string(),
+                multipliers: vec![0.0, 1.0],
+            }],
+            offensive: vec![
+                OffensiveRow {
+                    defense_type: "electric".to_string(),
+                    best_multiplier: 2.0,
+                    super_effective_by: vec!["garchomp".to_string()],
+                },
+                OffensiveRow {
+                    defense_type: "flying".to_string(),
+                    best_multiplier: 0.0,
+                    super_effective_by: vec![],
+                },
+            ],
+        };
+
+        let table = render_type_coverage(&report);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[1],
+            "ground     |   0   |          |    0 |    0 |    1"
+        );
+        assert_eq!(
+            lines[0],
+            "受け       | rotom | garchomp | 弱点 | 半減 | 無効"
+        );
+        assert_eq!(lines[3], "攻め       | 最大 | 効果抜群を取れるポケモン");
+        assert_eq!(lines[4], "electric   |  2   | garchomp");
+        assert_eq!(lines[7], "効果抜群を取れないタイプ: flying");
+    }
+}
@@ -3,92 +3,476 @@ mod domain;
 mod infrastructure;
 mod interfaces;
 
+use crate::application::dto::loaded_static_data::LoadedStaticData;
+use crate::application::usecase::generate_random_team::GenerateRandomTeamUsecase;
+use crate::application::usecase::load_battle::LoadBattleUsecase;
+use crate::application::usecase::load_replay::LoadReplayUsecase;
 use crate::application::usecase::load_static_data::LoadStaticDataUsecase;
+use crate::application::usecase::replay_battle::ReplayBattleUsecase;
+use crate::application::usecase::run_battle::{BattleRun, RunBattleUsecase};
+use crate::application::usecase::save_battle::SaveBattleUsecase;
+use crate::application::usecase::save_replay::SaveReplayUsecase;
+use crate::application::usecase::select_party::SelectPartyUsecase;
+use crate::application::usecase::start_new_battle::StartNewBattleUsecase;
+use crate::domain::model::battle::Battle;
+use crate::domain::model::battle_event::{BattleObserver, EventLog};
+use crate::domain::model::battle_format::BattleFormat;
+use crate::domain::model::battle_history::BattleHistory;
+use crate::domain::model::battle_result::BattleResult;
+use crate::domain::model::team::Team;
+use crate::domain::repository::format_repository::FormatRepository;
+use crate::domain::repository::team_repository::TeamRepository;
+use crate::domain::service::battle_agent::BattleAgent;
+use crate::domain::service::battle_engine::BattleEngine;
+use crate::domain::service::showdown_paste::{ShowdownNames, export_team, parse_team};
+use crate::domain::service::type_chart::TypeChart;
+use crate::domain::service::type_coverage::TypeCoverageReport;
 use crate::infrastructure::persistence::file_ability_repository::FileAbilityRepository;
+use crate::infrastructure::persistence::file_battle_snapshot_repository::FileBattleSnapshotRepository;
+use crate::infrastructure::persistence::file_format_repository::FileFormatRepository;
 use crate::infrastructure::persistence::file_item_repository::FileItemRepository;
 use crate::infrastructure::persistence::file_move_repository::FileMoveRepository;
 use crate::infrastructure::persistence::file_pokemon_repository::FilePokemonRepository;
 use crate::infrastructure::persistence::file_pokemon_species_repository::FilePokemonSpeciesRepository;
+use crate::infrastructure::persistence::file_replay_repository::FileReplayRepository;
+use crate::infrastructure::persistence::file_team_repository::FileTeamRepository;
 use crate::infrastructure::persistence::file_type_repository::FileTypeRepository;
+use crate::interfaces::cli::agent_option::{AgentKind, AgentOption};
+use crate::interfaces::cli::command::{
+    BattleCommand, Command, CommandLine, MemberEdit, TeamCommand, USAGE,
+};
+use crate::interfaces::cli::event_printer::EventPrinter;
+use crate::interfaces::cli::type_coverage_table::render_type_coverage;
 use log::info;
+use std::process::ExitCode;
 
-fn main() {
+fn main() -> ExitCode {
     // ロガーの初期化
     env_logger::init();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command_line = match CommandLine::parse(&args) {
+        Ok(command_line) => command_line,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
     info!("アプリケーションを開始します。");
+    let loader = StaticDataLoader {
+        include_unavailable: command_line.include_unavailable,
+    };
+    let teams = match &command_line.team_dir {
+        Some(directory) => FileTeamRepository::new(directory),
+        None => FileTeamRepository::default(),
+    };
+    let result = match command_line.command {
+        Command::Battle(command) => battle(&loader, &teams, command),
+        Command::Resume {
+            snapshot,
+            agents,
+            turn_limit,
+            replay,
+        } => resume(&loader, &snapshot, agents, turn_limit, replay.as_deref()),
+        Command::Replay(name) => replay(&loader, &name),
+        Command::Formats => formats(),
+        Command::Team(command) => team(&loader, &teams, command),
+    };
+    info!("アプリケーションを終了します。");
 
-    // 1. 具体的なリポジトリ実装のインスタンス化
-    let item_repository = FileItemRepository;
-    let pokemon_repository = FilePokemonRepository;
-    let move_repository = FileMoveRepository;
-    let pokemon_species_repository = FilePokemonSpeciesRepository;
-    let type_repository = FileTypeRepository;
-    let ability_repository = FileAbilityRepository;
-
-    // 2. ユースケースにリポジトリを注入（DI: Dependency Injection）
-    let load_static_data_use_case = LoadStaticDataUsecase::new(
-        item_repository,
-        pokemon_repository,
-        move_repository,
-        pokemon_species_repository,
-        type_repository,
-        ability_repository,
-    );
-
-    // 3. ユースケースの実行
-    let loaded_data = load_static_data_use_case.execute();
-
-    info!("ロードされたアイテムの数: {}", loaded_data.items.len());
-    if let Some(sample_item) = loaded_data.items.first() {
-        info!(
-            "アイテムサンプル出力: ID: {}, 名前: {}",
-            sample_item.id, sample_item.name
-        );
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
     }
+}
+
+/// コマンドラインで指定した読み込み方で図鑑データを読み込む
+struct StaticDataLoader {
+    include_unavailable: bool,
+}
+
+impl StaticDataLoader {
+    fn load(&self) -> LoadedStaticData {
+        // 具体的なリポジトリ実装をユースケースに注入する（DI: Dependency Injection）
+        let loaded_data = LoadStaticDataUsecase::new(
+            FileItemRepository,
+            FilePokemonRepository,
+            FileMoveRepository,
+            FilePokemonSpeciesRepository,
+            FileTypeRepository,
+            FileAbilityRepository,
+        )
+        .with_unavailable(self.include_unavailable)
+        .execute();
 
-    info!("ロードされたポケモンの数: {}", loaded_data.pokemons.len());
-    if let Some(sample_pokemon) = loaded_data.pokemons.first() {
         info!(
-            "ポケモンサンプル出力: ID: {}, 名前: {}",
-            sample_pokemon.id, sample_pokemon.name
+            "ロードしたデータ: アイテム {}, ポケモン {}, 技 {}, ポケモンの種類 {}, タイプ {}, 特性 {}",
+            loaded_data.items.len(),
+            loaded_data.pokemons.len(),
+            loaded_data.moves.len(),
+            loaded_data.pokemon_species.len(),
+            loaded_data.types.len(),
+            loaded_data.abilities.len()
         );
+        loaded_data
     }
+}
 
-    info!("ロードされた技の数: {}", loaded_data.moves.len());
-    if let Some(sample_move) = loaded_data.moves.first() {
-        info!(
-            "技サンプル出力: ID: {}, 名前: {}",
-            sample_move.id, sample_move.name
-        );
+fn showdown_names(data: &LoadedStaticData) -> ShowdownNames {
+    ShowdownNames::new(
+        &data.pokemons,
+        &data.pokemon_species,
+        &data.items,
+        &data.abilities,
+        &data.moves,
+        &data.types,
+    )
+}
+
+/// 保存したパーティ同士で見せ合いから決着まで戦う
+fn battle(
+    loader: &StaticDataLoader,
+    teams: &FileTeamRepository,
+    command: BattleCommand,
+) -> Result<(), String> {
+    let data = loader.load();
+    let select_party = SelectPartyUsecase::new(&data);
+    let format = command
+        .format
+        .as_deref()
+        .map(|id| FileFormatRepository::default().find_format(id))
+        .transpose()
+        .map_err(|error| error.to_string())?;
+
+    let mut parties = Vec::with_capacity(2);
+    for name in &command.teams {
+        let team = teams.find_team(name).map_err(|error| error.to_string())?;
+        let party = match &format {
+            Some(format) => select_party.to_format_party(&team, format),
+            None => select_party.to_battle_party(&team),
+        }
+        .map_err(|error| format!("{}: {}", team.name, error))?;
+        parties.push((team.name, party));
     }
+    let [first, second] = <[_; 2]>::try_from(parties).expect("two teams");
 
-    info!(
-        "ロードされたポケモンの種類の数: {}",
-        loaded_data.pokemon_species.len()
-    );
-    if let Some(sample_species) = loaded_data.pokemon_species.first() {
-        info!(
-            "ポケモン種類サンプル出力: ID: {}, 名前: {}",
-            sample_species.id, sample_species.name
-        );
+    let start = match &format {
+        Some(format) => StartNewBattleUsecase::for_format(format),
+        None => StartNewBattleUsecase::new(BattleFormat::Singles),
     }
+    .with_team_sheet(command.team_sheet)
+    .with_seed(command.seed);
+    let (type_chart, engine) = battle_engine(&data, command.turn_limit);
+    let [mut first_agent, mut second_agent] = command
+        .agents
+        .map(|agent| agent.build(&engine, &type_chart, &data));
+    let battle = start
+        .execute(
+            [first, second],
+            [first_agent.as_mut(), second_agent.as_mut()],
+        )
+        .map_err(|error| error.to_string())?;
 
-    info!("ロードされたタイプの数: {}", loaded_data.types.len());
-    if let Some(sample_type) = loaded_data.types.first() {
-        info!(
-            "タイプサンプル出力: ID: {}, 名前: {}",
-            sample_type.id, sample_type.name
-        );
+    let run = play(
+        &engine,
+        BattleHistory::new(&battle),
+        [first_agent, second_agent],
+        command.agents,
+    )?;
+    if let Some(name) = &command.snapshot {
+        let turn = command
+            .snapshot_turn
+            .unwrap_or_else(|| run.history.latest_turn());
+        let battle = run
+            .history
+            .state_at(run.history.current_branch(), turn)
+            .ok_or_else(|| format!("ターン{}の状態は記録されていません", turn))?;
+        SaveBattleUsecase::new(FileBattleSnapshotRepository::default())
+            .execute(name, battle)
+            .map_err(|error| error.to_string())?;
+        println!("ターン{}の状態を {} に保存しました", turn, name);
     }
+    save_replay(&run, command.replay.as_deref())?;
 
-    info!("ロードされた特性の数: {}", loaded_data.abilities.len());
-    if let Some(sample_ability) = loaded_data.abilities.first() {
-        info!(
-            "特性サンプル出力: ID: {}, 名前: {}",
-            sample_ability.id, sample_ability.name
-        );
+    let mut history = run.history;
+    if let Some(turn) = command.rewind_to {
+        history
+            .rewind(turn)
+            .ok_or_else(|| format!("ターン{}の状態は記録されていません", turn))?;
+        println!("ターン{}まで巻き戻して戦い直します", turn);
+        let agents = command
+            .agents
+            .map(|agent| agent.build(&engine, &type_chart, &data));
+        play(&engine, history, agents, command.agents)?;
+        return Ok(());
+    }
+    let Some(turn) = command.branch_from else {
+        return Ok(());
+    };
+    history
+        .branch_from(turn)
+        .ok_or_else(|| format!("ターン{}の状態は記録されていません", turn))?;
+    println!("ターン{}から分岐して戦い直します", turn);
+    let agents = command
+        .agents
+        .map(|agent| agent.build(&engine, &type_chart, &data));
+    let mut history = play(&engine, history, agents, command.agents)?.history;
+    for branch in 0..history.branch_count() {
+        if let Some(battle) = history.switch_branch(branch) {
+            println!(
+                "分岐{}: {}",
+                branch,
+                describe_result(battle.result, &battle)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 保存したバトルの途中の状態から決着まで戦う
+fn resume(
+    loader: &StaticDataLoader,
+    snapshot: &str,
+    agents: [AgentOption; 2],
+    turn_limit: Option<u32>,
+    replay: Option<&str>,
+) -> Result<(), String> {
+    let data = loader.load();
+    let battle = LoadBattleUsecase::new(FileBattleSnapshotRepository::default())
+        .execute(snapshot)
+        .map_err(|error| error.to_string())?;
+    let (type_chart, engine) = battle_engine(&data, turn_limit);
+    let built = agents.map(|agent| agent.build(&engine, &type_chart, &data));
+    let run = play(&engine, BattleHistory::new(&battle), built, agents)?;
+    save_replay(&run, replay)
+}
+
+/// バトルを打ち切るターン数を指定した場合はエンジンに反映する
+fn battle_engine(data: &LoadedStaticData, turn_limit: Option<u32>) -> (TypeChart, BattleEngine) {
+    let type_chart = TypeChart::new(&data.types);
+    let engine = BattleEngine::new(type_chart.clone());
+    let engine = match turn_limit {
+        Some(turn_limit) => engine.with_turn_limit(turn_limit),
+        None => engine,
+    };
+    (type_chart, engine)
+}
+
+fn describe_result(result: Option<BattleResult>, battle: &Battle) -> String {
+    match result {
+        Some(BattleResult::Win(side)) => format!("{} の勝ち", battle.sides[side].name),
+        Some(BattleResult::Draw) => "引き分け".to_string(),
+        None => "決着していません".to_string(),
     }
+}
 
-    info!("アプリケーションを終了します。");
+/// 最初の人間のプレイヤー（いなければ1人目）から見た出来事を表示しながら、
+/// 記録の最新の状態から決着まで進める
+fn play(
+    engine: &BattleEngine,
+    history: BattleHistory,
+    agents: [Box<dyn BattleAgent>; 2],
+    options: [AgentOption; 2],
+) -> Result<BattleRun, String> {
+    let shown_side = options
+        .iter()
+        .position(|option| option.kind == AgentKind::Human)
+        .unwrap_or(0);
+    let mut printer = EventPrinter::new(std::io::stdout());
+    let mut log = EventLog::default();
+    let observers: [&mut dyn BattleObserver; 2] = if shown_side == 0 {
+        [&mut printer, &mut log]
+    } else {
+        [&mut log, &mut printer]
+    };
+
+    let [mut first, mut second] = agents;
+    let run = RunBattleUsecase::new(engine.clone())
+        .execute(history, [first.as_mut(), second.as_mut()], observers)
+        .map_err(|error| format!("バトルを進められません: {:?}", error))?;
+    let last = run
+        .history
+        .state_at(run.history.current_branch(), run.history.latest_turn())
+        .expect("finished battle is recorded");
+    println!("{}です", describe_result(Some(run.result), last));
+    Ok(run)
+}
+
+fn save_replay(run: &BattleRun, name: Option<&str>) -> Result<(), String> {
+    let Some(name) = name else {
+        return Ok(());
+    };
+    SaveReplayUsecase::new(FileReplayRepository::default())
+        .execute(name, &run.replay)
+        .map_err(|error| error.to_string())?;
+    println!("リプレイを {} に保存しました", name);
+    Ok(())
+}
+
+/// 保存したリプレイの出来事を表示し、エンジンで再実行して記録と一致するかを確かめる
+fn replay(loader: &StaticDataLoader, name: &str) -> Result<(), String> {
+    let data = loader.load();
+    let replay = LoadReplayUsecase::new(FileReplayRepository::default())
+        .execute(name)
+        .map_err(|error| error.to_string())?;
+    let mut printer = EventPrinter::new(std::io::stdout());
+    for turn in &replay.turns {
+        printer.on_events(&turn.events);
+    }
+    ReplayBattleUsecase::new(BattleEngine::new(TypeChart::new(&data.types)))
+        .execute(&replay)
+        .map_err(|error| error.to_string())?;
+    println!("リプレイは記録どおりに再現されました");
+    Ok(())
+}
+
+fn formats() -> Result<(), String> {
+    for format in FileFormatRepository::default().find_all_formats() {
+        println!("{}: {}", format.id, format.name);
+    }
+    Ok(())
+}
+
+fn team(
+    loader: &StaticDataLoader,
+    teams: &FileTeamRepository,
+    command: TeamCommand,
+) -> Result<(), String> {
+    match command {
+        TeamCommand::List { folder } => {
+            let found = match folder {
+                Some(folder) => teams.find_teams_in_folder(&folder),
+                None => teams.find_all_teams(),
+            }
+            .map_err(|error| error.to_string())?;
+            for team in found {
+                let species: Vec<&str> = team
+                    .members
+                    .iter()
+                    .map(|member| member.display_name())
+                    .collect();
+                println!("{}: {}", team.name, species.join(", "));
+            }
+        }
+        TeamCommand::Show(name) => {
+            let team = teams.find_team(&name).map_err(|error| error.to_string())?;
+            let data = loader.load();
+            print!("{}", export_team(&team, &showdown_names(&data)));
+        }
+        TeamCommand::Import { name, path } => {
+            let text = std::fs::read_to_string(&path)
+                .map_err(|error| format!("{} を読み込めません: {}", path, error))?;
+            let data = loader.load();
+            let pasted = parse_team(&name, &text, &showdown_names(&data))
+                .map_err(|error| error.to_string())?;
+            let team = SelectPartyUsecase::new(&data)
+                .create_team(&name, pasted.members)
+                .map_err(|error| error.to_string())?;
+            teams.save_team(&team).map_err(|error| error.to_string())?;
+            println!("{} を保存しました（{}体）", team.name, team.members.len());
+        }
+        TeamCommand::Delete(name) => {
+            teams
+                .delete_team(&name)
+                .map_err(|error| error.to_string())?;
+            println!("{} を削除しました", name);
+        }
+        TeamCommand::Add { name, path } => {
+            let text = std::fs::read_to_string(&path)
+                .map_err(|error| format!("{} を読み込めません: {}", path, error))?;
+            edit_team(loader, teams, &name, |data, select_party, team| {
+                let pasted = parse_team(&name, &text, &showdown_names(data))
+                    .map_err(|error| error.to_string())?;
+                for member in pasted.members {
+                    select_party
+                        .add_member(team, member)
+                        .map_err(|error| error.to_string())?;
+                }
+                Ok(())
+            })?;
+        }
+        TeamCommand::Remove { name, index } => {
+            edit_team(loader, teams, &name, |_, select_party, team| {
+                let removed = select_party
+                    .remove_member(team, index)
+                    .map_err(|error| error.to_string())?;
+                println!("{} を外しました", removed.display_name());
+                Ok(())
+            })?
+        }
+        TeamCommand::Lead { name, index } => {
+            edit_team(loader, teams, &name, |_, select_party, team| {
+                select_party
+                    .set_lead(team, index)
+                    .map_err(|error| error.to_string())
+            })?
+        }
+        TeamCommand::Set { name, index, edit } => {
+            edit_team(loader, teams, &name, |_, select_party, team| {
+                match &edit {
+                    MemberEdit::Ability(ability) => select_party.set_ability(team, index, ability),
+                    MemberEdit::Item(item) => select_party.set_item(team, index, item.as_deref()),
+                    MemberEdit::TeraType(tera_type) => {
+                        select_party.set_tera_type(team, index, tera_type.as_deref())
+                    }
+                    MemberEdit::Moves(moves) => {
+                        let moves: Vec<&str> = moves.iter().map(String::as_str).collect();
+                        select_party.set_moves(team, index, &moves)
+                    }
+                    MemberEdit::Evs(evs) => select_party.set_evs(team, index, *evs),
+                }
+                .map_err(|error| error.to_string())
+            })?
+        }
+        TeamCommand::Tag { name, folder } => teams
+            .add_to_folder(&name, &folder)
+            .map_err(|error| error.to_string())?,
+        TeamCommand::Untag { name, folder } => teams
+            .remove_from_folder(&name, &folder)
+            .map_err(|error| error.to_string())?,
+        TeamCommand::Random { name, format, seed } => {
+            let format = FileFormatRepository::default()
+                .find_format(&format)
+                .map_err(|error| error.to_string())?;
+            let data = loader.load();
+            let mut team = GenerateRandomTeamUsecase::new(&data)
+                .with_seed(seed)
+                .execute(&format)
+                .map_err(|error| error.to_string())?;
+            team.name = name;
+            teams.save_team(&team).map_err(|error| error.to_string())?;
+            print!("{}", export_team(&team, &showdown_names(&data)));
+        }
+        TeamCommand::Coverage(name) => {
+            let team = teams.find_team(&name).map_err(|error| error.to_string())?;
+            let data = loader.load();
+            let party = SelectPartyUsecase::new(&data)
+                .to_battle_party(&team)
+                .map_err(|error| error.to_string())?;
+            println!(
+                "{}",
+                render_type_coverage(&TypeCoverageReport::new(&party, &data.types))
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 保存したパーティを図鑑データに照らして編集し、編集に成功した場合だけ保存し直す
+fn edit_team(
+    loader: &StaticDataLoader,
+    teams: &FileTeamRepository,
+    name: &str,
+    edit: impl FnOnce(&LoadedStaticData, &SelectPartyUsecase, &mut Team) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut team = teams.find_team(name).map_err(|error| error.to_string())?;
+    let data = loader.load();
+    edit(&data, &SelectPartyUsecase::new(&data), &mut team)?;
+    teams.save_team(&team).map_err(|error| error.to_string())?;
+    print!("{}", export_team(&team, &showdown_names(&data)));
+    Ok(())
 }